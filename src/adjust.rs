@@ -0,0 +1,100 @@
+use std::time::{Duration, SystemTime};
+
+use ssr_core::{task::Task, tasks_facade::TaskId};
+
+use crate::{Facade, ReviewRecord, TaskWrapper};
+
+impl<'a, T: Task<'a>> Facade<'a, T> {
+    /// Minimum interval applied to tasks without their own.
+    pub fn min_interval(&self) -> Duration {
+        self.min_interval
+    }
+    /// Never schedule a task sooner than `min_interval` after its review.
+    /// Protects against degenerate loops when the model outputs near-zero
+    /// intervals. A minimum reaching past the representable time range is
+    /// ignored.
+    pub fn set_min_interval(&mut self, min_interval: Duration) {
+        self.min_interval = min_interval;
+    }
+    /// Override facade-wide minimum interval for one task, `None` resets it.
+    /// Returns whether the task exists.
+    pub fn set_task_min_interval(&mut self, id: TaskId, min_interval: Option<Duration>) -> bool {
+        let Some(task) = self.find_task_mut(id) else {
+            return false;
+        };
+        task.min_interval = min_interval;
+        true
+    }
+    /// Cap on interval growth per review, see
+    /// [`Facade::set_max_interval_growth`].
+    pub fn max_interval_growth(&self) -> Option<f64> {
+        self.max_interval_growth
+    }
+    /// Never give a task more than `factor` times the interval its previous
+    /// review assigned, guarding against outliers of the model after
+    /// irregular reviews. Factors below 1 are treated as 1, `None` disables
+    /// the cap. First reviews aren't capped.
+    pub fn set_max_interval_growth(&mut self, factor: Option<f64>) {
+        self.max_interval_growth = factor.map(|factor| factor.max(1.));
+    }
+
+    /// Interval assigned by the last review that changed the task's schedule.
+    pub(crate) fn last_interval(&self, id: TaskId) -> Option<Duration> {
+        self.review_log
+            .for_task(id)
+            .rfind(|r| r.source.changes_schedule())
+            .map(ReviewRecord::next_interval)
+    }
+
+    /// Due time the facade stores after a review, given the scheduler's one.
+    pub(crate) fn adjust_next_due(
+        &self,
+        task: &TaskWrapper<T>,
+        reviewed_at: SystemTime,
+        next_due: SystemTime,
+        previous_interval: Option<Duration>,
+    ) -> SystemTime {
+        let next_due = match self.multiplier_of(task) {
+            Some(multiplier) => {
+                let interval = next_due.duration_since(reviewed_at).unwrap_or_default();
+                Duration::try_from_secs_f64(interval.as_secs_f64() * multiplier)
+                    .ok()
+                    .and_then(|interval| reviewed_at.checked_add(interval))
+                    .unwrap_or(next_due)
+            }
+            None => next_due,
+        };
+        let next_due = match (self.max_interval_growth, previous_interval) {
+            (Some(factor), Some(previous)) => {
+                Duration::try_from_secs_f64(previous.as_secs_f64() * factor)
+                    .ok()
+                    .and_then(|longest| reviewed_at.checked_add(longest))
+                    .map_or(next_due, |latest| next_due.min(latest))
+            }
+            _ => next_due,
+        };
+        let earliest = reviewed_at
+            .checked_add(task.min_interval.unwrap_or(self.min_interval))
+            .unwrap_or(next_due);
+        let next_due = next_due.max(earliest);
+        let (Some(spacing), Some(sibling)) = (
+            self.sibling_spacing,
+            task.sibling.and_then(|id| self.find_task(id)),
+        ) else {
+            return next_due;
+        };
+        let sibling_due = self.next_repetition(sibling);
+        let distance = match next_due.duration_since(sibling_due) {
+            Ok(after) => after,
+            Err(before) => before.duration(),
+        };
+        if distance >= spacing {
+            return next_due;
+        }
+        // Move to the nearer side of the sibling, but never before `earliest`.
+        match sibling_due.checked_sub(spacing) {
+            Some(sooner) if next_due < sibling_due && sooner >= earliest => sooner,
+            _ => sibling_due.checked_add(spacing).unwrap_or(next_due),
+        }
+    }
+}
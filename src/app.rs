@@ -0,0 +1,199 @@
+use std::{
+    fmt, fs,
+    io::{self, Read, Write},
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use ssr_core::{
+    task::Task,
+    tasks_facade::{self, TaskId, TasksFacade},
+};
+
+use crate::{BackupPolicy, Facade, backup::write_atomically};
+
+/// Serializes the facade, see [`Facade::save_with`].
+pub type WriteFn<'a, T> = fn(&Facade<'a, T>, &mut dyn Write) -> io::Result<()>;
+/// Deserializes the facade, see [`Facade::load_with`].
+pub type ReadFn<'a, T> = fn(&mut dyn Read) -> io::Result<Facade<'a, T>>;
+
+#[derive(Debug, Clone)]
+pub struct AppConfig {
+    /// Save after this many reviews. `0` saves only on [`App::save`] and
+    /// [`App::close`].
+    pub autosave_every: usize,
+    /// Backup written every time the file is opened.
+    pub backup: Option<BackupPolicy>,
+    /// Retention target of a newly created deck.
+    pub desired_retention: f64,
+}
+
+impl Default for AppConfig {
+    fn default() -> Self {
+        Self {
+            autosave_every: 1,
+            backup: None,
+            desired_retention: 0.9,
+        }
+    }
+}
+
+/// Deck stored in a single file, with autosave, backups and day rollover
+/// wired up. Meant for simple front-ends; use [`Facade`] directly for
+/// anything else.
+pub struct App<'a, T: Task<'a>> {
+    facade: Facade<'a, T>,
+    path: PathBuf,
+    config: AppConfig,
+    write: WriteFn<'a, T>,
+    /// Reviews and changes since the last save.
+    unsaved: usize,
+}
+
+#[derive(Debug)]
+pub enum AppError {
+    Io(io::Error),
+    Facade(tasks_facade::Error),
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => err.fmt(f),
+            Self::Facade(err) => err.fmt(f),
+        }
+    }
+}
+impl std::error::Error for AppError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            Self::Facade(err) => Some(err),
+        }
+    }
+}
+impl From<io::Error> for AppError {
+    fn from(value: io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+impl From<tasks_facade::Error> for AppError {
+    fn from(value: tasks_facade::Error) -> Self {
+        Self::Facade(value)
+    }
+}
+
+impl<'a, T: Task<'a>> App<'a, T> {
+    /// Load the deck from `path` with [`Facade::load_with`], or create an
+    /// empty one named after the file if it doesn't exist. Rolls the day over
+    /// and writes the backup configured in `config`.
+    /// # Errors
+    /// If the file exists but can't be loaded, or the backup fails. Files
+    /// written by a newer version of the crate are refused, since autosave
+    /// would drop what this version doesn't know; open them with
+    /// [`Facade::load_with_policy`] instead.
+    pub fn open(
+        path: impl Into<PathBuf>,
+        config: AppConfig,
+        read: ReadFn<'a, T>,
+        write: WriteFn<'a, T>,
+    ) -> io::Result<Self> {
+        let path = path.into();
+        let mut facade = match Facade::load_with(|| fs::read(&path), read) {
+            Ok(facade) => facade,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                Facade::new(deck_name(&path), config.desired_retention)
+            }
+            Err(err) => return Err(err),
+        };
+        facade.rollover_if_needed();
+        if let Some(BackupPolicy { dir, keep_n }) = &config.backup {
+            facade.backup(dir, *keep_n, write)?;
+        }
+        Ok(Self {
+            facade,
+            path,
+            config,
+            write,
+            unsaved: 0,
+        })
+    }
+
+    /// Complete one due task and autosave as configured.
+    /// # Errors
+    /// If there is no task to complete, `interaction` fails or autosave
+    /// fails. A failed autosave keeps the review; it is saved next time.
+    pub fn review(
+        &mut self,
+        interaction: &mut impl FnMut(
+            TaskId,
+            s_text_input_f::Blocks,
+        ) -> io::Result<s_text_input_f::Response>,
+    ) -> Result<(), AppError> {
+        self.facade.rollover_if_needed();
+        self.facade.complete_task(interaction)?;
+        self.unsaved += 1;
+        if self.config.autosave_every > 0 && self.unsaved >= self.config.autosave_every {
+            self.save()?;
+        }
+        Ok(())
+    }
+
+    /// Roll the day over if needed, see [`Facade::rollover_if_needed`].
+    /// Meant to be called periodically, e.g. from a timer.
+    pub fn tick(&mut self) -> bool {
+        self.facade.rollover_if_needed()
+    }
+
+    /// When the front-end should notify the user about due tasks: now if
+    /// something is due, `None` if the deck is empty.
+    #[must_use]
+    pub fn next_notification(&self) -> Option<SystemTime> {
+        self.facade
+            .until_next_repetition()
+            .map(|until| self.facade.now() + until)
+    }
+
+    /// Write the deck to its file, replacing it atomically.
+    /// # Errors
+    /// If serialization or any filesystem operation fails.
+    pub fn save(&mut self) -> io::Result<()> {
+        write_atomically(&self.path, |writer| (self.write)(&self.facade, writer))?;
+        self.unsaved = 0;
+        Ok(())
+    }
+
+    /// Save and hand out the facade.
+    /// # Errors
+    /// If saving fails; the app is returned with the error so nothing is lost.
+    pub fn close(mut self) -> Result<Facade<'a, T>, Box<(Self, io::Error)>> {
+        match self.save() {
+            Ok(()) => Ok(self.facade),
+            Err(err) => Err(Box::new((self, err))),
+        }
+    }
+
+    #[must_use]
+    pub fn facade(&self) -> &Facade<'a, T> {
+        &self.facade
+    }
+    /// Changes made through it are saved with the next save.
+    pub fn facade_mut(&mut self) -> &mut Facade<'a, T> {
+        self.unsaved += 1;
+        &mut self.facade
+    }
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+    /// Whether something changed since the last save.
+    #[must_use]
+    pub fn has_unsaved_changes(&self) -> bool {
+        self.unsaved > 0
+    }
+}
+
+fn deck_name(path: &Path) -> String {
+    path.file_stem()
+        .map_or_else(String::new, |stem| stem.to_string_lossy().into_owned())
+}
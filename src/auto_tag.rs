@@ -0,0 +1,72 @@
+use serde::{Deserialize, Serialize};
+use ssr_core::{task::Task, tasks_facade::TaskId};
+
+use crate::{Facade, Outcome};
+
+pub const LEECH_TAG: &str = "auto::leech";
+pub const EASY_TAG: &str = "auto::easy";
+
+/// Thresholds for tags maintained by the facade itself. Tags of disabled
+/// rules are removed.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct AutoTagPolicy {
+    /// Tag task with [`LEECH_TAG`] once it lapsed this many times.
+    pub leech_lapses: Option<usize>,
+    /// Tag task with [`EASY_TAG`] while this many of its latest reviews passed.
+    pub easy_streak: Option<usize>,
+}
+
+impl<'a, T: Task<'a>> Facade<'a, T> {
+    pub fn auto_tag_policy(&self) -> &AutoTagPolicy {
+        &self.auto_tag_policy
+    }
+    /// Set policy and re-evaluate automatic tags of all tasks.
+    pub fn set_auto_tag_policy(&mut self, policy: AutoTagPolicy) {
+        self.auto_tag_policy = policy;
+        let ids: Vec<_> = self.iter_ids().collect();
+        for id in ids {
+            self.update_auto_tags(id);
+        }
+    }
+
+    pub(crate) fn update_auto_tags(&mut self, id: TaskId) {
+        let history = self
+            .review_log
+            .for_task(id)
+            .filter(|r| r.source.changes_schedule());
+        let lapses = history
+            .clone()
+            .filter(|r| r.outcome == Outcome::Lapse)
+            .count();
+        let streak = history
+            .rev()
+            .take_while(|r| r.outcome == Outcome::Pass)
+            .count();
+
+        let AutoTagPolicy {
+            leech_lapses,
+            easy_streak,
+        } = self.auto_tag_policy;
+        let is_leech = leech_lapses.is_some_and(|threshold| lapses >= threshold);
+        let is_easy = easy_streak.is_some_and(|threshold| streak >= threshold);
+
+        let tags = [(LEECH_TAG, is_leech), (EASY_TAG, is_easy)];
+        let changed = self.find_task(id).is_some_and(|task| {
+            tags.iter()
+                .any(|&(tag, enabled)| task.tags.contains(tag) != enabled)
+        });
+        if !changed {
+            return;
+        }
+        let Some(task) = self.find_task_mut(id) else {
+            return;
+        };
+        for (tag, enabled) in tags {
+            if enabled {
+                task.tags.insert(tag.to_owned());
+            } else {
+                task.tags.remove(tag);
+            }
+        }
+    }
+}
@@ -0,0 +1,127 @@
+use std::{
+    cmp::Reverse,
+    fs::{self, File},
+    io::{self, BufReader, BufWriter, Read, Write},
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use ssr_core::task::Task;
+
+use crate::Facade;
+
+const BACKUP_EXTENSION: &str = "ssr-backup";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BackupInfo {
+    pub path: PathBuf,
+    pub created: SystemTime,
+}
+
+impl<'a, T: Task<'a>> Facade<'a, T> {
+    /// Write a timestamped snapshot into `dir` and remove all but the `keep_n`
+    /// newest ones. The new snapshot is always kept and counts towards
+    /// `keep_n`, so 0 keeps just it. Snapshots taken within the same
+    /// millisecond get a counter suffix instead of replacing each other.
+    ///
+    /// The crate is format-agnostic, so `write` is responsible for serializing
+    /// the facade. The snapshot is written to a temporary file first and
+    /// renamed afterwards, so an interrupted backup never leaves a truncated
+    /// snapshot behind.
+    /// # Errors
+    /// If any filesystem operation or `write` fails.
+    pub fn backup(
+        &self,
+        dir: impl AsRef<Path>,
+        keep_n: usize,
+        write: impl FnOnce(&Self, &mut dyn Write) -> io::Result<()>,
+    ) -> io::Result<PathBuf> {
+        let dir = dir.as_ref();
+        fs::create_dir_all(dir)?;
+
+        let millis = self
+            .now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis();
+        let mut path = dir.join(format!("{millis}.{BACKUP_EXTENSION}"));
+        let mut counter = 0;
+        while path.exists() {
+            counter += 1;
+            path = dir.join(format!("{millis}-{counter}.{BACKUP_EXTENSION}"));
+        }
+        write_atomically(&path, |writer| write(self, writer))?;
+
+        let old = Self::list_backups(dir)?
+            .into_iter()
+            .filter(|backup| backup.path != path);
+        for old in old.skip(keep_n.saturating_sub(1)) {
+            fs::remove_file(old.path)?;
+        }
+        Ok(path)
+    }
+
+    /// Backups found in `dir`, newest first.
+    /// # Errors
+    /// If `dir` can't be read.
+    pub fn list_backups(dir: impl AsRef<Path>) -> io::Result<Vec<BackupInfo>> {
+        let mut backups = Vec::new();
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.extension().is_none_or(|x| x != BACKUP_EXTENSION) {
+                continue;
+            }
+            let Some((millis, counter)) = path
+                .file_stem()
+                .and_then(|x| x.to_str())
+                .and_then(parse_backup_stem)
+            else {
+                continue;
+            };
+            let created = UNIX_EPOCH + Duration::from_millis(millis);
+            backups.push((BackupInfo { path, created }, counter));
+        }
+        backups.sort_by_key(|(info, counter)| Reverse((info.created, *counter)));
+        Ok(backups.into_iter().map(|(info, _)| info).collect())
+    }
+
+    /// Load a snapshot previously written by [`Facade::backup`] with
+    /// [`Facade::load`], using `read` to deserialize it.
+    /// # Errors
+    /// If the file can't be opened, `read` fails or the snapshot was written
+    /// by a newer version of the crate; the latter is
+    /// [`io::ErrorKind::InvalidData`] wrapping
+    /// [`NewerFormatError`](crate::NewerFormatError).
+    pub fn restore_backup(
+        path: impl AsRef<Path>,
+        read: impl FnOnce(&mut dyn Read) -> io::Result<Self>,
+    ) -> io::Result<Self> {
+        let (facade, _) = Self::load(BufReader::new(File::open(path)?), read)?;
+        Ok(facade)
+    }
+}
+
+/// Milliseconds and counter of a backup file stem like `1700000000000` or
+/// `1700000000000-2`.
+fn parse_backup_stem(stem: &str) -> Option<(u64, u32)> {
+    match stem.split_once('-') {
+        Some((millis, counter)) => Some((millis.parse().ok()?, counter.parse().ok()?)),
+        None => Some((stem.parse().ok()?, 0)),
+    }
+}
+
+/// Write into a temporary file next to `path` and rename it afterwards, so an
+/// interrupted write never leaves a truncated file behind.
+pub(crate) fn write_atomically(
+    path: &Path,
+    write: impl FnOnce(&mut dyn Write) -> io::Result<()>,
+) -> io::Result<()> {
+    let tmp_path = path.with_extension("tmp");
+    let mut writer = BufWriter::new(File::create(&tmp_path)?);
+    write(&mut writer)?;
+    writer
+        .into_inner()
+        .map_err(io::IntoInnerError::into_error)?
+        .sync_all()?;
+    fs::rename(&tmp_path, path)
+}
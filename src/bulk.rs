@@ -0,0 +1,81 @@
+use std::{
+    collections::HashSet,
+    time::{Duration, SystemTime},
+};
+
+use ssr_core::{task::Task, tasks_facade::TasksFacade};
+
+use crate::{Facade, OverrideKind, Query};
+
+/// Change applied to every task selected by a [`Query`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Action {
+    /// Exclude from scheduling until unsuspended.
+    Suspend,
+    Unsuspend,
+    /// Hide from reviews until the given time without changing the schedule.
+    Bury(SystemTime),
+    Unbury,
+    AddTag(String),
+    RemoveTag(String),
+    SetPriority(i32),
+    /// Push due time later without completing the task. Tasks whose due
+    /// time would overflow keep it.
+    Postpone(Duration),
+    Delete,
+}
+
+impl<'a, T: Task<'a>> Facade<'a, T> {
+    /// Apply `action` to all tasks matching `query` at once.
+    /// Returns number of affected tasks, which leaves out tasks a postpone
+    /// doesn't change, see [`Action::Postpone`].
+    pub fn apply_to_query(&mut self, query: &Query, action: &Action) -> usize {
+        let ids = self.query(query);
+        match action {
+            Action::Delete => {
+                return ids.into_iter().filter(|&id| self.remove(id)).count();
+            }
+            Action::Postpone(by) => {
+                let postponed = (ids.into_iter())
+                    .filter(|&id| {
+                        self.override_due(id, OverrideKind::Postpone, |due| due.checked_add(*by))
+                    })
+                    .count();
+                self.reload_all_tasks_timings();
+                return postponed;
+            }
+            _ => {}
+        }
+
+        let selected: HashSet<_> = ids.iter().copied().collect();
+        let epoch = self.next_epoch();
+        let now = self.now();
+        for task in self
+            .tasks_pool
+            .iter_mut()
+            .chain(self.tasks_to_recall.iter_mut())
+            .filter(|task| selected.contains(&task.id))
+        {
+            task.modified = epoch;
+            match action {
+                Action::Suspend => task.suspended = true,
+                Action::Unsuspend => task.suspended = false,
+                Action::Bury(until) => task.buried_until = Some(*until),
+                Action::Unbury => task.buried_until = None,
+                Action::AddTag(tag) => {
+                    task.tags.insert(tag.clone());
+                }
+                Action::RemoveTag(tag) => {
+                    task.tags.remove(tag);
+                }
+                Action::SetPriority(priority) => {
+                    task.priority = *priority;
+                    task.priority_set_at = Some(now);
+                }
+                Action::Postpone(_) | Action::Delete => unreachable!("handled above"),
+            }
+        }
+        self.reload_all_tasks_timings();
+        ids.len()
+    }
+}
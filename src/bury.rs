@@ -0,0 +1,31 @@
+use std::time::SystemTime;
+
+use ssr_core::{task::Task, tasks_facade::TaskId};
+
+use crate::{Facade, time::next_local_midnight};
+
+impl<'a, T: Task<'a>> Facade<'a, T> {
+    /// Hide the task until the next local day without changing its schedule.
+    /// Returns whether the task exists.
+    pub fn bury(&mut self, id: TaskId) -> bool {
+        let until = next_local_midnight(self.now(), self.day_offset_secs());
+        self.bury_until(id, until)
+    }
+    /// Returns whether the task exists.
+    pub fn bury_until(&mut self, id: TaskId, until: SystemTime) -> bool {
+        self.set_buried_until(id, Some(until))
+    }
+    /// Returns whether the task exists.
+    pub fn unbury(&mut self, id: TaskId) -> bool {
+        self.set_buried_until(id, None)
+    }
+
+    fn set_buried_until(&mut self, id: TaskId, until: Option<SystemTime>) -> bool {
+        let Some(mut task) = self.take_task(id) else {
+            return false;
+        };
+        task.buried_until = until;
+        self.place_task(task);
+        true
+    }
+}
@@ -0,0 +1,54 @@
+use std::collections::BTreeMap;
+
+use ssr_core::{
+    task::Task,
+    tasks_facade::{Error, TasksFacade},
+};
+
+use crate::Facade;
+
+impl<'a, T: Task<'a>> Facade<'a, T> {
+    /// Add `child` as a sub-deck under its name. Its tasks count towards
+    /// [`TasksFacade::tasks_total`] and [`TasksFacade::tasks_to_complete`] and
+    /// are reviewed by [`TasksFacade::complete_task`] once this facade has
+    /// nothing due. Each sub-deck keeps its own settings, limits and history.
+    ///
+    /// Returns the sub-deck previously stored under that name.
+    pub fn insert_child(&mut self, child: Facade<'a, T>) -> Option<Facade<'a, T>> {
+        self.children.insert(child.get_name().to_owned(), child)
+    }
+    pub fn remove_child(&mut self, name: &str) -> Option<Facade<'a, T>> {
+        self.children.remove(name)
+    }
+    #[must_use]
+    pub fn child(&self, name: &str) -> Option<&Facade<'a, T>> {
+        self.children.get(name)
+    }
+    /// Renaming the sub-deck through this doesn't move it, use
+    /// [`Facade::remove_child`] and [`Facade::insert_child`].
+    pub fn child_mut(&mut self, name: &str) -> Option<&mut Facade<'a, T>> {
+        self.children.get_mut(name)
+    }
+    /// Direct sub-decks by name.
+    pub fn children(&self) -> &BTreeMap<String, Facade<'a, T>> {
+        &self.children
+    }
+}
+
+/// Of two [`Error::NoTaskToComplete`] or [`Error::NoTask`], the one that
+/// ends first.
+pub(crate) fn sooner(a: Error, b: Error) -> Error {
+    match (a, b) {
+        (
+            Error::NoTaskToComplete {
+                time_until_next_repetition: a,
+            },
+            Error::NoTaskToComplete {
+                time_until_next_repetition: b,
+            },
+        ) => Error::NoTaskToComplete {
+            time_until_next_repetition: a.min(b),
+        },
+        (error @ Error::NoTaskToComplete { .. }, _) | (_, error) => error,
+    }
+}
@@ -0,0 +1,50 @@
+use std::collections::HashSet;
+
+use rand::Rng;
+use ssr_core::{task::Task, tasks_facade::TaskId};
+
+use crate::Facade;
+
+/// Random key, so keys of deleted tasks are never handed out again.
+pub(crate) fn new_citation_key() -> String {
+    new_citation_key_with(&mut rand::thread_rng())
+}
+pub(crate) fn new_citation_key_with(rng: &mut impl Rng) -> String {
+    format!("{:016x}", rng.r#gen::<u64>())
+}
+
+/// Add `key` to `taken`, replacing it with a fresh key if it's taken already.
+pub(crate) fn claim_citation_key(
+    taken: &mut HashSet<String>,
+    key: &mut String,
+    rng: &mut impl Rng,
+) {
+    while !taken.insert(key.clone()) {
+        *key = new_citation_key_with(rng);
+    }
+}
+
+impl<'a, T: Task<'a>> Facade<'a, T> {
+    /// Key for linking to the task from outside, e.g. from notes. Unlike the
+    /// id it survives merges and id regeneration, and restoring the task from
+    /// trash. Tasks saved before keys existed get one on load, which becomes
+    /// stable once the facade is saved.
+    pub fn citation_key(&self, id: TaskId) -> Option<&str> {
+        self.find_task(id).map(|task| task.citation_key.as_str())
+    }
+    pub(crate) fn citation_keys(&self) -> HashSet<String> {
+        self.tasks_pool
+            .iter()
+            .chain(self.tasks_to_recall.iter())
+            .map(|task| task.citation_key.clone())
+            .collect()
+    }
+    /// Task currently carrying the key, `None` if it was deleted.
+    pub fn find_by_citation_key(&self, key: &str) -> Option<TaskId> {
+        self.tasks_pool
+            .iter()
+            .chain(self.tasks_to_recall.iter())
+            .find(|task| task.citation_key == key)
+            .map(|task| task.id)
+    }
+}
@@ -0,0 +1,59 @@
+use std::time::{Duration, SystemTime};
+
+use serde::{Deserialize, Deserializer, de::IgnoredAny};
+use ssr_core::task::Task;
+
+use crate::{Event, Facade};
+
+/// Reads the latest seen time saved by older versions and drops it, so a
+/// clock glitch in one session doesn't pin the facade's time in later ones.
+pub(crate) fn forget_latest_seen_time<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<SystemTime>, D::Error> {
+    IgnoredAny::deserialize(deserializer)?;
+    Ok(None)
+}
+
+impl<'a, T: Task<'a>> Facade<'a, T> {
+    /// Current time, never earlier than the latest time the facade has
+    /// observed since it was created or loaded. Keeps due comparisons and the
+    /// review log monotonic when the system clock jumps backwards.
+    pub(crate) fn now(&self) -> SystemTime {
+        let now = self.clock.map_or_else(SystemTime::now, |clock| clock());
+        self.latest_seen_time.map_or(now, |seen| now.max(seen))
+    }
+    /// Like [`Facade::now`], but also records the observation and detects
+    /// clock skew.
+    pub(crate) fn observe_now(&mut self) -> SystemTime {
+        let now = self.clock.map_or_else(SystemTime::now, |clock| clock());
+        match self.latest_seen_time {
+            Some(seen) if now < seen => {
+                let by = seen.duration_since(now).unwrap_or_default();
+                if self.clock_skew.is_none() {
+                    self.events.push(Event::ClockSkewDetected { by });
+                }
+                self.clock_skew = Some(by);
+                seen
+            }
+            _ => {
+                self.latest_seen_time = Some(now);
+                self.clock_skew = None;
+                now
+            }
+        }
+    }
+
+    /// How far the system clock is behind the latest time the facade has
+    /// seen, if it went backwards. Until the clock catches up, the facade
+    /// keeps using that latest time. The latest time isn't saved, so loading
+    /// the facade starts over from the system clock.
+    pub fn clock_skew_detected(&self) -> Option<Duration> {
+        self.clock_skew
+    }
+    /// Forget the latest time seen and follow the system clock again, e.g.
+    /// after it was set far ahead by mistake and corrected.
+    pub fn reset_clock_skew(&mut self) {
+        self.latest_seen_time = None;
+        self.clock_skew = None;
+    }
+}
@@ -0,0 +1,303 @@
+use std::{collections::BTreeMap, fmt, time::Duration};
+
+use rand::{Rng, thread_rng};
+use serde::{Deserialize, Serialize};
+use ssr_core::{
+    task::Task,
+    tasks_facade::{Error, TaskId, TasksFacade},
+};
+
+use crate::Facade;
+
+/// Separates levels of deck names: `Languages::German` is a sub-deck of
+/// `Languages`.
+pub const DECK_SEPARATOR: &str = "::";
+
+/// Several named decks, each one a separate [`Facade`]. Decks form a tree
+/// through their names, see [`DECK_SEPARATOR`].
+#[derive(Serialize, Deserialize)]
+#[serde(bound(deserialize = "'a: 'de, 'de: 'a"))]
+pub struct Collection<'a, T>
+where
+    T: Task<'a>,
+{
+    decks: BTreeMap<String, Facade<'a, T>>,
+    #[serde(default)]
+    meta: BTreeMap<String, DeckMeta>,
+}
+
+/// Presentation settings of a deck. The crate itself never reads them.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct DeckMeta {
+    /// Free-form color tag, e.g. `#ff8800` or `red`.
+    pub color: Option<String>,
+    pub icon: Option<String>,
+    /// Decks without position are sorted after positioned ones.
+    pub position: Option<u32>,
+}
+
+impl<'a, T: Task<'a>> fmt::Debug for Collection<'a, T>
+where
+    Facade<'a, T>: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Collection")
+            .field("decks", &self.decks)
+            .field("meta", &self.meta)
+            .finish()
+    }
+}
+
+impl<'a, T: Task<'a>> Default for Collection<'a, T> {
+    fn default() -> Self {
+        Self {
+            decks: BTreeMap::new(),
+            meta: BTreeMap::new(),
+        }
+    }
+}
+
+impl<'a, T: Task<'a>> Collection<'a, T> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add deck under its own name.
+    /// Returns deck previously stored under this name.
+    pub fn insert_deck(&mut self, deck: Facade<'a, T>) -> Option<Facade<'a, T>> {
+        self.decks.insert(deck.get_name().to_owned(), deck)
+    }
+    pub fn remove_deck(&mut self, name: &str) -> Option<Facade<'a, T>> {
+        self.meta.remove(name);
+        self.decks.remove(name)
+    }
+    #[must_use]
+    pub fn deck(&self, name: &str) -> Option<&Facade<'a, T>> {
+        self.decks.get(name)
+    }
+    pub fn deck_mut(&mut self, name: &str) -> Option<&mut Facade<'a, T>> {
+        self.decks.get_mut(name)
+    }
+    pub fn deck_names(&self) -> impl Iterator<Item = &str> {
+        self.decks.keys().map(String::as_str)
+    }
+    /// Decks with their names, ordered by name.
+    pub fn decks(&self) -> impl Iterator<Item = (&str, &Facade<'a, T>)> {
+        self.decks.iter().map(|(name, deck)| (name.as_str(), deck))
+    }
+    pub fn decks_mut(&mut self) -> impl Iterator<Item = (&str, &mut Facade<'a, T>)> {
+        self.decks
+            .iter_mut()
+            .map(|(name, deck)| (name.as_str(), deck))
+    }
+    /// Deck names ordered by [`DeckMeta::position`], then by name.
+    #[must_use]
+    pub fn deck_names_by_position(&self) -> Vec<&str> {
+        let mut names: Vec<_> = self.deck_names().collect();
+        names.sort_by_key(|name| {
+            let position = self.meta.get(*name).and_then(|meta| meta.position);
+            (position.is_none(), position)
+        });
+        names
+    }
+
+    /// Returns `None` if there is no such deck.
+    #[must_use]
+    pub fn meta(&self, name: &str) -> Option<DeckMeta> {
+        self.decks
+            .contains_key(name)
+            .then(|| self.meta.get(name).cloned().unwrap_or_default())
+    }
+    /// Returns `None` if there is no such deck.
+    pub fn meta_mut(&mut self, name: &str) -> Option<&mut DeckMeta> {
+        self.decks
+            .contains_key(name)
+            .then(|| self.meta.entry(name.to_owned()).or_default())
+    }
+
+    /// Move task with its id, scheduling state and review history to another
+    /// deck. Sibling links and prerequisites between the task and the rest of
+    /// `from_deck` are dropped, since they can't cross decks.
+    /// Returns whether the task was moved. Nothing is changed if either deck or
+    /// the task doesn't exist, or the id is taken in `to_deck`.
+    pub fn move_task(&mut self, from_deck: &str, to_deck: &str, id: TaskId) -> bool {
+        if from_deck == to_deck || self.decks.get(to_deck).is_none_or(|deck| deck.contains(id)) {
+            return false;
+        }
+        let Some(source) = self.decks.get_mut(from_deck) else {
+            return false;
+        };
+        let Some((mut task, history, media)) = source.detach_task(id) else {
+            return false;
+        };
+        source.unlink_task(id);
+        task.sibling = None;
+        task.prerequisites.clear();
+        if let Some(deck) = self.decks.get_mut(to_deck) {
+            deck.attach_task(task, history, media);
+        }
+        true
+    }
+
+    pub fn tasks_total(&self) -> usize {
+        self.decks.values().map(TasksFacade::tasks_total).sum()
+    }
+    pub fn tasks_to_complete(&self) -> usize {
+        self.decks
+            .values()
+            .map(TasksFacade::tasks_to_complete)
+            .sum()
+    }
+    pub fn find_tasks_to_recall(&mut self) {
+        for deck in self.decks.values_mut() {
+            deck.find_tasks_to_recall();
+        }
+    }
+    /// Time until the soonest task of any deck, `None` if all decks are empty.
+    pub fn until_next_repetition(&self) -> Option<Duration> {
+        self.decks
+            .values()
+            .filter_map(Facade::until_next_repetition)
+            .min()
+    }
+
+    /// Global "review everything" session step: complete one task picked
+    /// uniformly from the due tasks of all decks, or from the first deck with
+    /// due tasks if every deck is in deterministic mode, see
+    /// [`Facade::set_deterministic`]. Each deck applies its own configuration
+    /// to the chosen task; decks that reached [`Facade::reviews_per_day`] are
+    /// skipped.
+    ///
+    /// `interaction` additionally receives name of the deck task belongs to.
+    /// # Errors
+    /// If no deck has due tasks, only decks over their daily limit do, or
+    /// interaction returns error.
+    pub fn complete_task(
+        &mut self,
+        interaction: &mut impl FnMut(
+            &str,
+            TaskId,
+            s_text_input_f::Blocks,
+        ) -> std::io::Result<s_text_input_f::Response>,
+    ) -> Result<(), Error> {
+        self.complete_task_among(|_| true, interaction)
+    }
+
+    /// Parent deck name of `name`, e.g. `Languages` for `Languages::German`.
+    /// The parent deck doesn't have to exist.
+    #[must_use]
+    pub fn parent_deck(name: &str) -> Option<&str> {
+        name.rsplit_once(DECK_SEPARATOR).map(|(parent, _)| parent)
+    }
+    /// Whether `name` is `root` or one of its sub-decks.
+    #[must_use]
+    pub fn is_in_subtree(root: &str, name: &str) -> bool {
+        name.strip_prefix(root)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with(DECK_SEPARATOR))
+    }
+    /// Direct sub-decks of `name`.
+    pub fn child_decks<'s>(&'s self, name: &'s str) -> impl Iterator<Item = &'s str> {
+        self.deck_names()
+            .filter(move |child| Self::parent_deck(child) == Some(name))
+    }
+    /// Decks in the subtree of `root`, including itself if it exists.
+    pub fn subtree<'s>(&'s self, root: &'s str) -> impl Iterator<Item = &'s str> {
+        self.deck_names()
+            .filter(move |name| Self::is_in_subtree(root, name))
+    }
+    pub fn tasks_total_in(&self, root: &str) -> usize {
+        self.subtree(root)
+            .filter_map(|name| self.decks.get(name))
+            .map(TasksFacade::tasks_total)
+            .sum()
+    }
+    pub fn tasks_to_complete_in(&self, root: &str) -> usize {
+        self.subtree(root)
+            .filter_map(|name| self.decks.get(name))
+            .map(TasksFacade::tasks_to_complete)
+            .sum()
+    }
+    pub fn find_tasks_to_recall_in(&mut self, root: &str) {
+        self.decks
+            .iter_mut()
+            .filter(|(name, _)| Self::is_in_subtree(root, name))
+            .for_each(|(_, deck)| deck.find_tasks_to_recall());
+    }
+    /// [`Collection::complete_task`] restricted to the subtree of `root`.
+    /// # Errors
+    /// If no deck of the subtree has due tasks or interaction returns error.
+    pub fn complete_task_in(
+        &mut self,
+        root: &str,
+        interaction: &mut impl FnMut(
+            &str,
+            TaskId,
+            s_text_input_f::Blocks,
+        ) -> std::io::Result<s_text_input_f::Response>,
+    ) -> Result<(), Error> {
+        self.complete_task_among(|name| Self::is_in_subtree(root, name), interaction)
+    }
+
+    pub(crate) fn complete_task_among(
+        &mut self,
+        selected: impl Fn(&str) -> bool,
+        interaction: &mut impl FnMut(
+            &str,
+            TaskId,
+            s_text_input_f::Blocks,
+        ) -> std::io::Result<s_text_input_f::Response>,
+    ) -> Result<(), Error> {
+        let mut decks = self
+            .decks
+            .iter_mut()
+            .filter(|(name, _)| selected(name))
+            .collect::<Vec<_>>();
+        for (_, deck) in &mut decks {
+            if !deck.rollover_if_needed() {
+                deck.find_tasks_to_recall();
+            }
+        }
+        // Decks over their daily review limit would only fail, pick among the
+        // others.
+        let (limited, decks): (Vec<_>, Vec<_>) = decks
+            .into_iter()
+            .partition(|(_, deck)| deck.daily_limit_reached());
+
+        let due = decks
+            .iter()
+            .map(|(_, deck)| deck.tasks_to_complete())
+            .sum::<usize>();
+        if due == 0 {
+            if let Some((_, deck)) = (limited.iter()).find(|(_, deck)| deck.tasks_to_complete() > 0)
+            {
+                deck.check_daily_limit()?;
+            }
+            return match decks
+                .iter()
+                .filter_map(|(_, deck)| deck.until_next_repetition())
+                .min()
+            {
+                Some(time_until_next_repetition) => Err(Error::NoTaskToComplete {
+                    time_until_next_repetition,
+                }),
+                None => Err(Error::NoTask),
+            };
+        }
+
+        let deterministic = (decks.iter()).all(|(_, deck)| deck.deterministic_seed().is_some());
+        let mut pick = if deterministic {
+            0
+        } else {
+            thread_rng().gen_range(0..due)
+        };
+        for (name, deck) in decks {
+            let deck_due = deck.tasks_to_complete();
+            if pick < deck_due {
+                return deck.complete_task(&mut |id, blocks| interaction(name, id, blocks));
+            }
+            pick -= deck_due;
+        }
+        unreachable!("pick is less than total due count")
+    }
+}
@@ -0,0 +1,137 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use ssr_core::{
+    task::Task,
+    tasks_facade::{TaskId, TasksFacade},
+};
+
+use crate::{Collection, SessionError};
+
+#[derive(Debug, Clone, Default)]
+pub struct CollectionSessionConfig {
+    /// Maximum reviews per local day, by deck name. Reviews made outside the
+    /// session count too.
+    pub daily_quotas: BTreeMap<String, usize>,
+    /// Maximum part of the session's reviews a single deck may take, e.g.
+    /// `0.4`. A deck over its share is still picked when no other deck has
+    /// due tasks.
+    pub max_deck_share: Option<f64>,
+}
+
+/// Global session over all decks of a [`Collection`], see
+/// [`Collection::start_session`].
+pub struct CollectionSession<'c, 'a, T: Task<'a>> {
+    collection: &'c mut Collection<'a, T>,
+    config: CollectionSessionConfig,
+    reviews: usize,
+    reviews_by_deck: BTreeMap<String, usize>,
+}
+
+impl<'a, T: Task<'a>> Collection<'a, T> {
+    pub fn start_session(
+        &mut self,
+        config: CollectionSessionConfig,
+    ) -> CollectionSession<'_, 'a, T> {
+        CollectionSession {
+            collection: self,
+            config,
+            reviews: 0,
+            reviews_by_deck: BTreeMap::new(),
+        }
+    }
+}
+
+impl<'a, T: Task<'a>> CollectionSession<'_, 'a, T> {
+    /// Like [`Collection::complete_task`], but skips decks that reached their
+    /// daily quota, their [`crate::Facade::reviews_per_day`] or their share of the
+    /// session.
+    ///
+    /// # Errors
+    /// If every deck with due tasks reached its quota, or the collection fails
+    /// to complete a task.
+    pub fn complete_task(
+        &mut self,
+        interaction: &mut impl FnMut(
+            &str,
+            TaskId,
+            s_text_input_f::Blocks,
+        ) -> std::io::Result<s_text_input_f::Response>,
+    ) -> Result<(), SessionError> {
+        let names = self
+            .collection
+            .deck_names()
+            .map(str::to_owned)
+            .collect::<Vec<_>>();
+        let mut under_quota = BTreeSet::new();
+        let mut has_due = false;
+        for name in names {
+            let Some(deck) = self.collection.deck_mut(&name) else {
+                continue;
+            };
+            deck.rollover_if_needed();
+            deck.find_tasks_to_recall();
+            if deck.tasks_to_complete() == 0 {
+                continue;
+            }
+            has_due = true;
+            let quota = self.config.daily_quotas.get(&name);
+            if quota.is_none_or(|&quota| deck.today_counters().reviews < quota)
+                && !deck.daily_limit_reached()
+            {
+                under_quota.insert(name);
+            }
+        }
+        if has_due && under_quota.is_empty() {
+            return Err(SessionError::QuotaReached);
+        }
+
+        let within_share = under_quota
+            .iter()
+            .filter(|name| self.within_share(name))
+            .cloned()
+            .collect::<BTreeSet<_>>();
+        let selected = if within_share.is_empty() {
+            under_quota
+        } else {
+            within_share
+        };
+
+        let mut picked = None;
+        self.collection.complete_task_among(
+            |name| !has_due || selected.contains(name),
+            &mut |name, id, blocks| {
+                picked = Some(name.to_owned());
+                interaction(name, id, blocks)
+            },
+        )?;
+        if let Some(name) = picked {
+            *self.reviews_by_deck.entry(name).or_default() += 1;
+        }
+        self.reviews += 1;
+        Ok(())
+    }
+
+    /// Whether the deck may take the next review without exceeding
+    /// [`CollectionSessionConfig::max_deck_share`].
+    fn within_share(&self, name: &str) -> bool {
+        let Some(share) = self.config.max_deck_share else {
+            return true;
+        };
+        let reviewed = self.reviews_by_deck.get(name).copied().unwrap_or(0);
+        #[allow(clippy::cast_precision_loss)] // review counts are far below 2^52
+        let allowed = (share * (self.reviews + 1) as f64).ceil();
+        #[allow(clippy::cast_precision_loss)]
+        let within = (reviewed as f64) < allowed;
+        within
+    }
+
+    #[must_use]
+    pub fn reviews(&self) -> usize {
+        self.reviews
+    }
+    /// Reviews made in this session, by deck name.
+    #[must_use]
+    pub fn reviews_by_deck(&self) -> &BTreeMap<String, usize> {
+        &self.reviews_by_deck
+    }
+}
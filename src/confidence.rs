@@ -0,0 +1,57 @@
+use ssr_core::{task::Task, tasks_facade::TaskId};
+
+use crate::{Facade, ReviewSource};
+
+/// Task type that can make use of self-reported confidence, see
+/// [`Facade::complete_task_with_confidence`]. Implement it with an empty body
+/// to only have confidence logged.
+pub trait ConfidenceTask<'a>: Task<'a> {
+    /// Called after a successful completion with confidence in `0.0..=1.0`.
+    /// Schedule changes made here aren't reflected in the logged `next_due`.
+    fn confidence_reported(&mut self, _shared_state: &mut Self::SharedState, _confidence: f64) {}
+}
+
+impl<'a, T: ConfidenceTask<'a>> Facade<'a, T> {
+    /// `complete_task` whose interaction may report how confident the user
+    /// was in the answer. The last reported confidence, clamped to
+    /// `0.0..=1.0`, is stored in [`crate::ReviewRecord::confidence`] and
+    /// passed to the task.
+    /// # Errors
+    /// If interaction return error.
+    pub fn complete_task_with_confidence(
+        &mut self,
+        interaction: &mut impl FnMut(
+            TaskId,
+            s_text_input_f::Blocks,
+        )
+            -> std::io::Result<(s_text_input_f::Response, Option<f64>)>,
+    ) -> Result<(), ssr_core::tasks_facade::Error> {
+        let mut confidence = None;
+        self.complete_task_as(ReviewSource::Normal, &mut |id, blocks| {
+            let (response, reported) = interaction(id, blocks)?;
+            confidence = reported.map(|c| c.clamp(0., 1.)).or(confidence);
+            Ok(response)
+        })?;
+        let Some(confidence) = confidence else {
+            return Ok(());
+        };
+        let Some(record) = self.review_log.last_mut() else {
+            return Ok(());
+        };
+        record.confidence = Some(confidence);
+        if !record.source.changes_schedule() {
+            return Ok(());
+        }
+        let id = record.task_id;
+        let Some(mut task) = self.take_task(id) else {
+            return Ok(());
+        };
+        let group = self.parameter_groups.group_of(&task).map(str::to_owned);
+        let state = self
+            .parameter_groups
+            .state_mut(&mut self.state, group.as_deref());
+        task.task.confidence_reported(state, confidence);
+        self.place_task(task);
+        Ok(())
+    }
+}
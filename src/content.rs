@@ -0,0 +1,34 @@
+use s_text_input_f::BlocksWithAnswer;
+
+/// Stable 64-bit digest of task content, used to detect duplicates and changes.
+///
+/// FNV-1a over the `Debug` representation: `BlocksWithAnswer` has no `Eq` or
+/// `Hash`, and the std hasher isn't guaranteed to be stable across releases.
+pub(crate) fn content_key(blocks: &BlocksWithAnswer) -> u64 {
+    fnv1a(format!("{blocks:?}").bytes())
+}
+
+/// 64-bit FNV-1a.
+pub(crate) fn fnv1a(bytes: impl IntoIterator<Item = u8>) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0100_0000_01b3;
+
+    bytes.into_iter().fold(OFFSET_BASIS, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(PRIME)
+    })
+}
+
+/// 128-bit FNV-1a variant of [`content_key`], wide enough to serve as task id.
+pub(crate) fn content_key_128(blocks: &BlocksWithAnswer) -> u128 {
+    fnv1a_128(format!("{blocks:?}").bytes())
+}
+
+/// 128-bit FNV-1a.
+pub(crate) fn fnv1a_128(bytes: impl IntoIterator<Item = u8>) -> u128 {
+    const OFFSET_BASIS: u128 = 0x6c62_272e_07bb_0142_62b8_2175_6295_c58d;
+    const PRIME: u128 = 0x0000_0000_0100_0000_0000_0000_0000_013b;
+
+    bytes.into_iter().fold(OFFSET_BASIS, |hash, byte| {
+        (hash ^ u128::from(byte)).wrapping_mul(PRIME)
+    })
+}
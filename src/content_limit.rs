@@ -0,0 +1,189 @@
+use s_text_input_f::{
+    Block, BlockAnswered, BlocksWithAnswer, ParagraphItem, ParagraphItemAnswered,
+};
+use serde::{Deserialize, Serialize};
+use ssr_core::task::Task;
+
+use crate::{
+    Facade, MediaStore, RejectReason,
+    media::{REFERENCE_LEN, paragraph_texts},
+};
+
+/// Appended to paragraph text cut by [`OversizePolicy::Truncate`].
+pub const TRUNCATION_MARKER: &str = "…[truncated]";
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ContentLimit {
+    /// Total size of all text in blocks and answer.
+    pub max_bytes: usize,
+    pub policy: OversizePolicy,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OversizePolicy {
+    Reject,
+    /// Cut paragraph text at the end, keeping its beginning. Options and
+    /// answers are never cut, so tasks that are too large even without
+    /// paragraph text are rejected.
+    Truncate,
+    /// Move the largest paragraph texts into the media store, see
+    /// [`Facade::media`], leaving references in their place. Options and
+    /// answers stay in the task, so tasks that are too large even with all
+    /// paragraph text moved out are rejected.
+    StoreOutOfLine,
+}
+
+fn block_size(block: &Block) -> (usize, usize) {
+    match block {
+        Block::Paragraph(items) => {
+            let text = items
+                .iter()
+                .map(|item| match item {
+                    ParagraphItem::Text(text) => text.len(),
+                    ParagraphItem::Placeholder => 0,
+                })
+                .sum();
+            (text, 0)
+        }
+        Block::Order(items) | Block::AnyOf(items) | Block::OneOf(items) => {
+            (0, items.iter().map(String::len).sum())
+        }
+        Block::Answered(answered) => (0, answered_size(answered)),
+        // Variants added to `s_text_input_f` later; their `Debug` form is
+        // an upper bound of the payload.
+        other => (0, format!("{other:?}").len()),
+    }
+}
+
+fn answered_size(block: &BlockAnswered) -> usize {
+    const INDEX: usize = size_of::<usize>();
+    let strings = |items: &[String]| items.iter().map(String::len).sum::<usize>();
+    match block {
+        BlockAnswered::Order {
+            items,
+            user_answer,
+            correct_answer,
+        }
+        | BlockAnswered::AnyOf {
+            items,
+            user_answer,
+            correct_answer,
+        } => strings(items) + INDEX * (user_answer.len() + correct_answer.len()),
+        BlockAnswered::OneOf { items, .. } => strings(items) + 2 * INDEX,
+        BlockAnswered::Paragraph(items) => items
+            .iter()
+            .map(|item| match item {
+                ParagraphItemAnswered::Text(text) => text.len(),
+                ParagraphItemAnswered::Answer {
+                    user_answer,
+                    correct_answer,
+                } => user_answer.len() + correct_answer.len(),
+            })
+            .sum(),
+        other => format!("{other:?}").len(),
+    }
+}
+
+/// Bytes of paragraph text and of everything else.
+fn content_size(content: &BlocksWithAnswer) -> (usize, usize) {
+    let answer: usize = content.answer.iter().flatten().map(String::len).sum();
+    content
+        .blocks
+        .iter()
+        .map(block_size)
+        .fold((0, answer), |(text, other), (t, o)| (text + t, other + o))
+}
+
+impl ContentLimit {
+    /// Content fitting the limit. Text moved out of line goes to `media`.
+    /// # Errors
+    /// If content is too large and can't be truncated or moved out of line.
+    pub fn apply(
+        &self,
+        mut content: BlocksWithAnswer,
+        media: &mut MediaStore,
+    ) -> Result<BlocksWithAnswer, RejectReason> {
+        let (text, other) = content_size(&content);
+        if text + other <= self.max_bytes {
+            return Ok(content);
+        }
+        match self.policy {
+            OversizePolicy::Reject => Err(RejectReason::TooLarge),
+            OversizePolicy::Truncate => self.truncate(content, other),
+            OversizePolicy::StoreOutOfLine => {
+                let mut texts = paragraph_texts(&mut content.blocks).collect::<Vec<_>>();
+                texts.sort_by_key(|text| std::cmp::Reverse(text.len()));
+                let mut size = text + other;
+                let mut moved = 0;
+                for text in &texts {
+                    if size <= self.max_bytes || text.len() <= REFERENCE_LEN {
+                        break;
+                    }
+                    size = size - text.len() + REFERENCE_LEN;
+                    moved += 1;
+                }
+                if size > self.max_bytes {
+                    return Err(RejectReason::TooLarge);
+                }
+                for text in texts.into_iter().take(moved) {
+                    *text = media.store(std::mem::take(text));
+                }
+                Ok(content)
+            }
+        }
+    }
+
+    fn truncate(
+        &self,
+        mut content: BlocksWithAnswer,
+        other: usize,
+    ) -> Result<BlocksWithAnswer, RejectReason> {
+        if other + TRUNCATION_MARKER.len() > self.max_bytes {
+            return Err(RejectReason::TooLarge);
+        }
+        let mut budget = self.max_bytes - other - TRUNCATION_MARKER.len();
+        let mut truncated = false;
+        for text in paragraph_texts(&mut content.blocks) {
+            if truncated {
+                text.clear();
+            } else if text.len() > budget {
+                let end = (0..=budget)
+                    .rev()
+                    .find(|&i| text.is_char_boundary(i))
+                    .unwrap_or(0);
+                text.truncate(end);
+                text.push_str(TRUNCATION_MARKER);
+                truncated = true;
+            } else {
+                budget -= text.len();
+            }
+        }
+        Ok(content)
+    }
+
+    /// Whether content fits the limit as it is.
+    pub(crate) fn fits(&self, content: &BlocksWithAnswer) -> bool {
+        let (text, other) = content_size(content);
+        text + other <= self.max_bytes
+    }
+}
+
+impl<'a, T: Task<'a>> Facade<'a, T> {
+    pub fn content_limit(&self) -> Option<ContentLimit> {
+        self.content_limit
+    }
+    /// Applies to tasks created afterwards.
+    pub fn set_content_limit(&mut self, limit: Option<ContentLimit>) {
+        self.content_limit = limit;
+    }
+    /// Content of a new task after applying the content limit.
+    pub(crate) fn limit_content(
+        &mut self,
+        content: BlocksWithAnswer,
+    ) -> Result<BlocksWithAnswer, RejectReason> {
+        match self.content_limit {
+            Some(limit) => limit.apply(content, &mut self.media),
+            None => Ok(content),
+        }
+    }
+}
@@ -0,0 +1,145 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    io::{self, Write},
+    time::Duration,
+};
+
+use ssr_core::{task::Task, tasks_facade::TasksFacade};
+
+use crate::{Facade, time::local_day};
+
+const FORECAST_DAYS: usize = 30;
+const HEATMAP_DAYS: i64 = 365;
+const DAY: Duration = Duration::from_hours(24);
+/// Upper bounds of maturity histogram buckets.
+const MATURITY_BUCKETS: [(&str, Duration); 5] = [
+    ("< 1 day", DAY),
+    ("< 1 week", Duration::from_hours(7 * 24)),
+    ("< 1 month", Duration::from_hours(30 * 24)),
+    ("< 1 year", Duration::from_hours(365 * 24)),
+    ("≥ 1 year", Duration::MAX),
+];
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn write_bars(
+    writer: &mut impl Write,
+    title: &str,
+    rows: impl IntoIterator<Item = (String, usize)>,
+) -> io::Result<()> {
+    let rows: Vec<_> = rows.into_iter().collect();
+    let max = rows.iter().map(|(_, x)| *x).max().unwrap_or(0).max(1);
+    writeln!(writer, "<h2>{title}</h2>\n<table>")?;
+    for (label, value) in rows {
+        let width = value * 100 / max;
+        writeln!(
+            writer,
+            "<tr><td>{label}</td><td>{value}</td>\
+             <td><div class=\"bar\" style=\"width:{width}%\"></div></td></tr>"
+        )?;
+    }
+    writeln!(writer, "</table>")
+}
+
+impl<'a, T: Task<'a>> Facade<'a, T> {
+    /// Write a self-contained static HTML page with due forecast, review
+    /// heatmap and maturity histogram of this facade.
+    /// # Errors
+    /// If writing fails.
+    pub fn export_dashboard_html(&self, mut writer: impl Write) -> io::Result<()> {
+        let today = local_day(self.now(), self.day_offset_secs());
+        let day_of = |time| local_day(time, self.day_offset_secs());
+
+        let mut maturity = [0; MATURITY_BUCKETS.len()];
+        let mut new = 0;
+        let last_reviews: HashMap<_, _> = self
+            .review_log
+            .iter()
+            .map(|r| (r.task_id, r.reviewed_at))
+            .collect();
+        for task in self.tasks_pool.iter().chain(self.tasks_to_recall.iter()) {
+            let Some(last_review) = last_reviews.get(&task.id) else {
+                new += 1;
+                continue;
+            };
+            let interval = self
+                .next_repetition(task)
+                .duration_since(*last_review)
+                .unwrap_or_default();
+            let bucket = MATURITY_BUCKETS
+                .iter()
+                .position(|(_, bound)| interval < *bound)
+                .unwrap_or(MATURITY_BUCKETS.len() - 1);
+            maturity[bucket] += 1;
+        }
+
+        let mut heatmap = BTreeMap::new();
+        for record in self.stats_records() {
+            let days_ago = today - day_of(record.reviewed_at);
+            if (0..HEATMAP_DAYS).contains(&days_ago) {
+                *heatmap.entry(days_ago).or_insert(0) += 1;
+            }
+        }
+        let heatmap_max = heatmap.values().copied().max().unwrap_or(0).max(1);
+
+        let name = escape(&self.name);
+        writeln!(
+            writer,
+            "<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\"><title>{name}</title>\n\
+             <style>body{{font-family:sans-serif}}td{{padding:0 .5em}}\
+             .bar{{background:#4a90d9;height:1em}}\
+             .cell{{display:inline-block;width:10px;height:10px;margin:1px}}</style>\n\
+             </head><body>\n<h1>{name}</h1>\n<p>{} tasks, {} due now</p>",
+            self.tasks_total(),
+            self.tasks_to_complete()
+        )?;
+        write_bars(
+            &mut writer,
+            "Forecast",
+            self.due_forecast(FORECAST_DAYS)
+                .into_iter()
+                .enumerate()
+                .map(|(day, (date, count))| {
+                    let label = match day {
+                        0 => "today".to_owned(),
+                        1 => "tomorrow".to_owned(),
+                        _ => date.to_string(),
+                    };
+                    (label, count)
+                }),
+        )?;
+
+        writeln!(writer, "<h2>Reviews in the last year</h2>\n<div>")?;
+        for days_ago in (0..HEATMAP_DAYS).rev() {
+            let count = heatmap.get(&days_ago).copied().unwrap_or(0);
+            let alpha = if count == 0 {
+                0.05
+            } else {
+                0.2 + 0.8 * f64::from(count) / f64::from(heatmap_max)
+            };
+            writeln!(
+                writer,
+                "<span class=\"cell\" title=\"{days_ago} days ago: {count}\" \
+                 style=\"background:rgba(74,144,217,{alpha:.2})\"></span>"
+            )?;
+        }
+        writeln!(writer, "</div>")?;
+
+        write_bars(
+            &mut writer,
+            "Maturity",
+            std::iter::once(("new".to_owned(), new)).chain(
+                MATURITY_BUCKETS
+                    .iter()
+                    .zip(maturity)
+                    .map(|((label, _), count)| ((*label).to_owned(), count)),
+            ),
+        )?;
+        writeln!(writer, "</body></html>")
+    }
+}
@@ -0,0 +1,124 @@
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+use ssr_core::{
+    task::Task,
+    tasks_facade::{TaskId, TasksFacade},
+};
+
+use crate::{DueOverride, Facade, ParameterGroups, ReviewRecord, TaskWrapper};
+
+/// Changes of a facade since some epoch, see [`Facade::delta_since`].
+///
+/// Carries changed tasks with their full review history and due overrides,
+/// removed task ids and the facade-wide scheduling state. Serialize it with
+/// any serde format.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(bound(deserialize = "'a: 'de, 'de: 'a"))]
+pub struct Delta<'a, T>
+where
+    T: Task<'a>,
+{
+    since: u64,
+    epoch: u64,
+    desired_retention: f64,
+    state: T::SharedState,
+    #[serde(default)]
+    parameter_groups: ParameterGroups<T::SharedState>,
+    tasks: Vec<TaskWrapper<T>>,
+    history: Vec<ReviewRecord>,
+    #[serde(default)]
+    overrides: Vec<DueOverride>,
+    deleted: Vec<TaskId>,
+}
+
+impl<'a, T: Task<'a>> Delta<'a, T> {
+    /// Epoch the delta starts after.
+    pub fn since(&self) -> u64 {
+        self.since
+    }
+    /// Epoch of the facade when the delta was taken; pass it to the next
+    /// [`Facade::delta_since`] call.
+    pub fn epoch(&self) -> u64 {
+        self.epoch
+    }
+    /// Whether no task was changed or removed.
+    pub fn is_empty(&self) -> bool {
+        self.tasks.is_empty() && self.deleted.is_empty()
+    }
+}
+
+impl<'a, T: Task<'a>> Facade<'a, T> {
+    /// Marker for [`Facade::delta_since`]: every change of a task increments
+    /// it.
+    pub fn epoch(&self) -> u64 {
+        self.epoch
+    }
+
+    /// Tasks changed after `since`, for incremental saves. A full save
+    /// followed by deltas taken at its [`Facade::epoch`] reproduces the tasks,
+    /// their review log and the scheduling state. Trash, settings, templates,
+    /// note links and daily counters aren't carried; save the full facade to
+    /// keep their changes.
+    pub fn delta_since(&self, since: u64) -> Delta<'a, T>
+    where
+        T: Clone,
+        T::SharedState: Clone,
+    {
+        let tasks: Vec<_> = self
+            .tasks_pool
+            .iter()
+            .chain(self.tasks_to_recall.iter())
+            .filter(|t| t.modified > since)
+            .cloned()
+            .collect();
+        let changed: HashSet<_> = tasks.iter().map(|t| t.id).collect();
+        Delta {
+            since,
+            epoch: self.epoch,
+            desired_retention: self.desired_retention,
+            state: self.state.clone(),
+            parameter_groups: self.parameter_groups.clone(),
+            history: self
+                .review_log
+                .iter()
+                .filter(|r| changed.contains(&r.task_id))
+                .cloned()
+                .collect(),
+            overrides: (self.review_log.overrides().iter())
+                .filter(|r| changed.contains(&r.task_id))
+                .cloned()
+                .collect(),
+            tasks,
+            deleted: self
+                .deleted
+                .iter()
+                .filter(|(_, epoch)| *epoch > since)
+                .map(|(id, _)| *id)
+                .collect(),
+        }
+    }
+
+    /// Apply changes produced by [`Facade::delta_since`] of a facade this one
+    /// was saved from.
+    pub fn apply_delta(&mut self, delta: Delta<'a, T>) {
+        for id in delta.deleted {
+            self.remove(id);
+        }
+        for task in delta.tasks {
+            let id = task.id;
+            self.next_position = self.next_position.max(task.position + 1);
+            self.take_task(id);
+            self.forget_trashed(id);
+            self.review_log.remove_task(id);
+            self.place_task(task);
+        }
+        self.review_log.extend(delta.history);
+        self.review_log.extend_overrides(delta.overrides);
+        self.desired_retention = delta.desired_retention;
+        self.state = delta.state;
+        self.parameter_groups = delta.parameter_groups;
+        self.epoch = self.epoch.max(delta.epoch);
+        self.reload_all_tasks_timings();
+    }
+}
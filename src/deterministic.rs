@@ -0,0 +1,45 @@
+use std::time::SystemTime;
+
+use rand::{SeedableRng, rngs::StdRng, thread_rng};
+use ssr_core::task::Task;
+
+use crate::{Facade, content::fnv1a};
+
+impl<'a, T: Task<'a>> Facade<'a, T> {
+    /// Seed of the randomness-free mode, `None` when it's off.
+    pub fn deterministic_seed(&self) -> Option<u64> {
+        self.deterministic_seed
+    }
+    /// Remove randomness from the facade, so the same operations on the same
+    /// facade give the same result, e.g. for reproducible front-end tests or
+    /// users who rely on a predictable order. Due tasks are handed out by due
+    /// time, then id, and ids and citation keys of new tasks derive from
+    /// `seed`. The facade never fuzzes intervals. `None` turns it off.
+    ///
+    /// Tasks read the system clock when reviewed; inject one with
+    /// [`Facade::set_clock`] for the facade itself. Citation keys given to
+    /// tasks saved before keys existed stay random.
+    pub fn set_deterministic(&mut self, seed: Option<u64>) {
+        self.deterministic_seed = seed;
+    }
+    /// Read time from `clock` instead of the system clock, `None` goes back to
+    /// the system clock. Not serialized. Times seen on the previous clock are
+    /// forgotten, see [`Facade::reset_clock_skew`].
+    pub fn set_clock(&mut self, clock: Option<fn() -> SystemTime>) {
+        self.clock = clock;
+        self.reset_clock_skew();
+    }
+
+    /// Random numbers for `purpose`, reproducible in deterministic mode: they
+    /// depend only on the seed, the purpose and the facade's epoch.
+    pub(crate) fn rng(&self, purpose: &str) -> StdRng {
+        match self.deterministic_seed {
+            Some(seed) => StdRng::seed_from_u64(fnv1a(
+                (seed.to_le_bytes().into_iter())
+                    .chain(self.epoch.to_le_bytes())
+                    .chain(purpose.bytes()),
+            )),
+            None => StdRng::from_rng(thread_rng()).expect("thread rng doesn't fail"),
+        }
+    }
+}
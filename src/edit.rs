@@ -0,0 +1,81 @@
+use s_text_input_f::BlocksWithAnswer;
+use ssr_core::{task::Task, tasks_facade::TaskId};
+
+use crate::{Facade, RejectReason, import::validate};
+
+impl<'a, T: Task<'a>> Facade<'a, T> {
+    /// Replace content of the task, keeping its id, tags, review history and
+    /// current due time. Returns whether the task exists.
+    ///
+    /// Task types can't change content in place, so the task is recreated
+    /// and loses its scheduling state: it keeps the due time through an
+    /// override, but its next review starts the schedule over and is logged
+    /// as [`crate::Outcome::New`]. Use [`Facade::edit_task_with`] to carry
+    /// the state over.
+    ///
+    /// # Errors
+    /// If the content is empty or exceeds the content limit.
+    pub fn edit_task(
+        &mut self,
+        id: TaskId,
+        content: BlocksWithAnswer,
+    ) -> Result<bool, RejectReason> {
+        validate(&content)?;
+        let content = self.limit_content(content)?;
+        let found = self.replace_content(id, content);
+        self.reload_all_tasks_timings();
+        Ok(found)
+    }
+
+    /// Replace the task with `convert` of it, e.g. a copy with new content
+    /// and the old scheduling state, so the schedule goes on uninterrupted.
+    /// Returns whether the task exists.
+    ///
+    /// # Errors
+    /// If the new content is empty or exceeds the content limit; content
+    /// isn't truncated here.
+    pub fn edit_task_with(
+        &mut self,
+        id: TaskId,
+        convert: impl FnOnce(&T) -> T,
+    ) -> Result<bool, RejectReason> {
+        let Some(task) = self.find_task(id) else {
+            return Ok(false);
+        };
+        let converted = convert(&task.task);
+        let content = converted.get_blocks();
+        validate(&content)?;
+        if self
+            .content_limit
+            .is_some_and(|limit| !limit.fits(&content))
+        {
+            return Err(RejectReason::TooLarge);
+        }
+        let now = self.now();
+        let Some(task) = self.find_task_mut(id) else {
+            return Ok(false);
+        };
+        task.task = converted;
+        task.content_changed_at = Some(now);
+        self.reload_all_tasks_timings();
+        Ok(true)
+    }
+
+    /// Doesn't move the task between queues.
+    pub(crate) fn replace_content(&mut self, id: TaskId, content: BlocksWithAnswer) -> bool {
+        let Some(task) = self.find_task(id) else {
+            return false;
+        };
+        let due = self.next_repetition(task);
+        let now = self.now();
+        let Some(task) = self.find_task_mut(id) else {
+            return false;
+        };
+        task.task = T::new(content);
+        task.reviewer_tasks.clear();
+        task.content_changed_at = Some(now);
+        task.due_override = Some(due);
+        task.state_reset = true;
+        true
+    }
+}
@@ -0,0 +1,40 @@
+use std::time::Duration;
+
+use ssr_core::{task::Task, tasks_facade::TaskId};
+
+use crate::{DailyCounters, Facade, RejectReason};
+
+/// Notable changes, queued until the front-end collects them with
+/// [`Facade::take_events`]. Events aren't serialized.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Event {
+    TasksImported {
+        ids: Vec<TaskId>,
+    },
+    /// `create_task` didn't create the task.
+    TaskRejected {
+        reason: RejectReason,
+    },
+    /// New local day started, see [`Facade::rollover_if_needed`].
+    DayRolledOver {
+        /// Counters of the day that ended.
+        previous: DailyCounters,
+        unburied: usize,
+    },
+    /// System clock went backwards by `by`.
+    ClockSkewDetected {
+        by: Duration,
+    },
+    /// `call`, a [`ssr_core::tasks_facade::TasksFacade`] method, had no effect
+    /// because the facade is [`crate::ReadOnly`].
+    ReadOnlyCallIgnored {
+        call: &'static str,
+    },
+}
+
+impl<'a, T: Task<'a>> Facade<'a, T> {
+    pub fn take_events(&mut self) -> Vec<Event> {
+        std::mem::take(&mut self.events)
+    }
+}
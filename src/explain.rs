@@ -0,0 +1,80 @@
+use std::time::SystemTime;
+
+use ssr_core::{task::Task, tasks_facade::TaskId};
+
+use crate::{DueOverride, Facade, ReviewRecord};
+
+/// Where the due time of a task comes from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DueSource {
+    /// Computed by the task type from its state and the retention target.
+    Scheduler,
+    /// Stored by the facade instead of the scheduler's one: scaled by an
+    /// interval multiplier, capped by the interval growth limit, raised to
+    /// the minimum interval, moved away from the sibling, or kept across a
+    /// content edit.
+    Adjusted,
+    /// Set by the user.
+    Manual(DueOverride),
+}
+
+/// Why a task is due when it is, see [`Facade::explain_schedule`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Explanation {
+    pub due: SystemTime,
+    pub due_source: DueSource,
+    /// When the task is handed out, `None` while suspended. Later than `due`
+    /// while buried or frozen.
+    pub available_at: Option<SystemTime>,
+    pub buried_until: Option<SystemTime>,
+    pub frozen_until: Option<SystemTime>,
+    /// Waits for immature prerequisites.
+    pub gated: bool,
+    /// Review that scheduled the task, with its outcome, interval and the
+    /// retention target of that time.
+    pub last_review: Option<ReviewRecord>,
+    pub desired_retention: f64,
+    pub parameter_group: Option<String>,
+    /// Manual priority plus automatic boost.
+    pub priority: i32,
+    /// Applied to the interval of the next review, see
+    /// [`Facade::set_interval_multiplier`].
+    pub interval_multiplier: Option<f64>,
+}
+
+impl<'a, T: Task<'a>> Facade<'a, T> {
+    /// Structured account of the current due time of the task, for display
+    /// and bug reports. The facade applies no fuzz to intervals.
+    pub fn explain_schedule(&self, id: TaskId) -> Option<Explanation> {
+        let task = self.find_task(id)?;
+        let due_source = match task.due_override {
+            None => DueSource::Scheduler,
+            Some(_) if task.manual_due => self
+                .review_log
+                .overrides()
+                .iter()
+                .rfind(|o| o.task_id == id)
+                .cloned()
+                .map_or(DueSource::Adjusted, DueSource::Manual),
+            Some(_) => DueSource::Adjusted,
+        };
+        let now = self.now();
+        Some(Explanation {
+            due: self.next_repetition(task),
+            due_source,
+            available_at: self.available_at(task),
+            buried_until: task.buried_until.filter(|until| *until > now),
+            frozen_until: task.frozen_until.filter(|until| *until > now),
+            gated: self.is_gated(id),
+            last_review: self
+                .review_log
+                .for_task(id)
+                .rfind(|r| r.source.changes_schedule())
+                .cloned(),
+            desired_retention: self.desired_retention,
+            parameter_group: self.parameter_groups.group_of(task).map(str::to_owned),
+            priority: task.effective_priority(),
+            interval_multiplier: self.multiplier_of(task),
+        })
+    }
+}
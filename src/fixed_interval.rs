@@ -0,0 +1,77 @@
+use std::time::{Duration, SystemTime};
+
+use s_text_input_f::{Block, BlocksWithAnswer};
+use serde::{Deserialize, Serialize};
+use ssr_core::task::Task;
+
+/// Interval of tasks created through [`Task::new`].
+pub const DEFAULT_FIXED_INTERVAL: Duration = Duration::from_hours(24);
+
+/// Task without a memory model that recurs on a fixed interval after each
+/// completion, for habits and recurring checklists kept in a facade.
+///
+/// New tasks are due immediately. The answer given doesn't matter. Insert
+/// tasks made with [`FixedIntervalTask::with_interval`] for intervals other
+/// than [`DEFAULT_FIXED_INTERVAL`].
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct FixedIntervalTask {
+    blocks: Vec<Block>,
+    answer: Vec<Vec<String>>,
+    interval: Duration,
+    last_done: Option<SystemTime>,
+}
+
+impl FixedIntervalTask {
+    #[must_use]
+    pub fn with_interval(input: BlocksWithAnswer, interval: Duration) -> Self {
+        Self {
+            blocks: input.blocks,
+            answer: input.answer,
+            interval,
+            last_done: None,
+        }
+    }
+    #[must_use]
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+    /// Takes effect from the last completion.
+    pub fn set_interval(&mut self, interval: Duration) {
+        self.interval = interval;
+    }
+    #[must_use]
+    pub fn last_done(&self) -> Option<SystemTime> {
+        self.last_done
+    }
+}
+
+impl Task<'_> for FixedIntervalTask {
+    type SharedState = ();
+
+    fn new(input: BlocksWithAnswer) -> Self {
+        Self::with_interval(input, DEFAULT_FIXED_INTERVAL)
+    }
+    fn get_blocks(&self) -> BlocksWithAnswer {
+        BlocksWithAnswer {
+            blocks: self.blocks.clone(),
+            answer: self.answer.clone(),
+        }
+    }
+    fn next_repetition(&self, _shared_state: &(), _desired_retention: f64) -> SystemTime {
+        self.last_done.map_or(SystemTime::UNIX_EPOCH, |last_done| {
+            last_done + self.interval
+        })
+    }
+    fn complete(
+        &mut self,
+        _shared_state: &mut (),
+        _desired_retention: f64,
+        interaction: &mut impl FnMut(
+            s_text_input_f::Blocks,
+        ) -> std::io::Result<s_text_input_f::Response>,
+    ) -> std::io::Result<()> {
+        interaction(self.blocks.clone())?;
+        self.last_done = Some(SystemTime::now());
+        Ok(())
+    }
+}
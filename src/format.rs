@@ -0,0 +1,70 @@
+use std::fmt;
+
+use serde::{Serialize, Serializer};
+use ssr_core::task::Task;
+
+use crate::{Facade, ReadOnly};
+
+/// Version of the serialized format written by this crate. Bumped when a
+/// release adds fields older releases would drop on save.
+pub const FORMAT_VERSION: u32 = 1;
+
+/// Always writes [`FORMAT_VERSION`]: a saved facade has the current format.
+#[allow(clippy::trivially_copy_pass_by_ref)] // signature required by serde
+pub(crate) fn serialize_format_version<S: Serializer>(
+    _loaded: &u32,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    FORMAT_VERSION.serialize(serializer)
+}
+
+/// What [`Facade::load_with_policy`] does with a file written by a newer
+/// version of the crate.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum NewerFormatPolicy {
+    /// Refuse to load it.
+    #[default]
+    Fail,
+    /// Load it as [`ReadOnly`], so it can't be overwritten with data missing.
+    ReadOnly,
+    /// Load it for writing, reported in
+    /// [`LoadDiagnostics::newer_format`](crate::LoadDiagnostics::newer_format).
+    /// Fields this version doesn't know inside tasks and other nested data are
+    /// dropped on the next save.
+    BestEffort,
+}
+
+/// File was written by a newer version of the crate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NewerFormatError {
+    pub found: u32,
+    pub supported: u32,
+}
+
+impl fmt::Display for NewerFormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "file format version {} is newer than supported version {}",
+            self.found, self.supported
+        )
+    }
+}
+impl std::error::Error for NewerFormatError {}
+
+/// Facade loaded by [`Facade::load_with_policy`].
+pub enum Loaded<'a, T: Task<'a>> {
+    Writable(Facade<'a, T>),
+    /// Only for files written by a newer version, with
+    /// [`NewerFormatPolicy::ReadOnly`].
+    ReadOnly(ReadOnly<'a, T>),
+}
+
+impl<'a, T: Task<'a>> Facade<'a, T> {
+    /// Format version of the file the facade was loaded from, `0` for files
+    /// written before versions were recorded. Saving always writes
+    /// [`FORMAT_VERSION`].
+    pub fn format_version(&self) -> u32 {
+        self.format_version
+    }
+}
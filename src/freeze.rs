@@ -0,0 +1,93 @@
+use std::time::SystemTime;
+
+use ssr_core::{task::Task, tasks_facade::TaskId};
+
+use crate::{Facade, Outcome, ReviewRecord, ReviewSource, TaskWrapper};
+
+impl<'a, T: Task<'a>> Facade<'a, T> {
+    /// Keep the schedule of the task as is until `until`, for material
+    /// studied outside of the facade. Returns whether the task exists.
+    ///
+    /// The task isn't handed out before `until`, manual due changes are
+    /// refused, and reviews taken anyway (e.g. with
+    /// [`Facade::complete_task_ahead`]) are logged as
+    /// [`ReviewSource::Frozen`] without completing the task.
+    pub fn freeze(&mut self, id: TaskId, until: SystemTime) -> bool {
+        let Some(task) = self.find_task_mut(id) else {
+            return false;
+        };
+        task.frozen_until = Some(until);
+        self.reload_all_tasks_timings();
+        true
+    }
+    /// Returns whether the task was frozen.
+    pub fn unfreeze(&mut self, id: TaskId) -> bool {
+        let now = self.now();
+        let Some(task) = self.find_task_mut(id) else {
+            return false;
+        };
+        let was_frozen = task.frozen_until.take().is_some_and(|until| until > now);
+        self.reload_all_tasks_timings();
+        was_frozen
+    }
+    /// End of the current freeze of the task.
+    pub fn frozen_until(&self, id: TaskId) -> Option<SystemTime> {
+        let now = self.now();
+        self.find_task(id)?
+            .frozen_until
+            .filter(|until| *until > now)
+    }
+
+    pub(crate) fn is_frozen(&self, task: &TaskWrapper<T>) -> bool {
+        task.frozen_until.is_some_and(|until| until > self.now())
+    }
+
+    /// Log a review of a frozen task without completing it. The outcome is
+    /// `Pass`, or `New` for a task never reviewed, since the task can't
+    /// judge the answer without changing its state.
+    pub(crate) fn complete_frozen_task(
+        &mut self,
+        wrapper: TaskWrapper<T>,
+        answered_at: Option<SystemTime>,
+        interaction: &mut impl FnMut(
+            TaskId,
+            s_text_input_f::Blocks,
+        ) -> std::io::Result<s_text_input_f::Response>,
+    ) -> Result<(), ssr_core::tasks_facade::Error> {
+        let id = wrapper.id;
+        let timer = self.idle.start_timer();
+        let result = interaction(id, self.media.resolve(wrapper.task.get_blocks().blocks));
+        let duration = timer.elapsed();
+        if let Err(err) = result {
+            self.tasks_pool.push(wrapper);
+            self.find_tasks_to_recall();
+            return Err(err.into());
+        }
+        let scheduled_due = self.next_repetition(&wrapper);
+        let previous_review = self.review_log.last_review(id);
+        let outcome = if previous_review.is_some() {
+            Outcome::Pass
+        } else {
+            Outcome::New
+        };
+        let now = self.observe_now();
+        let reviewed_at = answered_at.map_or(now, |answered_at| answered_at.min(now));
+        self.review_log.insert(ReviewRecord {
+            task_id: id,
+            reviewed_at,
+            duration,
+            previous_review,
+            scheduled_due,
+            next_due: scheduled_due,
+            desired_retention: self.desired_retention,
+            outcome,
+            source: ReviewSource::Frozen,
+            reviewer: self.reviewer.clone(),
+            manually_scheduled: false,
+            confidence: None,
+        });
+        self.tasks_pool.push(wrapper);
+        self.count_review(outcome, reviewed_at);
+        Ok(())
+    }
+}
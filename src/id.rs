@@ -0,0 +1,94 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use ssr_core::{task::Task, tasks_facade::TaskId};
+
+use crate::{Facade, content::content_key_128};
+
+/// How ids of new tasks are generated.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum IdGenerator {
+    /// 128 random bits.
+    #[default]
+    Random,
+    /// Random id that fits into `u64`, for storage or languages without
+    /// 128-bit integers.
+    Random64,
+    /// UUID version 7: ordering ids orders tasks by creation time.
+    UuidV7,
+    /// Digest of task content, so the same content gets the same id on every
+    /// device. Reveals whether two collections share a task.
+    ContentHash,
+}
+
+impl IdGenerator {
+    #[must_use]
+    pub fn generate(self, content: &s_text_input_f::BlocksWithAnswer, now: SystemTime) -> TaskId {
+        self.generate_with(content, now, &mut rand::thread_rng())
+    }
+    pub(crate) fn generate_with(
+        self,
+        content: &s_text_input_f::BlocksWithAnswer,
+        now: SystemTime,
+        rng: &mut impl Rng,
+    ) -> TaskId {
+        match self {
+            Self::Random => rng.r#gen(),
+            Self::Random64 => TaskId::from(rng.r#gen::<u64>()),
+            Self::UuidV7 => {
+                const VERSION: u128 = 0x7 << 76;
+                const VARIANT: u128 = 0b10 << 62;
+                const RANDOM_MASK: u128 = (0xfff << 64) | ((1 << 62) - 1);
+
+                let millis = now
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_millis();
+                ((millis & ((1 << 48) - 1)) << 80)
+                    | VERSION
+                    | VARIANT
+                    | (rng.r#gen::<u128>() & RANDOM_MASK)
+            }
+            Self::ContentHash => content_key_128(content),
+        }
+    }
+}
+
+impl<'a, T: Task<'a>> Facade<'a, T> {
+    pub fn id_generator(&self) -> IdGenerator {
+        self.id_generator
+    }
+    /// Affects only tasks created afterwards.
+    pub fn set_id_generator(&mut self, id_generator: IdGenerator) {
+        self.id_generator = id_generator;
+    }
+
+    /// Id for a new task that is not used by existing ones. Falls back to a
+    /// random id if the generated one is taken.
+    pub(crate) fn new_task_id(&self, task: &T) -> TaskId {
+        self.new_task_id_except(task, &mut self.rng("id"), |_| false)
+    }
+    /// [`Facade::new_task_id`] drawing from `rng` that also avoids ids
+    /// `taken` outside the facade.
+    pub(crate) fn new_task_id_except(
+        &self,
+        task: &T,
+        rng: &mut impl Rng,
+        taken: impl Fn(TaskId) -> bool,
+    ) -> TaskId {
+        let free = |id| self.find_task(id).is_none() && !taken(id);
+        let id = self
+            .id_generator
+            .generate_with(&task.get_blocks(), self.now(), rng);
+        if free(id) {
+            return id;
+        }
+        loop {
+            let id = rng.r#gen();
+            if free(id) {
+                break id;
+            }
+        }
+    }
+}
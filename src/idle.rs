@@ -0,0 +1,77 @@
+use std::{
+    sync::{Arc, Mutex, PoisonError},
+    time::{Duration, Instant},
+};
+
+use ssr_core::task::Task;
+
+use crate::Facade;
+
+/// Switch the front-end flips when the user goes idle and comes back, see
+/// [`Facade::idle_tracker`]. Clones share the state, so it can be used from
+/// inside an interaction or another thread.
+#[derive(Debug, Clone, Default)]
+pub struct IdleTracker(Arc<Mutex<IdleState>>);
+
+#[derive(Debug, Default)]
+struct IdleState {
+    since: Option<Instant>,
+    total: Duration,
+}
+
+/// Wall time minus idle time since its start.
+pub(crate) struct ActiveTimer {
+    tracker: IdleTracker,
+    start: Instant,
+    idle_at_start: Duration,
+}
+
+impl IdleTracker {
+    fn state(&self) -> std::sync::MutexGuard<'_, IdleState> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+    /// Does nothing if already idle.
+    pub fn idle(&self) {
+        let mut state = self.state();
+        state.since.get_or_insert_with(Instant::now);
+    }
+    pub fn resume(&self) {
+        let mut state = self.state();
+        if let Some(since) = state.since.take() {
+            state.total += since.elapsed();
+        }
+    }
+    #[must_use]
+    pub fn is_idle(&self) -> bool {
+        self.state().since.is_some()
+    }
+    /// Idle time so far, including the ongoing idle period.
+    #[must_use]
+    pub fn total(&self) -> Duration {
+        let state = self.state();
+        state.total + state.since.map_or(Duration::ZERO, |since| since.elapsed())
+    }
+
+    pub(crate) fn start_timer(&self) -> ActiveTimer {
+        ActiveTimer {
+            tracker: self.clone(),
+            start: Instant::now(),
+            idle_at_start: self.total(),
+        }
+    }
+}
+
+impl ActiveTimer {
+    pub(crate) fn elapsed(&self) -> Duration {
+        let idle = self.tracker.total().saturating_sub(self.idle_at_start);
+        self.start.elapsed().saturating_sub(idle)
+    }
+}
+
+impl<'a, T: Task<'a>> Facade<'a, T> {
+    /// Handle for reporting user idleness. Idle time is excluded from logged
+    /// review durations and from session timers.
+    pub fn idle_tracker(&self) -> IdleTracker {
+        self.idle.clone()
+    }
+}
@@ -0,0 +1,333 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+};
+
+use s_text_input_f::BlocksWithAnswer;
+use ssr_core::{task::Task, tasks_facade::TaskId};
+
+use crate::{
+    Event, Facade, MediaStore, TaskWrapper, TemplateError, TemplateRow, content::content_key,
+    template::Derivation,
+};
+
+/// Buffers task creation so large imports are committed in one batch.
+///
+/// Tasks whose content already exists in the facade (or earlier in the same
+/// import) are skipped, invalid ones are recorded in the report and don't
+/// stop the import. Dropping the importer without committing discards
+/// everything buffered, so [`Importer::report`] followed by a drop is a dry
+/// run.
+pub struct Importer<'f, 'a, T: Task<'a>> {
+    facade: &'f mut Facade<'a, T>,
+    /// Tasks by [`content_key`] of their content.
+    known_content: HashMap<u64, Vec<Known>>,
+    pending: Vec<TaskWrapper<T>>,
+    /// Text of pending tasks stored out of line.
+    pending_media: MediaStore,
+    pending_ids: HashSet<TaskId>,
+    report: ImportReport,
+    next_index: usize,
+    /// Where the next input comes from, see [`Importer::source`].
+    source: Option<(usize, String)>,
+}
+
+/// Task with some content key, see [`Importer::is_duplicate`].
+#[derive(Clone, Copy)]
+enum Known {
+    Existing(TaskId),
+    Pending(usize),
+}
+
+/// What an import does with each input, identified by its position among all
+/// tasks passed to the importer.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImportReport {
+    pub created: Vec<usize>,
+    pub duplicates: Vec<usize>,
+    pub rejected: Vec<ImportError>,
+}
+
+/// Input that wasn't imported.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportError {
+    /// Position among all inputs passed to the importer.
+    pub index: usize,
+    /// Line of the source file the record starts at, see
+    /// [`Importer::source`].
+    pub line: Option<usize>,
+    /// Record as it appeared in the source.
+    pub raw: Option<String>,
+    pub kind: ImportErrorKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImportErrorKind {
+    Rejected(RejectReason),
+    /// The front-end couldn't parse the record.
+    Malformed(String),
+    Template(TemplateError),
+}
+
+impl fmt::Display for ImportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "line {line}: ")?,
+            None => write!(f, "record {}: ", self.index)?,
+        }
+        match &self.kind {
+            ImportErrorKind::Rejected(reason) => reason.fmt(f),
+            ImportErrorKind::Malformed(message) => write!(f, "malformed record: {message}"),
+            ImportErrorKind::Template(err) => err.fmt(f),
+        }
+    }
+}
+impl std::error::Error for ImportError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RejectReason {
+    NoBlocks,
+    NoAnswer,
+    /// Exceeds the content limit, see [`Facade::set_content_limit`].
+    TooLarge,
+}
+
+impl fmt::Display for RejectReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoBlocks => write!(f, "task has no blocks"),
+            Self::NoAnswer => write!(f, "task has no answer"),
+            Self::TooLarge => write!(f, "task content is too large"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ImportSummary {
+    pub imported: Vec<TaskId>,
+    pub duplicates: usize,
+    pub rejected: usize,
+}
+
+pub(crate) fn validate(blocks: &BlocksWithAnswer) -> Result<(), RejectReason> {
+    if blocks.blocks.is_empty() {
+        Err(RejectReason::NoBlocks)
+    } else if blocks.answer.is_empty() {
+        Err(RejectReason::NoAnswer)
+    } else {
+        Ok(())
+    }
+}
+
+impl<'a, T: Task<'a>> Facade<'a, T> {
+    pub fn begin_import(&mut self) -> Importer<'_, 'a, T> {
+        let mut known_content = HashMap::<_, Vec<_>>::new();
+        for task in self.tasks_pool.iter().chain(self.tasks_to_recall.iter()) {
+            known_content
+                .entry(content_key(&task.task.get_blocks()))
+                .or_default()
+                .push(Known::Existing(task.id));
+        }
+        Importer {
+            facade: self,
+            known_content,
+            pending: Vec::new(),
+            pending_media: MediaStore::default(),
+            pending_ids: HashSet::new(),
+            report: ImportReport::default(),
+            next_index: 0,
+            source: None,
+        }
+    }
+}
+
+impl<'a, T: Task<'a>> Importer<'_, 'a, T> {
+    /// Describe where the next input comes from, so its error report points
+    /// back at the source.
+    pub fn source(&mut self, line: usize, raw: impl Into<String>) -> &mut Self {
+        self.source = Some((line, raw.into()));
+        self
+    }
+    /// Record an input the front-end couldn't parse, so it shows up in the
+    /// report next to the rejected ones.
+    pub fn malformed(&mut self, message: impl Into<String>) {
+        let index = self.next_input();
+        self.reject(index, ImportErrorKind::Malformed(message.into()));
+    }
+
+    /// Returns id the task will have, or `None` if it's a duplicate or
+    /// invalid.
+    pub fn create_task(&mut self, input: BlocksWithAnswer) -> Option<TaskId> {
+        let index = self.next_input();
+        let input = match validate(&input).and_then(|()| self.limit_content(input)) {
+            Ok(input) => input,
+            Err(reason) => {
+                self.reject(index, ImportErrorKind::Rejected(reason));
+                return None;
+            }
+        };
+        self.add(index, T::new(input))
+    }
+    /// Returns id the task will have, or `None` if it's a duplicate or
+    /// invalid.
+    pub fn insert(&mut self, task: T) -> Option<TaskId> {
+        let index = self.next_input();
+        if let Err(reason) = validate(&task.get_blocks()) {
+            self.reject(index, ImportErrorKind::Rejected(reason));
+            return None;
+        }
+        self.add(index, task)
+    }
+    /// [`Facade::create_from_template`] for a single row. Returns id the task
+    /// will have, or `None` if the row can't be rendered, or the task is a
+    /// duplicate or invalid.
+    pub fn create_from_template(&mut self, name: &str, row: TemplateRow) -> Option<TaskId> {
+        let index = self.next_input();
+        let rendered = self
+            .facade
+            .template(name)
+            .ok_or_else(|| TemplateError::UnknownTemplate(name.to_owned()))
+            .and_then(|template| template.render(&row))
+            .map_err(|err| match err {
+                TemplateError::MissingVariable { variable, .. } => TemplateError::MissingVariable {
+                    row: index,
+                    variable,
+                },
+                err => err,
+            });
+        let content = match rendered {
+            Ok(content) => content,
+            Err(err) => {
+                self.reject(index, ImportErrorKind::Template(err));
+                return None;
+            }
+        };
+        let content = match validate(&content).and_then(|()| self.limit_content(content)) {
+            Ok(content) => content,
+            Err(reason) => {
+                self.reject(index, ImportErrorKind::Rejected(reason));
+                return None;
+            }
+        };
+        let id = self.add(index, T::new(content))?;
+        if let Some(wrapper) = self.pending.last_mut() {
+            wrapper.derivation = Some(Derivation::new(name, row));
+        }
+        Some(id)
+    }
+
+    fn next_input(&mut self) -> usize {
+        self.next_index += 1;
+        self.next_index - 1
+    }
+    fn reject(&mut self, index: usize, kind: ImportErrorKind) {
+        let (line, raw) = self.source.take().unzip();
+        self.report.rejected.push(ImportError {
+            index,
+            line,
+            raw,
+            kind,
+        });
+    }
+    /// [`Facade::limit_content`] keeping text moved out of line with the
+    /// pending tasks until commit.
+    fn limit_content(
+        &mut self,
+        content: BlocksWithAnswer,
+    ) -> Result<BlocksWithAnswer, RejectReason> {
+        match self.facade.content_limit {
+            Some(limit) => limit.apply(content, &mut self.pending_media),
+            None => Ok(content),
+        }
+    }
+    fn add(&mut self, index: usize, task: T) -> Option<TaskId> {
+        self.source = None;
+        let content = task.get_blocks();
+        let key = content_key(&content);
+        if self.is_duplicate(key, &content) {
+            self.report.duplicates.push(index);
+            return None;
+        }
+        // Buffered tasks don't change the facade, so each draws from its own
+        // random stream for the deterministic mode.
+        let mut rng = self.facade.rng(&format!("import {}", self.pending.len()));
+        let pending_ids = &self.pending_ids;
+        let id = self
+            .facade
+            .new_task_id_except(&task, &mut rng, |id| pending_ids.contains(&id));
+        self.pending_ids.insert(id);
+        self.known_content
+            .entry(key)
+            .or_default()
+            .push(Known::Pending(self.pending.len()));
+        self.pending.push(TaskWrapper::new(task, id));
+        self.report.created.push(index);
+        Some(id)
+    }
+    /// Whether a known task has the same content. Equal keys alone don't
+    /// make a duplicate: different content may share a 64-bit key.
+    fn is_duplicate(&self, key: u64, content: &BlocksWithAnswer) -> bool {
+        let Some(known) = self.known_content.get(&key) else {
+            return false;
+        };
+        let content = format!("{content:?}");
+        known.iter().any(|known| {
+            let task = match *known {
+                Known::Existing(id) => self.facade.find_task(id),
+                Known::Pending(index) => self.pending.get(index),
+            };
+            task.is_some_and(|task| format!("{:?}", task.task.get_blocks()) == content)
+        })
+    }
+
+    #[must_use]
+    pub fn pending(&self) -> usize {
+        self.pending.len()
+    }
+    #[must_use]
+    pub fn duplicates(&self) -> usize {
+        self.report.duplicates.len()
+    }
+    /// What committing now would do.
+    #[must_use]
+    pub fn report(&self) -> &ImportReport {
+        &self.report
+    }
+
+    /// Add all buffered tasks to the facade, skipping rejected inputs.
+    #[must_use]
+    pub fn commit(self) -> ImportSummary {
+        let Self {
+            facade,
+            pending,
+            pending_media,
+            report,
+            ..
+        } = self;
+        facade.media.extend(pending_media);
+        let imported: Vec<_> = pending.iter().map(|t| t.id).collect();
+        for task in pending {
+            facade.push_new_wrapper(task);
+        }
+        facade.find_tasks_to_recall();
+        facade.events.push(Event::TasksImported {
+            ids: imported.clone(),
+        });
+        ImportSummary {
+            imported,
+            duplicates: report.duplicates.len(),
+            rejected: report.rejected.len(),
+        }
+    }
+    /// Commit only if no input was rejected; otherwise nothing is imported
+    /// and the report is returned.
+    /// # Errors
+    /// If some input was rejected.
+    pub fn commit_if_clean(self) -> Result<ImportSummary, ImportReport> {
+        if self.report.rejected.is_empty() {
+            Ok(self.commit())
+        } else {
+            Err(self.report)
+        }
+    }
+}
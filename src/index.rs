@@ -0,0 +1,111 @@
+use std::{
+    collections::HashMap,
+    sync::{Mutex, PoisonError},
+};
+
+use ssr_core::{task::Task, tasks_facade::TaskId};
+
+use crate::{Facade, TaskWrapper};
+
+#[derive(Debug, Clone, Copy)]
+enum Slot {
+    Pool(usize),
+    Recall(usize),
+}
+
+#[derive(Debug, Default)]
+struct Positions {
+    /// Facade epoch and task count the positions were taken at.
+    taken_at: Option<(u64, usize)>,
+    slots: HashMap<TaskId, Slot>,
+}
+
+/// Positions of tasks in the queues, rebuilt lazily. A found slot is always
+/// checked against the queue, so a stale index costs a rebuild, never a
+/// wrong answer.
+#[derive(Debug, Default)]
+pub(crate) struct TaskIndex(Mutex<Positions>);
+
+impl Clone for TaskIndex {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+impl<'a, T: Task<'a>> Facade<'a, T> {
+    fn slot(&self, id: TaskId) -> Option<Slot> {
+        let matches = |slot: Slot| {
+            let queue = match slot {
+                Slot::Pool(i) => self.tasks_pool.get(i),
+                Slot::Recall(i) => self.tasks_to_recall.get(i),
+            };
+            queue.is_some_and(|task| task.id == id)
+        };
+        let mut positions = self.index.0.lock().unwrap_or_else(PoisonError::into_inner);
+        let current = (
+            self.epoch,
+            self.tasks_pool.len() + self.tasks_to_recall.len(),
+        );
+        match positions.slots.get(&id) {
+            Some(&slot) if matches(slot) => return Some(slot),
+            None if positions.taken_at == Some(current) => return None,
+            _ => {}
+        }
+        positions.slots.clear();
+        let pool = (self.tasks_pool.iter().enumerate()).map(|(i, t)| (t.id, Slot::Pool(i)));
+        let recall =
+            (self.tasks_to_recall.iter().enumerate()).map(|(i, t)| (t.id, Slot::Recall(i)));
+        for (id, slot) in pool.chain(recall) {
+            positions.slots.entry(id).or_insert(slot);
+        }
+        positions.taken_at = Some(current);
+        positions.slots.get(&id).copied()
+    }
+    pub(crate) fn locate(&self, id: TaskId) -> Option<&TaskWrapper<T>> {
+        match self.slot(id)? {
+            Slot::Pool(i) => self.tasks_pool.get(i),
+            Slot::Recall(i) => self.tasks_to_recall.get(i),
+        }
+    }
+    pub(crate) fn locate_mut(&mut self, id: TaskId) -> Option<&mut TaskWrapper<T>> {
+        match self.slot(id)? {
+            Slot::Pool(i) => self.tasks_pool.get_mut(i),
+            Slot::Recall(i) => self.tasks_to_recall.get_mut(i),
+        }
+    }
+
+    /// Take the task out of its queue, keeping the index current.
+    pub(crate) fn take_task(&mut self, id: TaskId) -> Option<TaskWrapper<T>> {
+        let slot = self.slot(id)?;
+        let count = self.tasks_pool.len() + self.tasks_to_recall.len();
+        let (task, moved) = match slot {
+            Slot::Pool(i) => {
+                let task = self.tasks_pool.swap_remove(i);
+                (task, self.tasks_pool.get(i).map(|t| (t.id, Slot::Pool(i))))
+            }
+            Slot::Recall(i) => {
+                let task = self.tasks_to_recall.swap_remove(i);
+                (
+                    task,
+                    self.tasks_to_recall.get(i).map(|t| (t.id, Slot::Recall(i))),
+                )
+            }
+        };
+        let mut positions = self.index.0.lock().unwrap_or_else(PoisonError::into_inner);
+        positions.slots.remove(&id);
+        if let Some((moved, slot)) = moved {
+            positions.slots.insert(moved, slot);
+        }
+        if positions.taken_at == Some((self.epoch, count)) {
+            positions.taken_at = Some((self.epoch, count - 1));
+        }
+        Some(task)
+    }
+
+    pub fn get_task(&self, id: TaskId) -> Option<&T> {
+        self.locate(id).map(|task| &task.task)
+    }
+    pub fn contains(&self, id: TaskId) -> bool {
+        self.locate(id).is_some()
+    }
+}
@@ -1,15 +1,156 @@
 #![warn(clippy::pedantic)]
-#![feature(extract_if, hash_extract_if, iter_collect_into)]
+#![feature(iter_collect_into)]
 
-use std::time::{Duration, SystemTime};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    time::{Duration, SystemTime},
+};
 
-use rand::{thread_rng, Rng};
+use itertools::Itertools;
+use rand::{Rng, thread_rng};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use ssr_core::{
     task::{SharedStateExt, Task},
     tasks_facade::{TaskId, TasksFacade},
 };
 
+mod adjust;
+mod app;
+mod auto_tag;
+mod backup;
+mod bulk;
+mod bury;
+mod children;
+mod citation;
+mod clock;
+mod collection;
+mod collection_session;
+mod confidence;
+mod content;
+mod content_limit;
+mod dashboard;
+mod delta;
+mod deterministic;
+mod edit;
+mod event;
+mod explain;
+mod fixed_interval;
+mod format;
+mod freeze;
+mod id;
+mod idle;
+mod import;
+mod index;
+mod load;
+mod maintenance;
+mod media;
+mod merge;
+mod migrate;
+mod multiplier;
+mod notes;
+mod offline;
+mod optimize;
+mod ordering;
+mod parameter_groups;
+mod perf;
+mod position;
+mod prerequisites;
+mod priority;
+mod query;
+mod read_only;
+mod reschedule;
+mod research_export;
+mod reverse;
+mod review_ahead;
+mod review_log;
+mod reviewer;
+mod rollover;
+mod sandbox;
+mod schema;
+mod session;
+mod settings;
+mod shift;
+mod simulate;
+mod snapshot;
+mod split;
+mod stale;
+mod stats;
+mod status;
+mod storage;
+mod tags;
+mod task_ref;
+mod task_state;
+mod template;
+mod time;
+mod time_travel;
+mod trash;
+mod unknown_fields;
+
+pub use app::{App, AppConfig, AppError, ReadFn, WriteFn};
+pub use auto_tag::{AutoTagPolicy, EASY_TAG, LEECH_TAG};
+pub use backup::BackupInfo;
+pub use bulk::Action;
+pub use collection::{Collection, DECK_SEPARATOR, DeckMeta};
+pub use collection_session::{CollectionSession, CollectionSessionConfig};
+pub use confidence::ConfidenceTask;
+pub use content_limit::{ContentLimit, OversizePolicy, TRUNCATION_MARKER};
+pub use delta::Delta;
+pub use event::Event;
+pub use explain::{DueSource, Explanation};
+pub use fixed_interval::{DEFAULT_FIXED_INTERVAL, FixedIntervalTask};
+pub use format::{FORMAT_VERSION, Loaded, NewerFormatError, NewerFormatPolicy};
+pub use id::IdGenerator;
+pub use idle::IdleTracker;
+pub use import::{
+    ImportError, ImportErrorKind, ImportReport, ImportSummary, Importer, RejectReason,
+};
+pub use load::{LoadDiagnostics, LoadError, Repair};
+pub use maintenance::{BackupPolicy, MaintenancePolicy, MaintenanceReport};
+pub use media::{MEDIA_REFERENCE_PREFIX, MediaStore};
+pub use merge::{MergePolicy, MergeSummary};
+pub use notes::{NOTE_ANSWER_MARKER, NOTE_QUESTION_MARKER, NoteCard, NoteSyncReport, parse_note};
+pub use offline::{OfflineAnswer, ReviewResultError};
+pub use optimize::{DEFAULT_MIN_REVIEWS_FOR_OPTIMIZE, OptimizeError};
+pub use perf::PerfCounters;
+pub use prerequisites::DEFAULT_PREREQUISITE_MATURITY;
+pub use priority::{EXAM_TAG_PREFIX, PriorityRules};
+pub use query::{Query, QueryError};
+pub use read_only::{ReadOnly, ReadOnlyError};
+pub use reschedule::{DueOverride, OverrideKind};
+pub use reverse::ReverseMode;
+pub use review_ahead::{DEFAULT_LEARN_AHEAD, DEFAULT_REVIEW_AHEAD_CAP};
+pub use review_log::{Outcome, ReviewLog, ReviewRecord, ReviewSource};
+pub use rollover::{DailyCounters, DailyLimitReached};
+pub use sandbox::Sandbox;
+pub use session::{
+    NewTaskMix, Session, SessionConfig, SessionError, SessionProgress, SessionSummary,
+};
+pub use settings::{SettingValue, Settings};
+pub use shift::DueShift;
+pub use simulate::{SimulatedDay, SimulatedReview, Simulation, SimulationConfig};
+pub use snapshot::TaskSnapshot;
+pub use stale::StaleTask;
+pub use stats::{CalibrationBin, FacadeStats, MATURE_INTERVAL, PerformanceBucket};
+pub use status::{FacadeStatus, ForecastDay, ForecastInclusion, ForecastOptions, NoTaskContext};
+pub use storage::BackupStore;
+pub use task_ref::TaskRef;
+pub use task_state::{TaskState, TaskStates};
+pub use template::{Template, TemplateError, TemplateRow};
+pub use time::LocalDate;
+pub use time_travel::PastState;
+pub use trash::DEFAULT_TRASH_RETENTION;
+
+use index::TaskIndex;
+use notes::NoteLink;
+use parameter_groups::ParameterGroups;
+use perf::PerfRegistry;
+use prerequisites::TagPrerequisites;
+use rollover::DayState;
+use session::TaskKind;
+use template::Derivation;
+use trash::TrashedTask;
+use unknown_fields::UnknownFields;
+
 fn serialize_id<S>(id: &TaskId, serializer: S) -> Result<S::Ok, S::Error>
 where
     S: Serializer,
@@ -27,25 +168,155 @@ where
     Ok(id)
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(bound(deserialize = "T: Task<'de>"))]
+#[allow(clippy::struct_excessive_bools)]
 struct TaskWrapper<T> {
     task: T,
+    /// State of the task after only the reviews of each reviewer, see
+    /// [`Facade::set_schedule_reviewer`].
+    #[serde(default)]
+    reviewer_tasks: BTreeMap<String, T>,
     #[serde(serialize_with = "serialize_id", deserialize_with = "deserialize_id")]
     id: TaskId,
+    #[serde(default)]
+    tags: BTreeSet<String>,
+    #[serde(default)]
+    suspended: bool,
+    #[serde(default)]
+    buried_until: Option<SystemTime>,
+    /// See [`Facade::freeze`].
+    #[serde(default)]
+    frozen_until: Option<SystemTime>,
+    /// Due time at the last scheduling, see [`Facade::iter_by_due`].
+    #[serde(default)]
+    sort_due: Option<SystemTime>,
+    /// Due tasks with higher priority are handed out first.
+    #[serde(default)]
+    priority: i32,
+    /// When `priority` was last set, see [`PriorityRules::manual_decay_after`].
+    #[serde(default)]
+    priority_set_at: Option<SystemTime>,
+    /// Added to `priority` by [`PriorityRules`].
+    #[serde(default)]
+    priority_boost: i32,
+    /// Replaces scheduler's due time until the next completion.
+    #[serde(default)]
+    due_override: Option<SystemTime>,
+    /// `due_override` was set by the user.
+    #[serde(default)]
+    manual_due: bool,
+    #[serde(default)]
+    min_interval: Option<Duration>,
+    #[serde(default)]
+    excluded_from_optimization: bool,
+    /// Order of introduction while the task is new.
+    #[serde(default)]
+    position: u64,
+    /// Tasks that must be mature before this one is scheduled.
+    #[serde(default)]
+    prerequisites: BTreeSet<TaskId>,
+    /// Other side of a bidirectional pair.
+    #[serde(default)]
+    sibling: Option<TaskId>,
+    /// Overrides parameter group assigned through tags.
+    #[serde(default)]
+    parameter_group: Option<String>,
+    /// Template the task was created from, see [`Facade::set_template`].
+    #[serde(default)]
+    derivation: Option<Derivation>,
+    /// See [`Facade::set_interval_multiplier`].
+    #[serde(default)]
+    interval_multiplier: Option<f64>,
+    /// When the task was created or its content last replaced, see
+    /// [`Facade::stale_tasks`]. Unknown for tasks saved before it was
+    /// recorded.
+    #[serde(default)]
+    content_changed_at: Option<SystemTime>,
+    /// Content was replaced with a fresh task, so the next review starts its
+    /// schedule over and is logged as [`Outcome::New`].
+    #[serde(default)]
+    state_reset: bool,
+    /// See [`Facade::citation_key`].
+    #[serde(default = "citation::new_citation_key")]
+    citation_key: String,
+    /// Facade epoch of the last change.
+    #[serde(default)]
+    modified: u64,
 }
 
 impl<'a, T: Task<'a>> TaskWrapper<T> {
-    fn new(value: T) -> Self {
+    fn new(value: T, id: TaskId) -> Self {
         Self {
             task: value,
-            id: rand::random(),
+            reviewer_tasks: BTreeMap::new(),
+            id,
+            tags: BTreeSet::new(),
+            suspended: false,
+            buried_until: None,
+            frozen_until: None,
+            sort_due: None,
+            priority: 0,
+            priority_set_at: None,
+            priority_boost: 0,
+            due_override: None,
+            manual_due: false,
+            min_interval: None,
+            excluded_from_optimization: false,
+            position: 0,
+            prerequisites: BTreeSet::new(),
+            sibling: None,
+            parameter_group: None,
+            derivation: None,
+            interval_multiplier: None,
+            content_changed_at: None,
+            state_reset: false,
+            citation_key: citation::new_citation_key(),
+            modified: 0,
         }
     }
+
+    fn effective_priority(&self) -> i32 {
+        self.priority.saturating_add(self.priority_boost)
+    }
+    /// Due time by the reviews of `reviewer`, or of everyone if `None`.
+    fn next_repetition(
+        &self,
+        state: &T::SharedState,
+        desired_retention: f64,
+        reviewer: Option<&str>,
+    ) -> SystemTime {
+        self.due_override.unwrap_or_else(|| match reviewer {
+            None => self.task.next_repetition(state, desired_retention),
+            Some(reviewer) => match self.reviewer_tasks.get(reviewer) {
+                Some(task) => task.next_repetition(state, desired_retention),
+                // The reviewer hasn't reviewed it yet, so it's new to them.
+                None => T::new(self.task.get_blocks()).next_repetition(state, desired_retention),
+            },
+        })
+    }
+    /// When the task may be handed out for review, `None` while suspended.
+    fn available_at(
+        &self,
+        state: &T::SharedState,
+        desired_retention: f64,
+        reviewer: Option<&str>,
+    ) -> Option<SystemTime> {
+        if self.suspended {
+            return None;
+        }
+        let due = self.next_repetition(state, desired_retention, reviewer);
+        Some(
+            [self.buried_until, self.frozen_until]
+                .into_iter()
+                .flatten()
+                .fold(due, SystemTime::max),
+        )
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug)]
-#[serde(bound(deserialize = "'a: 'de, 'de: 'a"))]
+#[serde(remote = "Self", bound(deserialize = "'a: 'de, 'de: 'a"))]
 pub struct Facade<'a, T>
 where
     T: Task<'a>,
@@ -55,44 +326,611 @@ where
     tasks_to_recall: Vec<TaskWrapper<T>>,
     desired_retention: f64,
     state: T::SharedState,
+    /// See [`Facade::format_version`].
+    #[serde(default, serialize_with = "format::serialize_format_version")]
+    format_version: u32,
+    #[serde(default)]
+    parameter_groups: ParameterGroups<T::SharedState>,
+    #[serde(default)]
+    last_optimized: Option<SystemTime>,
+    #[serde(default = "optimize::default_min_reviews_for_optimize")]
+    min_reviews_for_optimize: usize,
+    #[serde(default)]
+    optimization_excluded_tags: BTreeSet<String>,
+    #[serde(default)]
+    min_interval: Duration,
+    #[serde(default)]
+    max_interval_growth: Option<f64>,
+    /// Latest time seen in this session, see [`Facade::reset_clock_skew`].
+    /// Older versions saved it; it is read and forgotten.
+    #[serde(
+        default,
+        skip_serializing,
+        deserialize_with = "clock::forget_latest_seen_time"
+    )]
+    latest_seen_time: Option<SystemTime>,
+    #[serde(skip)]
+    clock_skew: Option<Duration>,
+    /// See [`Facade::set_clock`].
+    #[serde(skip)]
+    clock: Option<fn() -> SystemTime>,
+    /// See [`Facade::set_deterministic`].
+    #[serde(default)]
+    deterministic_seed: Option<u64>,
+    /// Incremented on every change of a task, see [`Facade::epoch`].
+    #[serde(default)]
+    epoch: u64,
+    /// Ids of removed tasks with epoch of removal.
+    #[serde(default)]
+    deleted: Vec<(TaskId, u64)>,
+    #[serde(default)]
+    review_log: ReviewLog,
+    #[serde(default)]
+    trash: Vec<TrashedTask<T>>,
+    #[serde(default = "trash::default_trash_retention")]
+    trash_retention: Duration,
+    #[serde(default)]
+    auto_tag_policy: AutoTagPolicy,
+    #[serde(default)]
+    id_generator: IdGenerator,
+    #[serde(default)]
+    content_limit: Option<ContentLimit>,
+    /// See [`Facade::media`].
+    #[serde(default)]
+    media: MediaStore,
+    #[serde(default)]
+    next_position: u64,
+    #[serde(default)]
+    utc_offset_secs: i32,
+    /// Local hour at which a new day starts.
+    #[serde(default)]
+    rollover_hour: u8,
+    /// See [`Facade::set_day_granularity`].
+    #[serde(default)]
+    day_granularity: bool,
+    #[serde(skip)]
+    reviewer: Option<String>,
+    #[serde(default)]
+    stats_reviewer: Option<String>,
+    /// See [`Facade::set_schedule_reviewer`].
+    #[serde(default)]
+    schedule_reviewer: Option<String>,
+    #[serde(default)]
+    settings: Settings,
+    #[serde(default)]
+    templates: BTreeMap<String, Template>,
+    #[serde(default)]
+    day: DayState,
+    #[serde(default = "review_ahead::default_review_ahead_cap")]
+    review_ahead_cap: Option<Duration>,
+    #[serde(default = "review_ahead::default_learn_ahead")]
+    learn_ahead: Duration,
+    #[serde(default)]
+    tag_prerequisites: TagPrerequisites,
+    #[serde(default = "prerequisites::default_prerequisite_maturity")]
+    prerequisite_maturity: Duration,
+    #[serde(default)]
+    sibling_spacing: Option<Duration>,
+    #[serde(default)]
+    tag_interval_multipliers: BTreeMap<String, f64>,
+    #[serde(default)]
+    new_per_day: Option<usize>,
+    #[serde(default)]
+    reviews_per_day: Option<usize>,
+    /// Note cards mirrored by [`Facade::sync_notes`], by card key.
+    #[serde(default)]
+    note_links: BTreeMap<String, NoteLink>,
+    /// Sub-decks by name, see [`Facade::insert_child`].
+    #[serde(default)]
+    children: BTreeMap<String, Facade<'a, T>>,
+    /// Written back on save, see [`LoadDiagnostics::unknown_fields`].
+    #[serde(skip)]
+    unknown_fields: UnknownFields,
+    #[serde(skip)]
+    events: Vec<Event>,
+    #[serde(skip)]
+    perf: PerfRegistry,
+    #[serde(skip)]
+    index: TaskIndex,
+    #[serde(skip)]
+    recall_check: Option<RecallCheck>,
+    #[serde(skip)]
+    idle: IdleTracker,
+}
+
+impl<'a, T: Task<'a>> Serialize for Facade<'a, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        Self::serialize(
+            self,
+            unknown_fields::WithUnknown::new(serializer, &self.unknown_fields),
+        )
+    }
+}
+impl<'de, 'a, T> Deserialize<'de> for Facade<'a, T>
+where
+    'a: 'de,
+    'de: 'a,
+    T: Task<'a>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut unknown_fields = UnknownFields::new();
+        let mut facade = Self::deserialize(unknown_fields::Capturing::new(
+            deserializer,
+            &mut unknown_fields,
+        ))?;
+        facade.unknown_fields = unknown_fields;
+        Ok(facade)
+    }
+}
+
+/// Result of the last full scan in [`Facade::find_tasks_to_recall`], valid
+/// while `epoch` and pool length stay the same.
+#[derive(Debug, Clone, Copy)]
+struct RecallCheck {
+    epoch: u64,
+    pool_len: usize,
+    /// Earliest moment some pool task becomes available.
+    next_available: Option<SystemTime>,
+}
+
+impl<'a, T> Clone for Facade<'a, T>
+where
+    T: Task<'a> + Clone,
+    T::SharedState: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            name: self.name.clone(),
+            tasks_pool: self.tasks_pool.clone(),
+            tasks_to_recall: self.tasks_to_recall.clone(),
+            desired_retention: self.desired_retention,
+            state: self.state.clone(),
+            format_version: self.format_version,
+            parameter_groups: self.parameter_groups.clone(),
+            last_optimized: self.last_optimized,
+            min_reviews_for_optimize: self.min_reviews_for_optimize,
+            optimization_excluded_tags: self.optimization_excluded_tags.clone(),
+            min_interval: self.min_interval,
+            max_interval_growth: self.max_interval_growth,
+            latest_seen_time: self.latest_seen_time,
+            clock_skew: self.clock_skew,
+            clock: self.clock,
+            deterministic_seed: self.deterministic_seed,
+            epoch: self.epoch,
+            deleted: self.deleted.clone(),
+            review_log: self.review_log.clone(),
+            trash: self.trash.clone(),
+            trash_retention: self.trash_retention,
+            auto_tag_policy: self.auto_tag_policy.clone(),
+            id_generator: self.id_generator,
+            content_limit: self.content_limit,
+            media: self.media.clone(),
+            next_position: self.next_position,
+            utc_offset_secs: self.utc_offset_secs,
+            rollover_hour: self.rollover_hour,
+            day_granularity: self.day_granularity,
+            reviewer: self.reviewer.clone(),
+            stats_reviewer: self.stats_reviewer.clone(),
+            schedule_reviewer: self.schedule_reviewer.clone(),
+            settings: self.settings.clone(),
+            templates: self.templates.clone(),
+            day: self.day,
+            review_ahead_cap: self.review_ahead_cap,
+            learn_ahead: self.learn_ahead,
+            tag_prerequisites: self.tag_prerequisites.clone(),
+            prerequisite_maturity: self.prerequisite_maturity,
+            sibling_spacing: self.sibling_spacing,
+            tag_interval_multipliers: self.tag_interval_multipliers.clone(),
+            new_per_day: self.new_per_day,
+            reviews_per_day: self.reviews_per_day,
+            note_links: self.note_links.clone(),
+            children: self.children.clone(),
+            unknown_fields: self.unknown_fields.clone(),
+            events: self.events.clone(),
+            perf: self.perf.clone(),
+            index: TaskIndex::default(),
+            recall_check: self.recall_check,
+            idle: IdleTracker::default(),
+        }
+    }
 }
 
 impl<'a, T: Task<'a>> Facade<'a, T> {
+    /// Skips the scan of the pool if nothing changed since the previous one
+    /// and no pool task became available since. Sub-decks are scanned too.
     pub fn find_tasks_to_recall(&mut self) {
-        let now = SystemTime::now() + Duration::from_secs(10);
+        for child in self.children.values_mut() {
+            child.find_tasks_to_recall();
+        }
+        let now = self.observe_now() + self.learn_ahead;
+        if self.recall_check.is_some_and(|check| {
+            check.epoch == self.epoch
+                && check.pool_len == self.tasks_pool.len()
+                && check.next_available.is_none_or(|next| now < next)
+        }) {
+            return;
+        }
+        let start = self.perf.start();
+        let immature = self.immature_prerequisites();
+        let mut new_left = self.new_tasks_left_today();
+        let day_offset = self.day_granularity_offset();
+        let mut next_available = None::<SystemTime>;
         self.tasks_pool
             .extract_if(.., |t| {
-                t.task.next_repetition(&self.state, self.desired_retention) <= now
+                if immature
+                    .as_ref()
+                    .is_some_and(|immature| immature.blocks(t, &self.tag_prerequisites))
+                {
+                    return false;
+                }
+                let is_new = new_left.is_some() && self.review_log.for_task(t.id).next().is_none();
+                if is_new && new_left == Some(0) {
+                    return false;
+                }
+                self.perf.scheduler_evaluation();
+                self.perf.comparison();
+                let Some(available_at) = t.available_at(
+                    self.parameter_groups.state_for(&self.state, t),
+                    self.desired_retention,
+                    self.schedule_reviewer.as_deref(),
+                ) else {
+                    return false;
+                };
+                let available_at = time::due_from(available_at, day_offset);
+                if available_at <= now {
+                    if is_new {
+                        new_left = new_left.map(|left| left - 1);
+                    }
+                    return true;
+                }
+                next_available = Some(next_available.map_or(available_at, |n| n.min(available_at)));
+                false
             })
             .collect_into(&mut self.tasks_to_recall);
+        // Reviews can mature prerequisites, so gated pools are always scanned.
+        self.recall_check = immature.is_none().then_some(RecallCheck {
+            epoch: self.epoch,
+            pool_len: self.tasks_pool.len(),
+            next_available,
+        });
+        self.perf.queue_rebuild(start);
     }
     pub fn reload_all_tasks_timings(&mut self) {
+        self.recall_check = None;
         self.tasks_to_recall
             .drain(..)
             .collect_into(&mut self.tasks_pool);
+        let dues = (self.tasks_pool.iter())
+            .map(|task| self.next_repetition(task))
+            .collect::<Vec<_>>();
+        for (task, due) in self.tasks_pool.iter_mut().zip(dues) {
+            task.sort_due = Some(due);
+        }
         self.find_tasks_to_recall();
     }
 
-    fn take_random_task(&mut self) -> Option<TaskWrapper<T>> {
-        if self.tasks_to_recall.is_empty() {
-            return None;
+    fn next_repetition(&self, task: &TaskWrapper<T>) -> SystemTime {
+        self.perf.scheduler_evaluation();
+        task.next_repetition(
+            self.parameter_groups.state_for(&self.state, task),
+            self.desired_retention,
+            self.schedule_reviewer.as_deref(),
+        )
+    }
+    fn available_at(&self, task: &TaskWrapper<T>) -> Option<SystemTime> {
+        self.perf.scheduler_evaluation();
+        task.available_at(
+            self.parameter_groups.state_for(&self.state, task),
+            self.desired_retention,
+            self.schedule_reviewer.as_deref(),
+        )
+    }
+    fn is_due(&self, task: &TaskWrapper<T>, now: SystemTime) -> bool {
+        self.perf.comparison();
+        self.available_at(task)
+            .is_some_and(|available_at| self.due_from(available_at) <= now)
+    }
+    fn next_epoch(&mut self) -> u64 {
+        self.epoch += 1;
+        self.epoch
+    }
+    /// Put task into the queue it belongs to.
+    fn place_task(&mut self, mut task: TaskWrapper<T>) {
+        task.modified = self.next_epoch();
+        self.store_sort_key(&mut task);
+        let gated = self
+            .immature_prerequisites()
+            .is_some_and(|immature| immature.blocks(&task, &self.tag_prerequisites));
+        if !gated && self.is_due(&task, self.now() + self.learn_ahead) {
+            self.tasks_to_recall.push(task);
+        } else {
+            self.tasks_pool.push(task);
+        }
+    }
+    /// Adds the task to the pool as new.
+    fn push_new_task(&mut self, task: T) -> &mut TaskWrapper<T> {
+        let id = self.new_task_id(&task);
+        self.push_new_wrapper(TaskWrapper::new(task, id))
+    }
+    /// Adds the task with an id picked by [`Facade::new_task_id`] to the pool
+    /// as new.
+    fn push_new_wrapper(&mut self, mut wrapper: TaskWrapper<T>) -> &mut TaskWrapper<T> {
+        wrapper.content_changed_at = Some(self.now());
+        if self.deterministic_seed.is_some() {
+            wrapper.citation_key = citation::new_citation_key_with(&mut self.rng("citation"));
+        }
+        wrapper.position = self.take_next_position();
+        wrapper.modified = self.next_epoch();
+        self.store_sort_key(&mut wrapper);
+        self.tasks_pool.push(wrapper);
+        self.tasks_pool.last_mut().expect("task was just pushed")
+    }
+    fn find_task(&self, id: TaskId) -> Option<&TaskWrapper<T>> {
+        self.locate(id)
+    }
+    /// Marks found task as modified.
+    fn find_task_mut(&mut self, id: TaskId) -> Option<&mut TaskWrapper<T>> {
+        let epoch = self.next_epoch();
+        let task = self.locate_mut(id)?;
+        task.modified = epoch;
+        Some(task)
+    }
+    fn iter_ids(&self) -> impl Iterator<Item = TaskId> {
+        self.tasks_pool
+            .iter()
+            .chain(self.tasks_to_recall.iter())
+            .map(|t| t.id)
+    }
+
+    /// Random task of `kind` among due tasks with the highest priority, the
+    /// earliest due one in deterministic mode. If it's a new task, the new
+    /// one with the lowest position is taken instead.
+    fn take_random_task(&mut self, kind: TaskKind) -> Option<TaskWrapper<T>> {
+        let is_new = |t: &TaskWrapper<T>| self.review_log.for_task(t.id).next().is_none();
+        let allowed = |t: &TaskWrapper<T>| match kind {
+            TaskKind::Any => true,
+            TaskKind::New => is_new(t),
+            TaskKind::Reviewed => !is_new(t),
+        };
+        let priority = self
+            .tasks_to_recall
+            .iter()
+            .filter(|t| allowed(t))
+            .map(TaskWrapper::effective_priority)
+            .max()?;
+        let candidates: Vec<_> = self
+            .tasks_to_recall
+            .iter()
+            .positions(|t| t.effective_priority() == priority && allowed(t))
+            .collect();
+        let mut index = if self.deterministic_seed.is_some() {
+            (candidates.iter().copied())
+                .min_by_key(|&i| self.sort_key(&self.tasks_to_recall[i]))
+                .expect("there is a task with the highest priority")
+        } else {
+            candidates[thread_rng().gen_range(0..candidates.len())]
+        };
+        if self
+            .review_log
+            .last_review(self.tasks_to_recall[index].id)
+            .is_none()
+        {
+            index = candidates
+                .into_iter()
+                .filter(|&i| is_new(&self.tasks_to_recall[i]))
+                .min_by_key(|&i| {
+                    let task = &self.tasks_to_recall[i];
+                    (task.position, task.id)
+                })
+                .unwrap_or(index);
         }
-        let index = thread_rng().gen_range(0..self.tasks_to_recall.len());
         Some(self.tasks_to_recall.swap_remove(index))
     }
 
+    /// Do the queue upkeep `complete_task` needs before presenting a task:
+    /// day rollover and the O(n) scan for due tasks. Call it while the user
+    /// looks at the previous answer, and the next `complete_task` presents
+    /// its task without that work, unless something changed in between.
+    pub fn prepare_next_task(&mut self) {
+        if !self.rollover_if_needed() {
+            self.find_tasks_to_recall();
+        }
+    }
+
+    /// `complete_task` that labels the review with `source` in the review
+    /// log.
+    /// # Errors
+    /// If interaction return error or the daily review limit is reached, see
+    /// [`Facade::set_reviews_per_day`].
+    pub fn complete_task_as(
+        &mut self,
+        source: ReviewSource,
+        interaction: &mut impl FnMut(
+            TaskId,
+            s_text_input_f::Blocks,
+        ) -> std::io::Result<s_text_input_f::Response>,
+    ) -> Result<(), ssr_core::tasks_facade::Error> {
+        self.prepare_next_task();
+        self.check_daily_limit()?;
+        let Some(wrapper) = self.take_random_task(TaskKind::Any) else {
+            return Err(self.no_task_error());
+        };
+        self.complete_taken_task(wrapper, source, interaction)
+    }
+    /// `complete_task` that takes a task of the first kind in `preference`
+    /// that is due. Returns `Ok(false)` if tasks are due, but none of these
+    /// kinds.
+    pub(crate) fn complete_preferred_task(
+        &mut self,
+        preference: &[TaskKind],
+        interaction: &mut impl FnMut(
+            TaskId,
+            s_text_input_f::Blocks,
+        ) -> std::io::Result<s_text_input_f::Response>,
+    ) -> Result<bool, ssr_core::tasks_facade::Error> {
+        self.prepare_next_task();
+        self.check_daily_limit()?;
+        if self.tasks_to_recall.is_empty() {
+            return Err(self.no_task_error());
+        }
+        let Some(wrapper) = (preference.iter()).find_map(|&kind| self.take_random_task(kind))
+        else {
+            return Ok(false);
+        };
+        self.complete_taken_task(wrapper, ReviewSource::Normal, interaction)?;
+        Ok(true)
+    }
+    fn no_task_error(&self) -> ssr_core::tasks_facade::Error {
+        match self.until_next_repetition() {
+            Some(time_until_next_repetition) => ssr_core::tasks_facade::Error::NoTaskToComplete {
+                time_until_next_repetition,
+            },
+            None => ssr_core::tasks_facade::Error::NoTask,
+        }
+    }
+    /// Review task taken out of the queues. On failure the task is put back.
+    fn complete_taken_task(
+        &mut self,
+        wrapper: TaskWrapper<T>,
+        source: ReviewSource,
+        interaction: &mut impl FnMut(
+            TaskId,
+            s_text_input_f::Blocks,
+        ) -> std::io::Result<s_text_input_f::Response>,
+    ) -> Result<(), ssr_core::tasks_facade::Error> {
+        self.complete_taken_task_at(wrapper, source, None, interaction)
+    }
+    /// [`Facade::complete_taken_task`] for a review answered at `answered_at`
+    /// (not later than now). The review is logged at that time, the interval
+    /// starts there and it counts toward the limits of that day.
+    pub(crate) fn complete_taken_task_at(
+        &mut self,
+        mut wrapper: TaskWrapper<T>,
+        source: ReviewSource,
+        answered_at: Option<SystemTime>,
+        interaction: &mut impl FnMut(
+            TaskId,
+            s_text_input_f::Blocks,
+        ) -> std::io::Result<s_text_input_f::Response>,
+    ) -> Result<(), ssr_core::tasks_facade::Error> {
+        if self.is_frozen(&wrapper) {
+            return self.complete_frozen_task(wrapper, answered_at, interaction);
+        }
+        let id = wrapper.id;
+        let scheduled_due = self.next_repetition(&wrapper);
+        let group = self.parameter_groups.group_of(&wrapper).map(str::to_owned);
+        let state = self
+            .parameter_groups
+            .state_mut(&mut self.state, group.as_deref());
+        let (idle, media) = (&self.idle, &self.media);
+        let mut duration = Duration::ZERO;
+        let mut responses = Vec::new();
+        let result = wrapper
+            .task
+            .complete(state, self.desired_retention, &mut |blocks| {
+                let timer = idle.start_timer();
+                let response = interaction(id, media.resolve(blocks));
+                duration += timer.elapsed();
+                if let Ok(response) = &response {
+                    responses.push(response.clone());
+                }
+                response
+            });
+        if let Err(err) = result {
+            self.tasks_pool.push(wrapper);
+            self.find_tasks_to_recall();
+            return Err(err.into());
+        }
+        if let Some(reviewer) = &self.reviewer {
+            wrapper.replay_review(reviewer, state, self.desired_retention, responses);
+        }
+        wrapper.due_override = None;
+        let manually_scheduled = std::mem::take(&mut wrapper.manual_due);
+        let now = self.observe_now();
+        let reviewed_at = answered_at.map_or(now, |answered_at| answered_at.min(now));
+        let model_due = self.next_repetition(&wrapper);
+        // Task types count the interval from now.
+        let lag = now.duration_since(reviewed_at).unwrap_or_default();
+        let answered_due = model_due.checked_sub(lag).unwrap_or(model_due);
+        let next_due =
+            self.adjust_next_due(&wrapper, reviewed_at, answered_due, self.last_interval(id));
+        if next_due != model_due {
+            wrapper.due_override = Some(next_due);
+        }
+        let previous_review = self.review_log.last_review(id);
+        // Reviewed ahead, the task only lapses if it got less than the time
+        // that actually passed.
+        let judged_due = match source {
+            ReviewSource::ReviewAhead | ReviewSource::Cram => scheduled_due.min(reviewed_at),
+            _ => scheduled_due,
+        };
+        let outcome = if std::mem::take(&mut wrapper.state_reset) {
+            Outcome::New
+        } else {
+            Outcome::judge(previous_review, judged_due, reviewed_at, next_due)
+        };
+        self.review_log.insert(ReviewRecord {
+            task_id: id,
+            reviewed_at,
+            duration,
+            previous_review,
+            scheduled_due,
+            next_due,
+            desired_retention: self.desired_retention,
+            outcome,
+            source,
+            reviewer: self.reviewer.clone(),
+            manually_scheduled,
+            confidence: None,
+        });
+        if self.multiplier_of(&wrapper).is_some() {
+            self.review_log.push_override(DueOverride {
+                task_id: id,
+                at: reviewed_at,
+                previous_due: answered_due,
+                new_due: next_due,
+                kind: OverrideKind::Multiplier,
+            });
+        }
+        wrapper.modified = self.next_epoch();
+        self.store_sort_key(&mut wrapper);
+        self.tasks_pool.push(wrapper);
+        self.count_review(outcome, reviewed_at);
+        self.update_auto_tags(id);
+        Ok(())
+    }
+
+    /// Only this facade's own tasks are considered, not those of sub-decks.
     pub fn until_next_repetition(&self) -> Option<Duration> {
-        if self.tasks_total() == 0 {
+        if self.tasks_pool.is_empty() && self.tasks_to_recall.is_empty() {
             None
-        } else if self.tasks_to_complete() > 0 {
+        } else if !self.tasks_to_recall.is_empty() {
             Some(Duration::default())
         } else {
+            let immature = self.immature_prerequisites();
+            let now = self.now();
+            // New tasks over the daily limit wait for the next day.
+            let held_until = (self.new_tasks_left_today() == Some(0))
+                .then(|| time::next_local_midnight(now, self.day_offset_secs()));
             self.tasks_pool
                 .iter()
-                .map(|t| {
-                    t.task
-                        .next_repetition(&self.state, self.desired_retention)
-                        .duration_since(SystemTime::now())
+                .filter(|t| {
+                    !immature
+                        .as_ref()
+                        .is_some_and(|immature| immature.blocks(t, &self.tag_prerequisites))
+                })
+                .filter_map(|t| {
+                    let available_at = self.due_from(self.available_at(t)?);
+                    Some(match held_until {
+                        Some(held_until) if self.review_log.for_task(t.id).next().is_none() => {
+                            available_at.max(held_until)
+                        }
+                        _ => available_at,
+                    })
+                })
+                .map(|available_at| {
+                    available_at
+                        .duration_since(self.now())
                         .unwrap_or(Duration::default())
                 })
                 .min()
@@ -100,20 +938,23 @@ impl<'a, T: Task<'a>> Facade<'a, T> {
     }
 }
 impl<'a, F: Task<'a>> Facade<'a, F> {
+    /// Tags are kept, sub-decks are migrated too.
     /// # Warning
-    /// You will loose all progress.
+    /// You will loose all progress, see [`Facade::migrate_with`] to keep it.
     pub fn migrate<T: Task<'a> + std::fmt::Debug>(&self) -> Facade<'a, T>
     where
         T::SharedState: std::fmt::Debug,
     {
-        let task_templates = self
-            .tasks_pool
-            .iter()
-            .chain(self.tasks_to_recall.iter())
-            .map(|t| t.task.get_blocks());
         let mut new_facade = Facade::new(self.name.clone(), self.desired_retention);
-        for i in task_templates {
-            new_facade.create_task(i);
+        for task in self.tasks_pool.iter().chain(self.tasks_to_recall.iter()) {
+            new_facade
+                .push_new_task(T::new(task.task.get_blocks()))
+                .tags
+                .clone_from(&task.tags);
+        }
+        new_facade.media.clone_from(&self.media);
+        for child in self.children.values() {
+            new_facade.insert_child(child.migrate());
         }
         new_facade
     }
@@ -126,6 +967,52 @@ impl<'a, T: Task<'a>> TasksFacade<'a, T> for Facade<'a, T> {
             tasks_to_recall: Vec::default(),
             desired_retention,
             state: T::SharedState::default(),
+            format_version: FORMAT_VERSION,
+            parameter_groups: ParameterGroups::default(),
+            last_optimized: None,
+            min_reviews_for_optimize: DEFAULT_MIN_REVIEWS_FOR_OPTIMIZE,
+            optimization_excluded_tags: BTreeSet::new(),
+            min_interval: Duration::ZERO,
+            max_interval_growth: None,
+            latest_seen_time: None,
+            clock_skew: None,
+            clock: None,
+            deterministic_seed: None,
+            epoch: 0,
+            deleted: Vec::new(),
+            review_log: ReviewLog::default(),
+            trash: Vec::new(),
+            trash_retention: DEFAULT_TRASH_RETENTION,
+            auto_tag_policy: AutoTagPolicy::default(),
+            id_generator: IdGenerator::default(),
+            content_limit: None,
+            media: MediaStore::default(),
+            next_position: 0,
+            utc_offset_secs: 0,
+            rollover_hour: 0,
+            day_granularity: false,
+            reviewer: None,
+            stats_reviewer: None,
+            schedule_reviewer: None,
+            settings: Settings::default(),
+            templates: BTreeMap::new(),
+            day: DayState::default(),
+            review_ahead_cap: Some(DEFAULT_REVIEW_AHEAD_CAP),
+            learn_ahead: DEFAULT_LEARN_AHEAD,
+            tag_prerequisites: TagPrerequisites::new(),
+            prerequisite_maturity: DEFAULT_PREREQUISITE_MATURITY,
+            sibling_spacing: None,
+            tag_interval_multipliers: BTreeMap::new(),
+            new_per_day: None,
+            reviews_per_day: None,
+            note_links: BTreeMap::new(),
+            children: BTreeMap::new(),
+            unknown_fields: UnknownFields::new(),
+            events: Vec::new(),
+            perf: PerfRegistry::default(),
+            index: TaskIndex::default(),
+            recall_check: None,
+            idle: IdleTracker::default(),
         }
     }
 
@@ -133,13 +1020,22 @@ impl<'a, T: Task<'a>> TasksFacade<'a, T> for Facade<'a, T> {
         &self.name
     }
 
+    /// Including tasks of sub-decks.
     fn tasks_total(&self) -> usize {
-        self.tasks_pool.len() + self.tasks_to_recall.len()
+        self.tasks_pool.len()
+            + self.tasks_to_recall.len()
+            + self.children.values().map(Self::tasks_total).sum::<usize>()
     }
+    /// Including tasks of sub-decks.
     fn tasks_to_complete(&self) -> usize {
         self.tasks_to_recall.len()
+            + (self.children.values())
+                .map(Self::tasks_to_complete)
+                .sum::<usize>()
     }
 
+    /// Tasks of this facade come first, then those of sub-decks in name
+    /// order.
     fn complete_task(
         &mut self,
         interaction: &mut impl FnMut(
@@ -147,26 +1043,31 @@ impl<'a, T: Task<'a>> TasksFacade<'a, T> for Facade<'a, T> {
             s_text_input_f::Blocks,
         ) -> std::io::Result<s_text_input_f::Response>,
     ) -> Result<(), ssr_core::tasks_facade::Error> {
-        self.find_tasks_to_recall();
-        let Some(TaskWrapper { mut task, id }) = self.take_random_task() else {
-            return match self.until_next_repetition() {
-                Some(time_until_next_repetition) => {
-                    Err(ssr_core::tasks_facade::Error::NoTaskToComplete {
-                        time_until_next_repetition,
-                    })
+        let own = self.complete_task_as(ReviewSource::Normal, interaction);
+        if self.children.is_empty() {
+            return own;
+        }
+        let mut error = match own {
+            Err(
+                error @ (ssr_core::tasks_facade::Error::NoTaskToComplete { .. }
+                | ssr_core::tasks_facade::Error::NoTask),
+            ) => error,
+            own => return own,
+        };
+        for child in self.children.values_mut() {
+            error = match child.complete_task(interaction) {
+                Err(child_error @ ssr_core::tasks_facade::Error::NoTaskToComplete { .. }) => {
+                    children::sooner(error, child_error)
                 }
-                None => Err(ssr_core::tasks_facade::Error::NoTask),
+                Err(ssr_core::tasks_facade::Error::NoTask) => error,
+                result => return result,
             };
-        };
-        task.complete(&mut self.state, self.desired_retention, &mut |blocks| {
-            interaction(id, blocks)
-        })?;
-        self.tasks_pool.push(TaskWrapper { task, id });
-        Ok(())
+        }
+        Err(error)
     }
 
     fn insert(&mut self, task: T) {
-        self.tasks_pool.push(TaskWrapper::new(task));
+        self.push_new_task(task);
     }
 
     fn iter<'t>(&'t self) -> impl Iterator<Item = (&'t T, TaskId)>
@@ -176,28 +1077,15 @@ impl<'a, T: Task<'a>> TasksFacade<'a, T> for Facade<'a, T> {
         self.tasks_pool
             .iter()
             .chain(self.tasks_to_recall.iter())
-            .map(|TaskWrapper { task, id }| (task, *id))
+            .map(|TaskWrapper { task, id, .. }| (task, *id))
     }
 
+    /// Moves the task into the trash, see [`Facade::restore`].
     fn remove(&mut self, id: TaskId) -> bool {
-        let mut removed = false;
-        self.tasks_to_recall.retain(|task_wrapper| {
-            if task_wrapper.id == id {
-                removed = true;
-                false
-            } else {
-                true
-            }
-        });
-        if !removed {
-            self.tasks_pool.retain(|task_wrapper| {
-                if task_wrapper.id == id {
-                    removed = true;
-                    false
-                } else {
-                    true
-                }
-            });
+        let removed = self.trash_task(id);
+        if removed {
+            let epoch = self.next_epoch();
+            self.deleted.push((id, epoch));
         }
         removed
     }
@@ -212,20 +1100,54 @@ impl<'a, T: Task<'a>> TasksFacade<'a, T> for Facade<'a, T> {
         self.reload_all_tasks_timings();
     }
 
+    /// Oversized content is handled according to
+    /// [`Facade::set_content_limit`], rejection is reported with
+    /// [`Event::TaskRejected`].
     fn create_task(&mut self, input: s_text_input_f::BlocksWithAnswer) {
-        self.insert(T::new(input));
+        match self.limit_content(input) {
+            Ok(input) => self.insert(T::new(input)),
+            Err(reason) => self.events.push(Event::TaskRejected { reason }),
+        }
     }
 
     fn optimize(&mut self) -> Result<(), Box<dyn std::error::Error>>
     where
         T::SharedState: SharedStateExt<'a, T>,
     {
-        let items = self
+        self.check_enough_reviews()?;
+        let manually_scheduled = self.manually_scheduled_tasks();
+        let excluded_tags = &self.optimization_excluded_tags;
+        let used: Vec<_> = self
             .tasks_pool
             .iter()
             .chain(self.tasks_to_recall.iter())
-            .map(|x| &x.task);
-        self.state.optimize(items)?;
+            .filter(|t| {
+                t.used_for_optimization(excluded_tags) && !manually_scheduled.contains(&t.id)
+            })
+            .map(|t| (t, self.parameter_groups.group_of(t).map(str::to_owned)))
+            .collect();
+        // Shared states aren't `Clone`, so groups are fitted into fresh
+        // states and swapped in only once everything succeeded. The default
+        // state goes last: a failing `optimize` leaves it untouched.
+        let mut fitted = Vec::new();
+        for (name, _) in self.parameter_groups.states_mut() {
+            let mut state = T::SharedState::default();
+            state.optimize(
+                used.iter()
+                    .filter(|(_, group)| group.as_deref() == Some(name))
+                    .map(|(t, _)| &t.task),
+            )?;
+            fitted.push(state);
+        }
+        self.state.optimize(
+            used.iter()
+                .filter(|(_, group)| group.is_none())
+                .map(|(t, _)| &t.task),
+        )?;
+        for ((_, state), fitted) in self.parameter_groups.states_mut().zip(fitted) {
+            *state = fitted;
+        }
+        self.last_optimized = Some(self.now());
 
         self.reload_all_tasks_timings();
         Ok(())
@@ -0,0 +1,150 @@
+use std::{fmt, io};
+
+use ssr_core::{task::Task, tasks_facade::TaskId};
+
+use crate::{FORMAT_VERSION, Facade, Loaded, NewerFormatError, NewerFormatPolicy};
+
+/// What [`Facade::load`] fixed in the loaded data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Repair {
+    /// Rollover hour past 23, set to 23 as [`Facade::set_rollover_hour`] does.
+    RolloverHourClamped { found: u8 },
+    /// Sibling link of task `id` pointed to a task that no longer exists.
+    SiblingUnlinked { id: TaskId, sibling: TaskId },
+}
+
+/// How [`Facade::load_with_policy`] got from the file to the facade.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LoadDiagnostics {
+    /// Format version of a file written by an older version of the crate;
+    /// saving writes [`FORMAT_VERSION`].
+    pub upgraded_from: Option<u32>,
+    /// Set if the file was written by a newer version of the crate and
+    /// loaded anyway, as [`NewerFormatPolicy`] allowed.
+    pub newer_format: Option<NewerFormatError>,
+    /// Tasks that shared an id with another task and got a fresh one.
+    pub ids_regenerated: usize,
+    pub repairs: Vec<Repair>,
+    /// Top-level fields this version doesn't know, e.g. added by another
+    /// tool. They are kept and written back on save.
+    pub unknown_fields: Vec<String>,
+}
+
+#[derive(Debug)]
+pub enum LoadError {
+    /// Reading or deserializing failed.
+    Io(io::Error),
+    /// The file has fields this version would drop, see
+    /// [`Facade::load_with_policy`] to open it anyway.
+    NewerFormat(NewerFormatError),
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => err.fmt(f),
+            Self::NewerFormat(err) => err.fmt(f),
+        }
+    }
+}
+impl std::error::Error for LoadError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            Self::NewerFormat(err) => Some(err),
+        }
+    }
+}
+impl From<io::Error> for LoadError {
+    fn from(value: io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+/// [`LoadError::NewerFormat`] becomes [`io::ErrorKind::InvalidData`] wrapping
+/// [`NewerFormatError`].
+impl From<LoadError> for io::Error {
+    fn from(value: LoadError) -> Self {
+        match value {
+            LoadError::Io(err) => err,
+            LoadError::NewerFormat(err) => io::Error::new(io::ErrorKind::InvalidData, err),
+        }
+    }
+}
+
+impl<'a, T: Task<'a>> Facade<'a, T> {
+    /// Deserialize a facade from `reader` with `read`, then check and repair
+    /// it, reporting everything that was changed on the way.
+    ///
+    /// Unknown top-level fields are kept, but unknown fields inside tasks and
+    /// other nested data can't be, so files written by a newer version are
+    /// refused instead of losing them on the next save. Only self-describing
+    /// formats, like JSON, name the fields to keep.
+    /// # Errors
+    /// If `read` fails or the file is newer than [`FORMAT_VERSION`].
+    pub fn load(
+        reader: impl io::Read,
+        read: impl FnOnce(&mut dyn io::Read) -> io::Result<Self>,
+    ) -> Result<(Self, LoadDiagnostics), LoadError> {
+        match Self::load_with_policy(reader, read, NewerFormatPolicy::Fail)? {
+            (Loaded::Writable(facade), diagnostics) => Ok((facade, diagnostics)),
+            (Loaded::ReadOnly(_), _) => unreachable!("only newer files are loaded read-only"),
+        }
+    }
+
+    /// [`Facade::load`] that handles files written by newer versions of the
+    /// crate as `policy` says. Every other way of loading a facade goes
+    /// through here with [`NewerFormatPolicy::Fail`].
+    /// # Errors
+    /// If `read` fails, or the file is newer than [`FORMAT_VERSION`] and
+    /// `policy` is [`NewerFormatPolicy::Fail`].
+    pub fn load_with_policy(
+        mut reader: impl io::Read,
+        read: impl FnOnce(&mut dyn io::Read) -> io::Result<Self>,
+        policy: NewerFormatPolicy,
+    ) -> Result<(Loaded<'a, T>, LoadDiagnostics), LoadError> {
+        let mut facade = read(&mut reader)?;
+        let newer_format = (facade.format_version > FORMAT_VERSION).then_some(NewerFormatError {
+            found: facade.format_version,
+            supported: FORMAT_VERSION,
+        });
+        if let Some(error) = newer_format
+            && policy == NewerFormatPolicy::Fail
+        {
+            return Err(LoadError::NewerFormat(error));
+        }
+        let mut diagnostics = LoadDiagnostics {
+            upgraded_from: (facade.format_version < FORMAT_VERSION)
+                .then_some(facade.format_version),
+            newer_format,
+            ids_regenerated: facade.regenerate_duplicate_ids(),
+            repairs: Vec::new(),
+            unknown_fields: facade.unknown_fields.keys().cloned().collect(),
+        };
+        if facade.rollover_hour > 23 {
+            diagnostics.repairs.push(Repair::RolloverHourClamped {
+                found: facade.rollover_hour,
+            });
+            facade.set_rollover_hour(facade.rollover_hour);
+        }
+        let dangling = (facade.tasks_pool.iter())
+            .chain(facade.tasks_to_recall.iter())
+            .filter_map(|task| Some((task.id, task.sibling?)))
+            .filter(|&(_, sibling)| facade.find_task(sibling).is_none())
+            .collect::<Vec<_>>();
+        for (id, sibling) in dangling {
+            if let Some(task) = facade.find_task_mut(id) {
+                task.sibling = None;
+            }
+            diagnostics
+                .repairs
+                .push(Repair::SiblingUnlinked { id, sibling });
+        }
+        facade.reload_all_tasks_timings();
+        let loaded = if newer_format.is_some() && policy == NewerFormatPolicy::ReadOnly {
+            Loaded::ReadOnly(facade.into_read_only())
+        } else {
+            Loaded::Writable(facade)
+        };
+        Ok((loaded, diagnostics))
+    }
+}
@@ -0,0 +1,182 @@
+use std::{
+    collections::{HashMap, HashSet},
+    error::Error,
+    io::{self, Write},
+    path::PathBuf,
+    time::Duration,
+};
+
+use rand::Rng;
+use ssr_core::{
+    task::{SharedStateExt, Task},
+    tasks_facade::{TaskId, TasksFacade},
+};
+
+use crate::{DueOverride, Facade, PriorityRules, ReviewRecord};
+
+#[derive(Debug, Clone, Default)]
+pub struct MaintenancePolicy {
+    pub backup: Option<BackupPolicy>,
+    /// Re-fit shared state when the last optimization is older than this and
+    /// there is enough review history. `None` disables auto-optimization.
+    pub optimize_every: Option<Duration>,
+    pub priority_rules: Option<PriorityRules>,
+}
+
+#[derive(Debug, Clone)]
+pub struct BackupPolicy {
+    pub dir: PathBuf,
+    pub keep_n: usize,
+}
+
+/// What [`Facade::run_maintenance`] did.
+#[derive(Debug, Default)]
+pub struct MaintenanceReport {
+    /// Tasks that shared an id with another task and got a fresh one.
+    pub ids_regenerated: usize,
+    /// Removed tasks deleted permanently after trash retention expired.
+    pub trash_purged: usize,
+    /// Media entries no task refers to anymore, see [`Facade::prune_media`].
+    pub media_pruned: usize,
+    /// Tasks whose priority was changed by [`PriorityRules`].
+    pub priorities_adjusted: usize,
+    pub backup: Option<PathBuf>,
+    pub optimized: bool,
+}
+
+impl<'a, T: Task<'a>> Facade<'a, T> {
+    /// Housekeeping meant to be called on application startup or idle.
+    ///
+    /// Verifies id uniqueness, empties expired trash, drops unused media,
+    /// applies priority rules, rebuilds the due queue, releases unused memory,
+    /// writes a backup and re-optimizes shared state as configured by
+    /// `policy`. `write_backup`
+    /// serializes the facade, see [`Facade::backup`].
+    /// # Errors
+    /// If backup or optimization fails. Steps completed before the failure are
+    /// kept.
+    pub fn run_maintenance(
+        &mut self,
+        policy: &MaintenancePolicy,
+        write_backup: impl FnOnce(&Self, &mut dyn Write) -> io::Result<()>,
+    ) -> Result<MaintenanceReport, Box<dyn Error>>
+    where
+        T::SharedState: SharedStateExt<'a, T>,
+    {
+        let mut report = MaintenanceReport {
+            ids_regenerated: self.regenerate_duplicate_ids(),
+            trash_purged: self.purge_expired_trash(),
+            ..Default::default()
+        };
+        report.media_pruned = self.prune_media();
+        if let Some(rules) = &policy.priority_rules {
+            report.priorities_adjusted = self.apply_priority_rules(rules);
+        }
+
+        self.reload_all_tasks_timings();
+        self.tasks_pool.shrink_to_fit();
+        self.tasks_to_recall.shrink_to_fit();
+
+        if let Some(BackupPolicy { dir, keep_n }) = &policy.backup {
+            report.backup = Some(self.backup(dir, *keep_n, write_backup)?);
+        }
+
+        if let Some(optimize_every) = policy.optimize_every {
+            let due = self.last_optimized.is_none_or(|last| {
+                self.now()
+                    .duration_since(last)
+                    .is_ok_and(|elapsed| elapsed >= optimize_every)
+            });
+            if due && self.check_enough_reviews().is_ok() {
+                self.optimize()?;
+                report.optimized = true;
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Give every task but the first of those sharing an id a fresh one.
+    /// Returns the number of renamed tasks.
+    pub(crate) fn regenerate_duplicate_ids(&mut self) -> usize {
+        let mut rng = self.rng("id");
+        let epoch = self.next_epoch();
+        let mut seen = HashSet::new();
+        // Ids of every copy of a duplicated id, the kept one first.
+        let mut copies = HashMap::<TaskId, Vec<TaskId>>::new();
+        let mut regenerated = 0;
+        for wrapper in self
+            .tasks_pool
+            .iter_mut()
+            .chain(self.tasks_to_recall.iter_mut())
+        {
+            if seen.insert(wrapper.id) {
+                continue;
+            }
+            let id = loop {
+                let id = rng.r#gen();
+                if seen.insert(id) {
+                    break id;
+                }
+            };
+            (copies.entry(wrapper.id))
+                .or_insert_with(|| vec![wrapper.id])
+                .push(id);
+            wrapper.id = id;
+            wrapper.modified = epoch;
+            regenerated += 1;
+        }
+        self.relink_copies(&copies);
+        regenerated
+    }
+
+    /// Duplicates usually come from a collection stored twice, so the n-th
+    /// copy of a task is linked to the n-th copy of its sibling and
+    /// prerequisites where those were duplicated too.
+    fn relink_copies(&mut self, copies: &HashMap<TaskId, Vec<TaskId>>) {
+        let renamed = (copies.values())
+            .flat_map(|ids| ids.iter().enumerate().skip(1).map(|(i, &id)| (id, i)))
+            .collect::<HashMap<_, _>>();
+        let copy_of = |id: TaskId, index: usize| {
+            (copies.get(&id))
+                .and_then(|ids| ids.get(index))
+                .copied()
+                .unwrap_or(id)
+        };
+        for task in self
+            .tasks_pool
+            .iter_mut()
+            .chain(self.tasks_to_recall.iter_mut())
+        {
+            let Some(&index) = renamed.get(&task.id) else {
+                continue;
+            };
+            task.sibling = task.sibling.map(|id| copy_of(id, index));
+            task.prerequisites = (task.prerequisites.iter())
+                .map(|&id| copy_of(id, index))
+                .collect();
+        }
+
+        // The log can't tell the copies apart, so every copy keeps the
+        // history.
+        for (&kept, ids) in copies {
+            for &id in &ids[1..] {
+                let history = (self.review_log.for_task(kept))
+                    .map(|record| ReviewRecord {
+                        task_id: id,
+                        ..record.clone()
+                    })
+                    .collect::<Vec<_>>();
+                self.review_log.extend(history);
+                let overrides = (self.review_log.overrides().iter())
+                    .filter(|record| record.task_id == kept)
+                    .map(|record| DueOverride {
+                        task_id: id,
+                        ..record.clone()
+                    })
+                    .collect::<Vec<_>>();
+                self.review_log.extend_overrides(overrides);
+            }
+        }
+    }
+}
@@ -0,0 +1,149 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use s_text_input_f::{Block, Blocks, BlocksWithAnswer, ParagraphItem};
+use serde::{Deserialize, Serialize};
+use ssr_core::task::Task;
+
+use crate::{Facade, content::fnv1a_128, trash::TrashedTask};
+
+/// Paragraph text stored out of line is replaced by this prefix followed by
+/// the key of the text in [`MediaStore`].
+pub const MEDIA_REFERENCE_PREFIX: &str = "ssr-media:";
+/// Keys are 128-bit hashes of the text in hex.
+const KEY_LEN: usize = 32;
+pub(crate) const REFERENCE_LEN: usize = MEDIA_REFERENCE_PREFIX.len() + KEY_LEN;
+
+/// Paragraph text moved out of tasks by
+/// [`crate::OversizePolicy::StoreOutOfLine`], by key. Equal texts are stored
+/// once.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(transparent)]
+pub struct MediaStore(BTreeMap<String, String>);
+
+impl MediaStore {
+    #[must_use]
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).map(String::as_str)
+    }
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+    /// Blocks with references replaced by the text they refer to. References
+    /// to missing entries are left as they are.
+    #[must_use]
+    pub fn resolve(&self, mut blocks: Blocks) -> Blocks {
+        for text in paragraph_texts(&mut blocks) {
+            if let Some(stored) = referenced_key(text).and_then(|key| self.0.get(key)) {
+                text.clone_from(stored);
+            }
+        }
+        blocks
+    }
+
+    /// Reference to the stored `text`.
+    pub(crate) fn store(&mut self, text: String) -> String {
+        let mut hash = fnv1a_128(text.bytes());
+        loop {
+            let key = format!("{hash:032x}");
+            match self.0.get(&key) {
+                Some(stored) if *stored != text => hash = hash.wrapping_add(1),
+                Some(_) => return format!("{MEDIA_REFERENCE_PREFIX}{key}"),
+                None => {
+                    let reference = format!("{MEDIA_REFERENCE_PREFIX}{key}");
+                    self.0.insert(key, text);
+                    return reference;
+                }
+            }
+        }
+    }
+    /// Entries `content` refers to, to carry them along with the task.
+    pub(crate) fn referenced_by(&self, content: &BlocksWithAnswer) -> Self {
+        Self(
+            referenced_keys(content)
+                .filter_map(|key| self.0.get_key_value(key))
+                .map(|(key, text)| (key.clone(), text.clone()))
+                .collect(),
+        )
+    }
+    /// Add entries of `other` whose keys aren't taken.
+    pub(crate) fn extend(&mut self, other: Self) {
+        for (key, text) in other.0 {
+            self.0.entry(key).or_insert(text);
+        }
+    }
+    /// Drop entries none of `contents` refers to. Returns how many were
+    /// dropped.
+    pub(crate) fn retain_referenced(
+        &mut self,
+        contents: impl IntoIterator<Item = BlocksWithAnswer>,
+    ) -> usize {
+        let referenced = contents
+            .into_iter()
+            .flat_map(|content| {
+                referenced_keys(&content)
+                    .map(str::to_owned)
+                    .collect::<Vec<_>>()
+            })
+            .collect::<BTreeSet<_>>();
+        let before = self.0.len();
+        self.0.retain(|key, _| referenced.contains(key));
+        before - self.0.len()
+    }
+}
+
+pub(crate) fn paragraph_texts(blocks: &mut [Block]) -> impl Iterator<Item = &mut String> {
+    blocks
+        .iter_mut()
+        .flat_map(|block| match block {
+            Block::Paragraph(items) => items.as_mut_slice(),
+            _ => &mut [],
+        })
+        .filter_map(|item| match item {
+            ParagraphItem::Text(text) => Some(text),
+            ParagraphItem::Placeholder => None,
+        })
+}
+
+fn referenced_keys(content: &BlocksWithAnswer) -> impl Iterator<Item = &str> {
+    content
+        .blocks
+        .iter()
+        .flat_map(|block| match block {
+            Block::Paragraph(items) => items.as_slice(),
+            _ => &[],
+        })
+        .filter_map(|item| match item {
+            ParagraphItem::Text(text) => referenced_key(text),
+            ParagraphItem::Placeholder => None,
+        })
+}
+
+fn referenced_key(text: &str) -> Option<&str> {
+    text.strip_prefix(MEDIA_REFERENCE_PREFIX)
+        .filter(|key| key.len() == KEY_LEN)
+}
+
+impl<'a, T: Task<'a>> Facade<'a, T> {
+    /// Paragraph text stored out of line, see
+    /// [`crate::OversizePolicy::StoreOutOfLine`]. Blocks handed to review
+    /// interactions are already resolved; use [`MediaStore::resolve`] for
+    /// blocks obtained from tasks directly.
+    pub fn media(&self) -> &MediaStore {
+        &self.media
+    }
+    /// Drop stored text no task refers to, including trashed ones. Returns
+    /// how many entries were dropped.
+    pub fn prune_media(&mut self) -> usize {
+        let contents = (self.tasks_pool.iter())
+            .chain(self.tasks_to_recall.iter())
+            .chain(self.trash.iter().map(TrashedTask::task))
+            .map(|task| task.task.get_blocks())
+            .collect::<Vec<_>>();
+        self.media.retain_referenced(contents)
+    }
+}
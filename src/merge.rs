@@ -0,0 +1,167 @@
+use std::collections::{BTreeMap, HashMap};
+
+use ssr_core::{task::Task, tasks_facade::TaskId};
+
+use crate::{Event, Facade, citation::claim_citation_key, content::content_key};
+
+/// What [`Facade::merge`] does when both facades have a task with the same id.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MergePolicy {
+    /// Keep the task reviewed more recently, the existing one on a tie.
+    #[default]
+    KeepNewer,
+    KeepExisting,
+    KeepIncoming,
+    /// Keep both if their content differs, the incoming task gets a new id.
+    /// Tasks with the same content are resolved like [`MergePolicy::KeepNewer`].
+    KeepBoth,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MergeSummary {
+    /// Incoming tasks added under their own id.
+    pub added: Vec<TaskId>,
+    /// Existing tasks replaced by the incoming ones with the same id.
+    pub replaced: Vec<TaskId>,
+    /// Incoming ids that now refer to another task: new ids of renamed
+    /// tasks, and ids of existing tasks with the same content for skipped
+    /// duplicates. References of merged tasks to each other are rewritten
+    /// through it.
+    pub id_map: BTreeMap<TaskId, TaskId>,
+    /// Incoming tasks dropped because of a collision.
+    pub kept_existing: Vec<TaskId>,
+    /// Incoming tasks dropped because an existing task has the same content.
+    pub duplicates: usize,
+}
+
+enum Resolution {
+    Add,
+    Replace,
+    Rename,
+    Drop,
+}
+
+impl<'a, T: Task<'a>> Facade<'a, T> {
+    /// Add tasks of `other` with their review history, e.g. a deck exported
+    /// from another machine. Incoming tasks whose content already exists
+    /// under another id are skipped; id collisions are resolved by `policy`.
+    /// Scheduling state and settings of `other` are ignored.
+    ///
+    /// Replaced tasks keep their citation key; merged tasks whose key is
+    /// taken by another task get a new one.
+    pub fn merge(&mut self, other: Facade<'a, T>, policy: MergePolicy) -> MergeSummary {
+        let Facade {
+            tasks_pool,
+            tasks_to_recall,
+            mut review_log,
+            media,
+            ..
+        } = other;
+        self.media.extend(media);
+        let mut known_content = self
+            .tasks_pool
+            .iter()
+            .chain(self.tasks_to_recall.iter())
+            .map(|task| (content_key(&task.task.get_blocks()), task.id))
+            .collect::<HashMap<_, _>>();
+        let mut citation_keys = self.citation_keys();
+        let mut rng = self.rng("citation");
+        let mut summary = MergeSummary::default();
+        let mut merged = Vec::new();
+        let mut merged_history = Vec::new();
+        for mut task in tasks_pool.into_iter().chain(tasks_to_recall) {
+            let id = task.id;
+            let mut history = review_log.remove_task(id);
+            let content = content_key(&task.task.get_blocks());
+            let resolution = match self.find_task(id) {
+                None if known_content.contains_key(&content) => Resolution::Drop,
+                None => Resolution::Add,
+                Some(existing) => {
+                    let same_content = content_key(&existing.task.get_blocks()) == content;
+                    let incoming_newer =
+                        history.last().map(|r| r.reviewed_at) > self.review_log.last_review(id);
+                    match policy {
+                        MergePolicy::KeepExisting => Resolution::Drop,
+                        MergePolicy::KeepIncoming => Resolution::Replace,
+                        MergePolicy::KeepBoth if !same_content => Resolution::Rename,
+                        MergePolicy::KeepNewer | MergePolicy::KeepBoth => {
+                            if incoming_newer {
+                                Resolution::Replace
+                            } else {
+                                Resolution::Drop
+                            }
+                        }
+                    }
+                }
+            };
+            match resolution {
+                Resolution::Drop if self.find_task(id).is_some() => summary.kept_existing.push(id),
+                Resolution::Drop => {
+                    summary.id_map.insert(id, known_content[&content]);
+                    summary.duplicates += 1;
+                }
+                Resolution::Add => summary.added.push(id),
+                Resolution::Replace => {
+                    if let Some(existing) = self.take_task(id) {
+                        task.citation_key = existing.citation_key;
+                    }
+                    self.review_log.remove_task(id);
+                    summary.replaced.push(id);
+                }
+                Resolution::Rename => {
+                    let new_id = self.new_task_id(&task.task);
+                    task.id = new_id;
+                    for record in &mut history {
+                        record.task_id = new_id;
+                    }
+                    summary.id_map.insert(id, new_id);
+                }
+            }
+            if matches!(resolution, Resolution::Drop) {
+                continue;
+            }
+            if !matches!(resolution, Resolution::Replace) {
+                claim_citation_key(&mut citation_keys, &mut task.citation_key, &mut rng);
+            }
+            known_content.insert(content, task.id);
+            task.position = self.take_next_position();
+            merged.push(task.id);
+            self.place_task(task);
+            merged_history.extend(history);
+        }
+        if !summary.id_map.is_empty() {
+            for &id in &merged {
+                let Some(task) = self.find_task_mut(id) else {
+                    continue;
+                };
+                if let Some(sibling) = &mut task.sibling {
+                    *sibling = summary.id_map.get(sibling).copied().unwrap_or(*sibling);
+                }
+                task.prerequisites = task
+                    .prerequisites
+                    .iter()
+                    .map(|id| summary.id_map.get(id).copied().unwrap_or(*id))
+                    .collect();
+            }
+        }
+        self.review_log.extend(merged_history);
+        self.reload_all_tasks_timings();
+        self.events.push(Event::TasksImported { ids: merged });
+        summary
+    }
+}
+
+impl<'a, T> Facade<'a, T>
+where
+    T: Task<'a> + Clone,
+    T::SharedState: Clone,
+{
+    /// What [`Facade::merge`] would do, computed by merging copies of both
+    /// facades, e.g. for a confirmation dialog. Ids of renamed tasks in
+    /// [`MergeSummary::id_map`] are only examples: the real merge picks new
+    /// ones.
+    #[must_use]
+    pub fn merge_preview(&self, other: &Facade<'a, T>, policy: MergePolicy) -> MergeSummary {
+        self.clone().merge(other.clone(), policy)
+    }
+}
@@ -0,0 +1,117 @@
+use ssr_core::task::Task;
+
+use crate::{Facade, IdleTracker, TaskIndex, TaskWrapper};
+
+impl<T> TaskWrapper<T> {
+    /// Same task metadata around a converted task.
+    pub(crate) fn map_task<U>(&self, mut convert: impl FnMut(&T) -> U) -> TaskWrapper<U> {
+        TaskWrapper {
+            task: convert(&self.task),
+            reviewer_tasks: (self.reviewer_tasks.iter())
+                .map(|(reviewer, task)| (reviewer.clone(), convert(task)))
+                .collect(),
+            id: self.id,
+            tags: self.tags.clone(),
+            suspended: self.suspended,
+            buried_until: self.buried_until,
+            frozen_until: self.frozen_until,
+            sort_due: self.sort_due,
+            priority: self.priority,
+            priority_set_at: self.priority_set_at,
+            priority_boost: self.priority_boost,
+            due_override: self.due_override,
+            manual_due: self.manual_due,
+            min_interval: self.min_interval,
+            excluded_from_optimization: self.excluded_from_optimization,
+            position: self.position,
+            prerequisites: self.prerequisites.clone(),
+            sibling: self.sibling,
+            parameter_group: self.parameter_group.clone(),
+            derivation: self.derivation.clone(),
+            interval_multiplier: self.interval_multiplier,
+            content_changed_at: self.content_changed_at,
+            state_reset: self.state_reset,
+            citation_key: self.citation_key.clone(),
+            modified: self.modified,
+        }
+    }
+}
+
+impl<'a, F: Task<'a>> Facade<'a, F> {
+    /// [`Facade::migrate`] that keeps progress: `convert` maps every task,
+    /// including trashed ones, to the new type with its scheduling state.
+    /// Ids, metadata, review history and settings are kept.
+    ///
+    /// Shared states of the new task type start from their defaults;
+    /// parameter groups keep their names and tag assignments. Sub-decks are
+    /// migrated too.
+    pub fn migrate_with<T: Task<'a>>(&self, mut convert: impl FnMut(&F) -> T) -> Facade<'a, T> {
+        self.migrate_with_dyn(&mut convert)
+    }
+    /// Behind `dyn` so sub-decks don't instantiate it again per level.
+    fn migrate_with_dyn<T: Task<'a>>(&self, convert: &mut dyn FnMut(&F) -> T) -> Facade<'a, T> {
+        let mut facade = Facade {
+            name: self.name.clone(),
+            tasks_pool: (self.tasks_pool.iter())
+                .map(|task| task.map_task(&mut *convert))
+                .collect(),
+            tasks_to_recall: (self.tasks_to_recall.iter())
+                .map(|task| task.map_task(&mut *convert))
+                .collect(),
+            desired_retention: self.desired_retention,
+            state: T::SharedState::default(),
+            format_version: self.format_version,
+            parameter_groups: self.parameter_groups.with_default_states(),
+            last_optimized: None,
+            min_reviews_for_optimize: self.min_reviews_for_optimize,
+            optimization_excluded_tags: self.optimization_excluded_tags.clone(),
+            min_interval: self.min_interval,
+            max_interval_growth: self.max_interval_growth,
+            latest_seen_time: self.latest_seen_time,
+            clock_skew: self.clock_skew,
+            clock: self.clock,
+            deterministic_seed: self.deterministic_seed,
+            epoch: self.epoch,
+            deleted: self.deleted.clone(),
+            review_log: self.review_log.clone(),
+            trash: (self.trash.iter())
+                .map(|task| task.map_task(&mut *convert))
+                .collect(),
+            trash_retention: self.trash_retention,
+            auto_tag_policy: self.auto_tag_policy.clone(),
+            id_generator: self.id_generator,
+            content_limit: self.content_limit,
+            media: self.media.clone(),
+            next_position: self.next_position,
+            utc_offset_secs: self.utc_offset_secs,
+            rollover_hour: self.rollover_hour,
+            day_granularity: self.day_granularity,
+            reviewer: self.reviewer.clone(),
+            stats_reviewer: self.stats_reviewer.clone(),
+            schedule_reviewer: self.schedule_reviewer.clone(),
+            settings: self.settings.clone(),
+            templates: self.templates.clone(),
+            day: self.day,
+            review_ahead_cap: self.review_ahead_cap,
+            learn_ahead: self.learn_ahead,
+            tag_prerequisites: self.tag_prerequisites.clone(),
+            prerequisite_maturity: self.prerequisite_maturity,
+            sibling_spacing: self.sibling_spacing,
+            tag_interval_multipliers: self.tag_interval_multipliers.clone(),
+            new_per_day: self.new_per_day,
+            reviews_per_day: self.reviews_per_day,
+            note_links: self.note_links.clone(),
+            children: (self.children.iter())
+                .map(|(name, child)| (name.clone(), child.migrate_with_dyn(&mut *convert)))
+                .collect(),
+            unknown_fields: self.unknown_fields.clone(),
+            events: Vec::new(),
+            perf: self.perf.clone(),
+            index: TaskIndex::default(),
+            recall_check: None,
+            idle: IdleTracker::default(),
+        };
+        facade.reload_all_tasks_timings();
+        facade
+    }
+}
@@ -0,0 +1,60 @@
+use ssr_core::{task::Task, tasks_facade::TaskId};
+
+use crate::{Facade, TaskWrapper};
+
+fn is_valid(multiplier: f64) -> bool {
+    multiplier.is_finite() && multiplier > 0.
+}
+
+impl<'a, T: Task<'a>> Facade<'a, T> {
+    /// Scale intervals the scheduler assigns to the task, e.g. `0.7` for
+    /// fragile material. Overrides tag multipliers, `None` resets it.
+    /// Returns whether the task exists and the multiplier is positive.
+    ///
+    /// Every scaled review is recorded in [`crate::ReviewLog::overrides`].
+    pub fn set_interval_multiplier(&mut self, id: TaskId, multiplier: Option<f64>) -> bool {
+        if !multiplier.is_none_or(is_valid) {
+            return false;
+        }
+        let Some(task) = self.find_task_mut(id) else {
+            return false;
+        };
+        task.interval_multiplier = multiplier;
+        true
+    }
+    /// Scale intervals of tasks tagged `tag` without their own multiplier.
+    /// Multipliers of several tags are multiplied. Returns whether the
+    /// multiplier is positive.
+    pub fn set_tag_interval_multiplier(
+        &mut self,
+        tag: impl Into<String>,
+        multiplier: Option<f64>,
+    ) -> bool {
+        let tag = tag.into();
+        match multiplier {
+            Some(multiplier) if is_valid(multiplier) => {
+                self.tag_interval_multipliers.insert(tag, multiplier);
+            }
+            Some(_) => return false,
+            None => {
+                self.tag_interval_multipliers.remove(&tag);
+            }
+        }
+        true
+    }
+    /// Multiplier applied to the task's intervals, `None` if they are kept
+    /// as is.
+    pub fn interval_multiplier(&self, id: TaskId) -> Option<f64> {
+        self.find_task(id).and_then(|task| self.multiplier_of(task))
+    }
+
+    pub(crate) fn multiplier_of(&self, task: &TaskWrapper<T>) -> Option<f64> {
+        task.interval_multiplier.or_else(|| {
+            task.tags
+                .iter()
+                .filter_map(|tag| self.tag_interval_multipliers.get(tag))
+                .copied()
+                .reduce(|a, b| a * b)
+        })
+    }
+}
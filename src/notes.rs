@@ -0,0 +1,238 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use s_text_input_f::{Block, BlocksWithAnswer, ParagraphItem};
+use serde::{Deserialize, Serialize};
+use ssr_core::{
+    task::Task,
+    tasks_facade::{TaskId, TasksFacade},
+};
+
+use crate::{
+    Event, Facade, ImportError, ImportErrorKind,
+    content::{content_key, fnv1a},
+    import::validate,
+};
+
+/// Starts the question of a card in a note. The question continues on the
+/// following lines up to the answer line.
+pub const NOTE_QUESTION_MARKER: &str = "Q:";
+/// Starts the one-line answer of a card in a note.
+pub const NOTE_ANSWER_MARKER: &str = "A:";
+/// Markdown (Obsidian) and org-mode notes.
+const NOTE_EXTENSIONS: [&str; 2] = ["md", "org"];
+
+/// Task mirroring a note card, see [`Facade::sync_notes`].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub(crate) struct NoteLink {
+    citation_key: String,
+    /// Content of the card at the last sync.
+    content: u64,
+}
+
+/// Question and answer found in a note.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NoteCard {
+    /// 1-based line of the question marker.
+    pub line: usize,
+    pub question: String,
+    pub answer: String,
+}
+
+/// What [`Facade::sync_notes`] did.
+#[derive(Debug, Default)]
+pub struct NoteSyncReport {
+    pub created: Vec<TaskId>,
+    /// Tasks whose answer changed in the notes.
+    pub updated: Vec<TaskId>,
+    /// Tasks whose card disappeared from the notes, moved to trash.
+    pub removed: Vec<TaskId>,
+    pub unchanged: usize,
+    /// Cards that can't become tasks, by note path relative to the synced
+    /// directory.
+    pub rejected: Vec<(PathBuf, ImportError)>,
+}
+
+/// Cards marked with [`NOTE_QUESTION_MARKER`] and [`NOTE_ANSWER_MARKER`]. A
+/// blank line or another question before the answer drops the card.
+#[must_use]
+pub fn parse_note(text: &str) -> Vec<NoteCard> {
+    let mut cards = Vec::new();
+    let mut question = None::<(usize, Vec<&str>)>;
+    for (i, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if let Some(rest) = line.strip_prefix(NOTE_QUESTION_MARKER) {
+            question = Some((i + 1, vec![rest.trim()]));
+        } else if let Some(rest) = line.strip_prefix(NOTE_ANSWER_MARKER) {
+            if let Some((line, lines)) = question.take() {
+                cards.push(NoteCard {
+                    line,
+                    question: lines.join("\n").trim().to_owned(),
+                    answer: rest.trim().to_owned(),
+                });
+            }
+        } else if line.is_empty() {
+            question = None;
+        } else if let Some((_, lines)) = &mut question {
+            lines.push(line);
+        }
+    }
+    cards
+}
+
+impl NoteCard {
+    #[must_use]
+    pub fn content(&self) -> BlocksWithAnswer {
+        BlocksWithAnswer {
+            blocks: vec![Block::Paragraph(vec![
+                ParagraphItem::Text(self.question.clone()),
+                ParagraphItem::Placeholder,
+            ])],
+            answer: vec![vec![self.answer.clone()]],
+        }
+    }
+    /// Identifies the card across syncs: note path and question. Editing the
+    /// answer keeps the task, editing the question replaces it.
+    fn key(&self, path: &Path) -> String {
+        let path = path
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        format!("{path}#{:016x}", fnv1a(self.question.bytes()))
+    }
+}
+
+/// Note files under `dir`, relative to it, skipping hidden entries like
+/// `.obsidian` and `.git`.
+fn note_files(dir: &Path, relative: &Path, files: &mut Vec<PathBuf>) -> io::Result<()> {
+    for entry in fs::read_dir(dir.join(relative))? {
+        let entry = entry?;
+        let name = entry.file_name();
+        if name.to_string_lossy().starts_with('.') {
+            continue;
+        }
+        let path = relative.join(&name);
+        if entry.file_type()?.is_dir() {
+            note_files(dir, &path, files)?;
+        } else if path
+            .extension()
+            .is_some_and(|x| NOTE_EXTENSIONS.iter().any(|ext| x == *ext))
+        {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+impl<'a, T: Task<'a>> Facade<'a, T> {
+    /// Mirror cards of the Markdown and org-mode notes under `dir`, see
+    /// [`parse_note`]. Notes stay the source of truth: cards new to the facade
+    /// become tasks, tasks whose answer changed are edited like with
+    /// [`Facade::edit_task`], and tasks whose card is gone are removed.
+    /// Tasks are tracked through their citation key, so they survive id
+    /// changes; tasks not created by a sync are never touched.
+    /// # Errors
+    /// If `dir` or a note can't be read; nothing is changed then.
+    pub fn sync_notes(&mut self, dir: impl AsRef<Path>) -> io::Result<NoteSyncReport> {
+        let dir = dir.as_ref();
+        let mut files = Vec::new();
+        note_files(dir, Path::new(""), &mut files)?;
+        files.sort();
+        let notes = files
+            .into_iter()
+            .map(|path| Ok((parse_note(&fs::read_to_string(dir.join(&path))?), path)))
+            .collect::<io::Result<Vec<_>>>()?;
+
+        // Tasks by citation key, the first one of duplicates like
+        // `find_by_citation_key` finds.
+        let mut ids = HashMap::new();
+        for task in self.tasks_pool.iter().chain(self.tasks_to_recall.iter()) {
+            ids.entry(task.citation_key.clone()).or_insert(task.id);
+        }
+        let mut report = NoteSyncReport::default();
+        let mut seen = BTreeSet::new();
+        for (cards, path) in notes {
+            for card in cards {
+                let key = card.key(&path);
+                if seen.insert(key.clone()) {
+                    self.sync_card(&path, &card, key, &ids, &mut report);
+                }
+            }
+        }
+        let gone = self
+            .note_links
+            .extract_if(.., |key, _| !seen.contains(key))
+            .collect::<Vec<_>>();
+        for (_, link) in gone {
+            if let Some(&id) = ids.get(&link.citation_key)
+                && self.remove(id)
+            {
+                report.removed.push(id);
+            }
+        }
+        self.reload_all_tasks_timings();
+        if !report.created.is_empty() {
+            self.events.push(Event::TasksImported {
+                ids: report.created.clone(),
+            });
+        }
+        Ok(report)
+    }
+
+    fn sync_card(
+        &mut self,
+        path: &Path,
+        card: &NoteCard,
+        key: String,
+        ids: &HashMap<String, TaskId>,
+        report: &mut NoteSyncReport,
+    ) {
+        let content =
+            match validate(&card.content()).and_then(|()| self.limit_content(card.content())) {
+                Ok(content) => content,
+                Err(reason) => {
+                    let error = ImportError {
+                        index: report.rejected.len(),
+                        line: Some(card.line),
+                        raw: Some(format!("{NOTE_QUESTION_MARKER} {}", card.question)),
+                        kind: ImportErrorKind::Rejected(reason),
+                    };
+                    report.rejected.push((path.to_owned(), error));
+                    return;
+                }
+            };
+        let hash = content_key(&content);
+        let linked = self.note_links.get(&key).and_then(|link| {
+            let id = *ids.get(&link.citation_key)?;
+            Some((id, link.content == hash))
+        });
+        match linked {
+            Some((_, true)) => report.unchanged += 1,
+            Some((id, false)) => {
+                self.replace_content(id, content);
+                report.updated.push(id);
+            }
+            None => {
+                let wrapper = self.push_new_task(T::new(content));
+                let id = wrapper.id;
+                let citation_key = wrapper.citation_key.clone();
+                report.created.push(id);
+                self.note_links.insert(
+                    key,
+                    NoteLink {
+                        citation_key,
+                        content: hash,
+                    },
+                );
+                return;
+            }
+        }
+        if let Some(link) = self.note_links.get_mut(&key) {
+            link.content = hash;
+        }
+    }
+}
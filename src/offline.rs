@@ -0,0 +1,102 @@
+use std::{fmt, io, time::SystemTime};
+
+use ssr_core::{task::Task, tasks_facade::TaskId};
+
+use crate::{Facade, ReviewSource};
+
+/// Answer collected while disconnected, see [`Facade::apply_review_results`].
+#[derive(Debug, Clone)]
+pub enum OfflineAnswer {
+    Response(s_text_input_f::Response),
+    /// Answered with the correct answer of the task.
+    Pass,
+    /// Answered with empty strings in place of the answer.
+    Fail,
+}
+
+#[derive(Debug)]
+pub enum ReviewResultError {
+    NoTask,
+    /// The task was reviewed at `last_review`, after the result was
+    /// collected.
+    Stale {
+        last_review: SystemTime,
+    },
+    Interaction(io::Error),
+}
+
+impl fmt::Display for ReviewResultError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoTask => write!(f, "task doesn't exist"),
+            Self::Stale { .. } => write!(f, "task was reviewed after the result"),
+            Self::Interaction(err) => write!(f, "task rejected the response: {err}"),
+        }
+    }
+}
+impl std::error::Error for ReviewResultError {}
+
+impl<'a, T: Task<'a>> Facade<'a, T> {
+    /// Complete tasks with answers collected while disconnected. Results are
+    /// applied in order of their timestamps and logged at those times (but
+    /// not later than now). A result older than the latest review of its task
+    /// is rejected, since history can't be rewritten.
+    ///
+    /// Task types schedule from the time they are completed, so the next due
+    /// time they give is moved back by how long ago the answer was collected.
+    /// Results answered on an earlier day don't count toward today's limits.
+    ///
+    /// Returns outcome of every result, in input order.
+    pub fn apply_review_results(
+        &mut self,
+        results: impl IntoIterator<Item = (TaskId, OfflineAnswer, SystemTime)>,
+    ) -> Vec<Result<(), ReviewResultError>> {
+        let mut results = results.into_iter().enumerate().collect::<Vec<_>>();
+        results.sort_by_key(|(_, (_, _, answered_at))| *answered_at);
+        let mut outcomes = results
+            .into_iter()
+            .map(|(index, (id, answer, answered_at))| {
+                (index, self.apply_review_result(id, answer, answered_at))
+            })
+            .collect::<Vec<_>>();
+        outcomes.sort_by_key(|(index, _)| *index);
+        self.find_tasks_to_recall();
+        outcomes.into_iter().map(|(_, outcome)| outcome).collect()
+    }
+
+    fn apply_review_result(
+        &mut self,
+        id: TaskId,
+        answer: OfflineAnswer,
+        answered_at: SystemTime,
+    ) -> Result<(), ReviewResultError> {
+        let task = self.find_task(id).ok_or(ReviewResultError::NoTask)?;
+        if let Some(last_review) = self.review_log.last_review(id)
+            && last_review > answered_at
+        {
+            return Err(ReviewResultError::Stale { last_review });
+        }
+        let response = match answer {
+            OfflineAnswer::Response(response) => response,
+            OfflineAnswer::Pass => task.task.get_blocks().answer,
+            OfflineAnswer::Fail => task
+                .task
+                .get_blocks()
+                .answer
+                .iter()
+                .map(|item| vec![String::new(); item.len()])
+                .collect(),
+        };
+        let wrapper = self.take_task(id).ok_or(ReviewResultError::NoTask)?;
+        self.complete_taken_task_at(
+            wrapper,
+            ReviewSource::Normal,
+            Some(answered_at),
+            &mut |_, _| Ok(response.clone()),
+        )
+        .map_err(|err| match err {
+            ssr_core::tasks_facade::Error::IO(err) => ReviewResultError::Interaction(err),
+            _ => ReviewResultError::NoTask,
+        })
+    }
+}
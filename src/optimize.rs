@@ -0,0 +1,112 @@
+use std::{
+    collections::{BTreeSet, HashSet},
+    fmt,
+};
+
+use ssr_core::{task::Task, tasks_facade::TaskId};
+
+use crate::{Facade, Outcome, TaskWrapper};
+
+/// Default for [`Facade::min_reviews_for_optimize`].
+pub const DEFAULT_MIN_REVIEWS_FOR_OPTIMIZE: usize = 400;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OptimizeError {
+    /// Review history is too small to fit meaningful parameters.
+    NotEnoughReviews { reviews: usize, required: usize },
+}
+
+impl fmt::Display for OptimizeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotEnoughReviews { reviews, required } => write!(
+                f,
+                "not enough reviews to optimize: {reviews} of {required}, {} more needed",
+                required - reviews
+            ),
+        }
+    }
+}
+impl std::error::Error for OptimizeError {}
+
+pub(crate) fn default_min_reviews_for_optimize() -> usize {
+    DEFAULT_MIN_REVIEWS_FOR_OPTIMIZE
+}
+
+impl<T> TaskWrapper<T> {
+    /// Whether `optimize` fits parameters to this task.
+    pub(crate) fn used_for_optimization(&self, excluded_tags: &BTreeSet<String>) -> bool {
+        !self.excluded_from_optimization && self.tags.is_disjoint(excluded_tags)
+    }
+}
+
+impl<'a, T: Task<'a>> Facade<'a, T> {
+    /// Number of logged reviews (excluding first reviews of tasks) `optimize`
+    /// requires.
+    pub fn min_reviews_for_optimize(&self) -> usize {
+        self.min_reviews_for_optimize
+    }
+    pub fn set_min_reviews_for_optimize(&mut self, min_reviews: usize) {
+        self.min_reviews_for_optimize = min_reviews;
+    }
+
+    /// Keep task out of the data `optimize` fits parameters to, e.g. because
+    /// it was answered by looking things up. Returns whether the task exists.
+    pub fn set_excluded_from_optimization(&mut self, id: TaskId, excluded: bool) -> bool {
+        let Some(task) = self.find_task_mut(id) else {
+            return false;
+        };
+        task.excluded_from_optimization = excluded;
+        true
+    }
+    /// Tasks with any of these tags are not used by `optimize`.
+    pub fn optimization_excluded_tags(&self) -> &BTreeSet<String> {
+        &self.optimization_excluded_tags
+    }
+    pub fn exclude_tag_from_optimization(&mut self, tag: impl Into<String>) {
+        self.optimization_excluded_tags.insert(tag.into());
+    }
+    pub fn include_tag_in_optimization(&mut self, tag: &str) {
+        self.optimization_excluded_tags.remove(tag);
+    }
+
+    /// Tasks reviewed on a manual schedule at least once, which `optimize`
+    /// skips.
+    pub(crate) fn manually_scheduled_tasks(&self) -> HashSet<TaskId> {
+        self.review_log
+            .iter()
+            .filter(|r| r.manually_scheduled)
+            .map(|r| r.task_id)
+            .collect()
+    }
+    /// # Errors
+    /// [`OptimizeError::NotEnoughReviews`] if optimization would overfit.
+    pub(crate) fn check_enough_reviews(&self) -> Result<(), OptimizeError> {
+        let manually_scheduled = self.manually_scheduled_tasks();
+        let included: HashSet<_> = self
+            .tasks_pool
+            .iter()
+            .chain(self.tasks_to_recall.iter())
+            .filter(|t| {
+                t.used_for_optimization(&self.optimization_excluded_tags)
+                    && !manually_scheduled.contains(&t.id)
+            })
+            .map(|t| t.id)
+            .collect();
+        let reviews = self
+            .review_log
+            .iter()
+            .filter(|r| {
+                r.outcome != Outcome::New
+                    && r.source.is_scheduled()
+                    && included.contains(&r.task_id)
+            })
+            .count();
+        let required = self.min_reviews_for_optimize;
+        if reviews < required {
+            Err(OptimizeError::NotEnoughReviews { reviews, required })
+        } else {
+            Ok(())
+        }
+    }
+}
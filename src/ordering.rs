@@ -0,0 +1,31 @@
+use std::time::SystemTime;
+
+use ssr_core::{task::Task, tasks_facade::TaskId};
+
+use crate::{Facade, TaskWrapper};
+
+impl<'a, T: Task<'a>> Facade<'a, T> {
+    /// Tasks with ids ordered by due time at their last scheduling, ties
+    /// broken by id. The order is stored with the facade, so it doesn't
+    /// depend on when it's requested and survives saving and loading.
+    pub fn iter_by_due(&self) -> impl Iterator<Item = (&T, TaskId)> {
+        let mut tasks = self
+            .tasks_pool
+            .iter()
+            .chain(self.tasks_to_recall.iter())
+            .map(|task| (self.sort_key(task), task))
+            .collect::<Vec<_>>();
+        tasks.sort_unstable_by_key(|(key, _)| *key);
+        tasks.into_iter().map(|(_, task)| (&task.task, task.id))
+    }
+
+    /// Tasks scheduled before the key was stored are ordered by their current
+    /// due time.
+    pub(crate) fn sort_key(&self, task: &TaskWrapper<T>) -> (SystemTime, TaskId) {
+        let due = task.sort_due.unwrap_or_else(|| self.next_repetition(task));
+        (due, task.id)
+    }
+    pub(crate) fn store_sort_key(&self, task: &mut TaskWrapper<T>) {
+        task.sort_due = Some(self.next_repetition(task));
+    }
+}
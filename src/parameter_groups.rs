@@ -0,0 +1,118 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use ssr_core::{task::Task, tasks_facade::TaskId};
+
+use crate::{Facade, TaskWrapper};
+
+/// Named shared states that tasks are scheduled and optimized with instead of
+/// the facade's default one, so structurally different material gets its own
+/// parameters.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub(crate) struct ParameterGroups<S> {
+    states: BTreeMap<String, S>,
+    /// Tag -> group for tasks without explicitly assigned group.
+    tags: BTreeMap<String, String>,
+}
+
+impl<S> ParameterGroups<S> {
+    /// Same groups and tag assignments with default states of another type.
+    pub(crate) fn with_default_states<U: Default>(&self) -> ParameterGroups<U> {
+        ParameterGroups {
+            states: self
+                .states
+                .keys()
+                .map(|name| (name.clone(), U::default()))
+                .collect(),
+            tags: self.tags.clone(),
+        }
+    }
+    /// Group the task is scheduled with, `None` for the default one.
+    pub(crate) fn group_of<'s, T>(&'s self, task: &'s TaskWrapper<T>) -> Option<&'s str> {
+        let group = match &task.parameter_group {
+            Some(group) => group,
+            None => task.tags.iter().find_map(|tag| self.tags.get(tag))?,
+        };
+        self.states.contains_key(group).then_some(group.as_str())
+    }
+    /// Shared state the task is scheduled with.
+    pub(crate) fn state_for<'s, T>(&'s self, default: &'s S, task: &TaskWrapper<T>) -> &'s S {
+        self.group_of(task)
+            .and_then(|x| self.states.get(x))
+            .unwrap_or(default)
+    }
+    pub(crate) fn state_mut<'s>(
+        &'s mut self,
+        default: &'s mut S,
+        group: Option<&str>,
+    ) -> &'s mut S {
+        group
+            .and_then(|x| self.states.get_mut(x))
+            .unwrap_or(default)
+    }
+    pub(crate) fn states_mut(&mut self) -> impl Iterator<Item = (&str, &mut S)> {
+        self.states
+            .iter_mut()
+            .map(|(name, state)| (name.as_str(), state))
+    }
+}
+
+impl<'a, T: Task<'a>> Facade<'a, T> {
+    /// Create group with default shared state. Returns whether it didn't
+    /// exist. `optimize` fits group states starting from a default state, so
+    /// a failure in one group leaves all of them unchanged.
+    pub fn add_parameter_group(&mut self, name: impl Into<String>) -> bool {
+        let name = name.into();
+        if self.parameter_groups.states.contains_key(&name) {
+            return false;
+        }
+        self.parameter_groups
+            .states
+            .insert(name, T::SharedState::default());
+        true
+    }
+    /// Tasks of removed group fall back to the default shared state.
+    pub fn remove_parameter_group(&mut self, name: &str) -> Option<T::SharedState> {
+        let state = self.parameter_groups.states.remove(name)?;
+        self.parameter_groups.tags.retain(|_, group| group != name);
+        self.reload_all_tasks_timings();
+        Some(state)
+    }
+    pub fn parameter_groups(&self) -> impl Iterator<Item = &str> {
+        self.parameter_groups.states.keys().map(String::as_str)
+    }
+    pub fn parameter_group_state(&self, name: &str) -> Option<&T::SharedState> {
+        self.parameter_groups.states.get(name)
+    }
+
+    /// Schedule tasks tagged `tag` with `group`, unless they have a group
+    /// assigned explicitly. If a task has several such tags, the
+    /// alphabetically first one wins.
+    pub fn assign_tag_to_parameter_group(
+        &mut self,
+        tag: impl Into<String>,
+        group: impl Into<String>,
+    ) {
+        self.parameter_groups.tags.insert(tag.into(), group.into());
+        self.reload_all_tasks_timings();
+    }
+    pub fn unassign_tag_from_parameter_group(&mut self, tag: &str) {
+        self.parameter_groups.tags.remove(tag);
+        self.reload_all_tasks_timings();
+    }
+    /// Assign task to `group` explicitly, `None` resets assignment.
+    /// Returns whether the task exists.
+    pub fn set_task_parameter_group(&mut self, id: TaskId, group: Option<String>) -> bool {
+        let Some(task) = self.find_task_mut(id) else {
+            return false;
+        };
+        task.parameter_group = group;
+        self.reload_all_tasks_timings();
+        true
+    }
+    /// Group the task is scheduled with, `None` for the default one or if
+    /// there is no such task.
+    pub fn task_parameter_group(&self, id: TaskId) -> Option<&str> {
+        self.parameter_groups.group_of(self.find_task(id)?)
+    }
+}
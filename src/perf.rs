@@ -0,0 +1,91 @@
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::{Duration, Instant},
+};
+
+use ssr_core::task::Task;
+
+use crate::Facade;
+
+/// Hot path counters, see [`Facade::perf_counters`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PerfCounters {
+    /// Due time computations by the scheduler.
+    pub scheduler_evaluations: u64,
+    /// Due time comparisons while searching due tasks.
+    pub comparisons: u64,
+    /// Searches of due tasks in the whole pool.
+    pub queue_rebuilds: u64,
+    pub queue_rebuild_time: Duration,
+}
+
+/// Counters updated through `&self`, disabled by default.
+#[derive(Debug, Default)]
+pub(crate) struct PerfRegistry {
+    enabled: bool,
+    scheduler_evaluations: AtomicU64,
+    comparisons: AtomicU64,
+    queue_rebuilds: AtomicU64,
+    queue_rebuild_nanos: AtomicU64,
+}
+
+impl Clone for PerfRegistry {
+    fn clone(&self) -> Self {
+        let load = |x: &AtomicU64| AtomicU64::new(x.load(Ordering::Relaxed));
+        Self {
+            enabled: self.enabled,
+            scheduler_evaluations: load(&self.scheduler_evaluations),
+            comparisons: load(&self.comparisons),
+            queue_rebuilds: load(&self.queue_rebuilds),
+            queue_rebuild_nanos: load(&self.queue_rebuild_nanos),
+        }
+    }
+}
+
+impl PerfRegistry {
+    pub(crate) fn scheduler_evaluation(&self) {
+        if self.enabled {
+            self.scheduler_evaluations.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+    pub(crate) fn comparison(&self) {
+        if self.enabled {
+            self.comparisons.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+    /// Start of a timed section, `None` while disabled.
+    pub(crate) fn start(&self) -> Option<Instant> {
+        self.enabled.then(Instant::now)
+    }
+    pub(crate) fn queue_rebuild(&self, start: Option<Instant>) {
+        if let Some(start) = start {
+            let nanos = u64::try_from(start.elapsed().as_nanos()).unwrap_or(u64::MAX);
+            self.queue_rebuilds.fetch_add(1, Ordering::Relaxed);
+            self.queue_rebuild_nanos.fetch_add(nanos, Ordering::Relaxed);
+        }
+    }
+}
+
+impl<'a, T: Task<'a>> Facade<'a, T> {
+    /// Counting is off by default; counters are kept when disabled.
+    pub fn set_perf_counters_enabled(&mut self, enabled: bool) {
+        self.perf.enabled = enabled;
+    }
+    /// Counters accumulated since creation or loading of the facade while
+    /// counting was enabled.
+    pub fn perf_counters(&self) -> PerfCounters {
+        let load = |x: &AtomicU64| x.load(Ordering::Relaxed);
+        PerfCounters {
+            scheduler_evaluations: load(&self.perf.scheduler_evaluations),
+            comparisons: load(&self.perf.comparisons),
+            queue_rebuilds: load(&self.perf.queue_rebuilds),
+            queue_rebuild_time: Duration::from_nanos(load(&self.perf.queue_rebuild_nanos)),
+        }
+    }
+    pub fn reset_perf_counters(&mut self) {
+        self.perf = PerfRegistry {
+            enabled: self.perf.enabled,
+            ..PerfRegistry::default()
+        };
+    }
+}
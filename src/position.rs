@@ -0,0 +1,42 @@
+use ssr_core::{task::Task, tasks_facade::TaskId};
+
+use crate::Facade;
+
+impl<'a, T: Task<'a>> Facade<'a, T> {
+    /// New tasks are introduced in ascending position order; ties are broken
+    /// by id. Defaults to insertion order.
+    pub fn position(&self, id: TaskId) -> Option<u64> {
+        self.find_task(id).map(|task| task.position)
+    }
+    /// Returns whether the task exists.
+    pub fn reposition(&mut self, id: TaskId, position: u64) -> bool {
+        let Some(task) = self.find_task_mut(id) else {
+            return false;
+        };
+        task.position = position;
+        self.next_position = self.next_position.max(position.saturating_add(1));
+        true
+    }
+    /// Give listed tasks consecutive positions in the listed order, starting
+    /// at the smallest of their current positions. Positions past `u64::MAX`
+    /// stay at `u64::MAX`. Returns number of tasks found.
+    pub fn reorder(&mut self, ids: &[TaskId]) -> usize {
+        let Some(start) = ids.iter().filter_map(|&id| self.position(id)).min() else {
+            return 0;
+        };
+        let mut found = 0;
+        for &id in ids {
+            if self.reposition(id, start.saturating_add(found)) {
+                found += 1;
+            }
+        }
+        usize::try_from(found).unwrap_or(usize::MAX)
+    }
+
+    /// Position for a task added now.
+    pub(crate) fn take_next_position(&mut self) -> u64 {
+        let position = self.next_position;
+        self.next_position = self.next_position.saturating_add(1);
+        position
+    }
+}
@@ -0,0 +1,152 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    time::Duration,
+};
+
+use ssr_core::{task::Task, tasks_facade::TaskId};
+
+use crate::{Facade, TaskWrapper};
+
+/// Interval a prerequisite must reach before gated tasks are scheduled, by
+/// default.
+pub const DEFAULT_PREREQUISITE_MATURITY: Duration = Duration::from_hours(21 * 24);
+
+pub(crate) fn default_prerequisite_maturity() -> Duration {
+    DEFAULT_PREREQUISITE_MATURITY
+}
+
+/// Tag -> tags whose tasks must be mature.
+pub(crate) type TagPrerequisites = BTreeMap<String, BTreeSet<String>>;
+
+/// Prerequisites that are not mature yet.
+pub(crate) struct Immature {
+    tasks: HashSet<TaskId>,
+    tags: HashMap<String, usize>,
+}
+
+impl Immature {
+    pub(crate) fn blocks<T>(
+        &self,
+        task: &TaskWrapper<T>,
+        tag_prerequisites: &TagPrerequisites,
+    ) -> bool {
+        let task_is_immature = self.tasks.contains(&task.id);
+        task.prerequisites.iter().any(|id| self.tasks.contains(id))
+            || task
+                .tags
+                .iter()
+                .filter_map(|tag| tag_prerequisites.get(tag))
+                .flatten()
+                .any(|required| {
+                    let own = usize::from(task_is_immature && task.tags.contains(required));
+                    self.tags.get(required).is_some_and(|count| *count > own)
+                })
+    }
+}
+
+impl<'a, T: Task<'a>> Facade<'a, T> {
+    /// Task isn't scheduled until `prerequisite` is mature. Returns whether
+    /// the task exists. Circular prerequisites block each other forever.
+    pub fn add_prerequisite(&mut self, id: TaskId, prerequisite: TaskId) -> bool {
+        let Some(task) = self.find_task_mut(id) else {
+            return false;
+        };
+        task.prerequisites.insert(prerequisite);
+        self.reload_all_tasks_timings();
+        true
+    }
+    /// Returns whether the link existed.
+    pub fn remove_prerequisite(&mut self, id: TaskId, prerequisite: TaskId) -> bool {
+        let removed = self
+            .find_task_mut(id)
+            .is_some_and(|task| task.prerequisites.remove(&prerequisite));
+        self.reload_all_tasks_timings();
+        removed
+    }
+    pub fn prerequisites(&self, id: TaskId) -> impl Iterator<Item = TaskId> {
+        self.find_task(id)
+            .into_iter()
+            .flat_map(|task| task.prerequisites.iter().copied())
+    }
+
+    /// Tasks tagged `tag` aren't scheduled until every task tagged
+    /// `prerequisite` is mature.
+    pub fn add_tag_prerequisite(
+        &mut self,
+        tag: impl Into<String>,
+        prerequisite: impl Into<String>,
+    ) {
+        self.tag_prerequisites
+            .entry(tag.into())
+            .or_default()
+            .insert(prerequisite.into());
+        self.reload_all_tasks_timings();
+    }
+    pub fn remove_tag_prerequisite(&mut self, tag: &str, prerequisite: &str) -> bool {
+        let Some(required) = self.tag_prerequisites.get_mut(tag) else {
+            return false;
+        };
+        let removed = required.remove(prerequisite);
+        if required.is_empty() {
+            self.tag_prerequisites.remove(tag);
+        }
+        self.reload_all_tasks_timings();
+        removed
+    }
+    pub fn tag_prerequisites(&self, tag: &str) -> impl Iterator<Item = &str> {
+        self.tag_prerequisites
+            .get(tag)
+            .into_iter()
+            .flatten()
+            .map(String::as_str)
+    }
+
+    /// A prerequisite is mature once its latest review scheduled it at least
+    /// this far ahead.
+    pub fn prerequisite_maturity(&self) -> Duration {
+        self.prerequisite_maturity
+    }
+    pub fn set_prerequisite_maturity(&mut self, maturity: Duration) {
+        self.prerequisite_maturity = maturity;
+        self.reload_all_tasks_timings();
+    }
+
+    /// Whether the task waits for its prerequisites.
+    pub fn is_gated(&self, id: TaskId) -> bool {
+        self.find_task(id).is_some_and(|task| {
+            self.immature_prerequisites()
+                .is_some_and(|immature| immature.blocks(task, &self.tag_prerequisites))
+        })
+    }
+
+    /// `None` if no task has prerequisites.
+    pub(crate) fn immature_prerequisites(&self) -> Option<Immature> {
+        let tasks = || self.tasks_pool.iter().chain(self.tasks_to_recall.iter());
+        if self.tag_prerequisites.is_empty() && tasks().all(|t| t.prerequisites.is_empty()) {
+            return None;
+        }
+        let mut latest_interval = HashMap::new();
+        for record in self
+            .review_log
+            .iter()
+            .filter(|r| r.source.changes_schedule())
+        {
+            latest_interval.insert(record.task_id, record.next_interval());
+        }
+        let mut immature = Immature {
+            tasks: HashSet::new(),
+            tags: HashMap::new(),
+        };
+        for task in tasks().filter(|t| {
+            latest_interval
+                .get(&t.id)
+                .is_none_or(|interval| *interval < self.prerequisite_maturity)
+        }) {
+            immature.tasks.insert(task.id);
+            for tag in &task.tags {
+                *immature.tags.entry(tag.clone()).or_default() += 1;
+            }
+        }
+        Some(immature)
+    }
+}
@@ -0,0 +1,99 @@
+use std::time::{Duration, SystemTime};
+
+use ssr_core::task::Task;
+
+use crate::{
+    Facade, LocalDate, TaskWrapper,
+    time::{SECS_PER_DAY, from_local_secs},
+};
+
+/// Tasks tagged `exam::YYYY-MM-DD` are boosted before the exam.
+pub const EXAM_TAG_PREFIX: &str = "exam::";
+
+/// Automatic priority adjustments applied by [`Facade::run_maintenance`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PriorityRules {
+    /// How long before an exam its tasks start getting boosted.
+    pub exam_window: Duration,
+    /// Boost on the exam day; it grows linearly over the window.
+    pub max_exam_boost: i32,
+    /// Manual priorities set longer ago than this move one step towards
+    /// zero, once per period. `None` keeps them forever.
+    pub manual_decay_after: Option<Duration>,
+}
+
+fn parse_date(text: &str) -> Option<LocalDate> {
+    let mut parts = text.splitn(3, '-');
+    let date = LocalDate {
+        year: parts.next()?.parse().ok()?,
+        month: parts.next()?.parse().ok()?,
+        day: parts.next()?.parse().ok()?,
+    };
+    ((1..=12).contains(&date.month) && (1..=31).contains(&date.day)).then_some(date)
+}
+
+fn exam_boost(rules: &PriorityRules, now: SystemTime, exam: SystemTime) -> i32 {
+    let Ok(remaining) = exam.duration_since(now) else {
+        return 0;
+    };
+    if remaining > rules.exam_window || rules.exam_window.is_zero() {
+        return 0;
+    }
+    let elapsed = 1. - remaining.as_secs_f64() / rules.exam_window.as_secs_f64();
+    #[allow(clippy::cast_possible_truncation)] // boost is within `0..=max_exam_boost`
+    let boost = (f64::from(rules.max_exam_boost) * elapsed).ceil() as i32;
+    boost
+}
+
+impl<'a, T: Task<'a>> Facade<'a, T> {
+    /// Recompute exam boosts and decay stale manual priorities. Returns number
+    /// of tasks whose priority changed.
+    pub(crate) fn apply_priority_rules(&mut self, rules: &PriorityRules) -> usize {
+        let now = self.now();
+        let exam_days = |task: &TaskWrapper<T>| {
+            task.tags
+                .iter()
+                .filter_map(|tag| parse_date(tag.strip_prefix(EXAM_TAG_PREFIX)?))
+                .collect::<Vec<_>>()
+        };
+        let epoch = self.next_epoch();
+        let day_offset_secs = self.day_offset_secs();
+        let mut changed = 0;
+        for task in self
+            .tasks_pool
+            .iter_mut()
+            .chain(self.tasks_to_recall.iter_mut())
+        {
+            let boost = exam_days(task)
+                .into_iter()
+                .map(|date| {
+                    // The boost peaks at the end of the exam day.
+                    let exam = from_local_secs((date.to_day() + 1) * SECS_PER_DAY, day_offset_secs);
+                    exam_boost(rules, now, exam)
+                })
+                .max()
+                .unwrap_or(0);
+            let mut priority = task.priority;
+            if priority != 0 && task.priority_set_at.is_none() {
+                task.priority_set_at = Some(now);
+            }
+            if let Some(decay_after) = rules.manual_decay_after
+                && priority != 0
+                && task
+                    .priority_set_at
+                    .and_then(|set_at| set_at.checked_add(decay_after))
+                    .is_some_and(|decays_at| decays_at <= now)
+            {
+                priority -= priority.signum();
+                task.priority_set_at = Some(now);
+            }
+            if boost != task.priority_boost || priority != task.priority {
+                task.priority_boost = boost;
+                task.priority = priority;
+                task.modified = epoch;
+                changed += 1;
+            }
+        }
+        changed
+    }
+}
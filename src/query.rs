@@ -0,0 +1,171 @@
+use std::{collections::HashSet, fmt, str::FromStr, time::SystemTime};
+
+use ssr_core::{task::Task, tasks_facade::TaskId};
+
+use crate::{Facade, TaskWrapper};
+
+/// Filter selecting tasks of a facade.
+///
+/// Can be built directly or parsed from a search string in the spirit of
+/// Anki's search syntax: whitespace separated terms must all match, `or`
+/// separates alternatives and a leading `-` negates a term.
+///
+/// | term           | matches                             |
+/// |----------------|-------------------------------------|
+/// | `tag:name`     | tasks tagged `name`                 |
+/// | `tag:prefix*`  | tasks with a tag starting with it   |
+/// | `id:123`       | task with this id                   |
+/// | `is:due`       | tasks that can be reviewed now      |
+/// | `is:new`       | tasks that were never reviewed      |
+/// | `is:suspended` | suspended tasks                     |
+/// | `is:buried`    | tasks buried at the moment          |
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Query {
+    All,
+    Tag(String),
+    TagPrefix(String),
+    Id(TaskId),
+    Due,
+    New,
+    Suspended,
+    Buried,
+    Not(Box<Query>),
+    And(Vec<Query>),
+    Or(Vec<Query>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QueryError {
+    UnknownTerm(String),
+    UnknownState(String),
+    InvalidId(String),
+    /// `or` with no terms on one of its sides.
+    EmptyAlternative,
+}
+
+impl fmt::Display for QueryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownTerm(term) => write!(f, "unknown search term: {term}"),
+            Self::UnknownState(state) => write!(f, "unknown task state: {state}"),
+            Self::InvalidId(id) => write!(f, "invalid task id: {id}"),
+            Self::EmptyAlternative => write!(f, "`or` without terms on one side"),
+        }
+    }
+}
+impl std::error::Error for QueryError {}
+
+impl FromStr for Query {
+    type Err = QueryError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut alternatives = Vec::new();
+        let mut terms = Vec::new();
+        for token in s.split_whitespace() {
+            if token.eq_ignore_ascii_case("or") {
+                if terms.is_empty() {
+                    return Err(QueryError::EmptyAlternative);
+                }
+                alternatives.push(Self::all_of(std::mem::take(&mut terms)));
+                continue;
+            }
+            terms.push(parse_term(token)?);
+        }
+        if terms.is_empty() && !alternatives.is_empty() {
+            return Err(QueryError::EmptyAlternative);
+        }
+        alternatives.push(Self::all_of(terms));
+
+        Ok(if alternatives.len() == 1 {
+            alternatives.pop().expect("checked length")
+        } else {
+            Self::Or(alternatives)
+        })
+    }
+}
+
+fn parse_term(token: &str) -> Result<Query, QueryError> {
+    if let Some(token) = token.strip_prefix('-') {
+        return Ok(Query::Not(Box::new(parse_term(token)?)));
+    }
+    let Some((key, value)) = token.split_once(':') else {
+        return Err(QueryError::UnknownTerm(token.to_owned()));
+    };
+    match key.to_ascii_lowercase().as_str() {
+        "tag" => Ok(match value.strip_suffix('*') {
+            Some(prefix) => Query::TagPrefix(prefix.to_owned()),
+            None => Query::Tag(value.to_owned()),
+        }),
+        "id" => value
+            .parse()
+            .map(Query::Id)
+            .map_err(|_| QueryError::InvalidId(value.to_owned())),
+        "is" => match value.to_ascii_lowercase().as_str() {
+            "due" => Ok(Query::Due),
+            "new" => Ok(Query::New),
+            "suspended" => Ok(Query::Suspended),
+            "buried" => Ok(Query::Buried),
+            _ => Err(QueryError::UnknownState(value.to_owned())),
+        },
+        _ => Err(QueryError::UnknownTerm(token.to_owned())),
+    }
+}
+
+impl Query {
+    fn all_of(mut terms: Vec<Query>) -> Self {
+        match terms.len() {
+            0 => Self::All,
+            1 => terms.pop().expect("checked length"),
+            _ => Self::And(terms),
+        }
+    }
+}
+
+/// Data shared by all evaluations of one query.
+pub(crate) struct QueryContext {
+    now: SystemTime,
+    reviewed: HashSet<TaskId>,
+}
+
+impl Query {
+    pub(crate) fn matches<'a, T: Task<'a>>(
+        &self,
+        facade: &Facade<'a, T>,
+        context: &QueryContext,
+        task: &TaskWrapper<T>,
+    ) -> bool {
+        match self {
+            Self::All => true,
+            Self::Tag(tag) => task.tags.contains(tag),
+            Self::TagPrefix(prefix) => task.tags.iter().any(|tag| tag.starts_with(prefix)),
+            Self::Id(id) => task.id == *id,
+            Self::Due => facade.is_due(task, context.now),
+            Self::New => !context.reviewed.contains(&task.id),
+            Self::Suspended => task.suspended,
+            Self::Buried => task.buried_until.is_some_and(|until| until > context.now),
+            Self::Not(query) => !query.matches(facade, context, task),
+            Self::And(queries) => queries.iter().all(|q| q.matches(facade, context, task)),
+            Self::Or(queries) => queries.iter().any(|q| q.matches(facade, context, task)),
+        }
+    }
+}
+
+impl<'a, T: Task<'a>> Facade<'a, T> {
+    pub(crate) fn query_context(&self) -> QueryContext {
+        QueryContext {
+            now: self.now(),
+            reviewed: self.review_log.iter().map(|r| r.task_id).collect(),
+        }
+    }
+
+    /// Ids of all tasks matching `query`.
+    pub fn query(&self, query: &Query) -> Vec<TaskId> {
+        let context = self.query_context();
+        self.tasks_pool
+            .iter()
+            .chain(self.tasks_to_recall.iter())
+            .filter(|task| query.matches(self, &context, task))
+            .map(|task| task.id)
+            .collect()
+    }
+}
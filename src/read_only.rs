@@ -0,0 +1,137 @@
+use std::{fmt, io, ops::Deref};
+
+use serde::{Deserialize, Serialize};
+use ssr_core::{
+    task::{SharedStateExt, Task},
+    tasks_facade::{self, TaskId, TasksFacade},
+};
+
+use crate::{Event, Facade};
+
+/// Facade that can't be changed, for viewers and publishing pipelines.
+///
+/// Derefs to [`Facade`] for everything that only reads. Through
+/// [`TasksFacade`], `complete_task` and `optimize` fail with
+/// [`ReadOnlyError`], `remove` returns `false` and other mutating calls have
+/// no effect. Ignored calls are reported with [`Event::ReadOnlyCallIgnored`].
+/// Serializes exactly like the wrapped facade.
+#[derive(Serialize, Deserialize)]
+#[serde(transparent, bound(deserialize = "'a: 'de, 'de: 'a"))]
+pub struct ReadOnly<'a, T: Task<'a>> {
+    facade: Facade<'a, T>,
+}
+
+/// A mutating call was made on a [`ReadOnly`] facade.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReadOnlyError;
+
+impl fmt::Display for ReadOnlyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "facade is read-only")
+    }
+}
+impl std::error::Error for ReadOnlyError {}
+
+impl<'a, T: Task<'a>> fmt::Debug for ReadOnly<'a, T>
+where
+    Facade<'a, T>: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReadOnly")
+            .field("facade", &self.facade)
+            .finish()
+    }
+}
+
+impl<'a, T: Task<'a>> Facade<'a, T> {
+    pub fn into_read_only(self) -> ReadOnly<'a, T> {
+        ReadOnly { facade: self }
+    }
+}
+
+impl<'a, T: Task<'a>> ReadOnly<'a, T> {
+    /// [`Facade::take_events`], the only change a read-only facade allows.
+    pub fn take_events(&mut self) -> Vec<Event> {
+        self.facade.take_events()
+    }
+    fn ignored(&mut self, call: &'static str) {
+        self.facade.events.push(Event::ReadOnlyCallIgnored { call });
+    }
+}
+
+impl<'a, T: Task<'a>> Deref for ReadOnly<'a, T> {
+    type Target = Facade<'a, T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.facade
+    }
+}
+
+impl<'a, T: Task<'a>> TasksFacade<'a, T> for ReadOnly<'a, T> {
+    fn new(name: String, desired_retention: f64) -> Self {
+        Facade::new(name, desired_retention).into_read_only()
+    }
+
+    fn get_name(&self) -> &str {
+        self.facade.get_name()
+    }
+
+    fn get_desired_retention(&self) -> f64 {
+        self.facade.get_desired_retention()
+    }
+
+    /// Has no effect, reported with [`Event::ReadOnlyCallIgnored`].
+    fn set_desired_retention(&mut self, _desired_retention: f64) {
+        self.ignored("set_desired_retention");
+    }
+
+    fn tasks_total(&self) -> usize {
+        self.facade.tasks_total()
+    }
+
+    fn tasks_to_complete(&self) -> usize {
+        self.facade.tasks_to_complete()
+    }
+
+    /// Always fails with [`io::ErrorKind::PermissionDenied`] wrapping
+    /// [`ReadOnlyError`].
+    fn complete_task(
+        &mut self,
+        _interaction: &mut impl FnMut(
+            TaskId,
+            s_text_input_f::Blocks,
+        ) -> std::io::Result<s_text_input_f::Response>,
+    ) -> Result<(), tasks_facade::Error> {
+        Err(io::Error::new(io::ErrorKind::PermissionDenied, ReadOnlyError).into())
+    }
+
+    /// Has no effect, reported with [`Event::ReadOnlyCallIgnored`].
+    fn insert(&mut self, _task: T) {
+        self.ignored("insert");
+    }
+
+    /// Has no effect, reported with [`Event::ReadOnlyCallIgnored`].
+    fn create_task(&mut self, _input: s_text_input_f::BlocksWithAnswer) {
+        self.ignored("create_task");
+    }
+
+    fn iter<'t>(&'t self) -> impl Iterator<Item = (&'t T, TaskId)>
+    where
+        T: 't,
+    {
+        self.facade.iter()
+    }
+
+    /// Always returns `false`, reported with [`Event::ReadOnlyCallIgnored`].
+    fn remove(&mut self, _id: TaskId) -> bool {
+        self.ignored("remove");
+        false
+    }
+
+    fn optimize(&mut self) -> Result<(), Box<dyn std::error::Error>>
+    where
+        T::SharedState: SharedStateExt<'a, T>,
+    {
+        Err(Box::new(ReadOnlyError))
+    }
+}
@@ -0,0 +1,83 @@
+use std::time::{Duration, SystemTime};
+
+use serde::{Deserialize, Serialize};
+use ssr_core::{task::Task, tasks_facade::TaskId};
+
+use crate::Facade;
+
+/// Due time set by the user rather than the scheduler.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct DueOverride {
+    pub task_id: TaskId,
+    pub at: SystemTime,
+    pub previous_due: SystemTime,
+    pub new_due: SystemTime,
+    pub kind: OverrideKind,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OverrideKind {
+    SetDue,
+    Postpone,
+    /// Moved by [`Facade::shift_due`].
+    Shift,
+    /// Interval scaled after a review, see [`Facade::set_interval_multiplier`].
+    Multiplier,
+}
+
+impl<'a, T: Task<'a>> Facade<'a, T> {
+    /// Make the task due at `due` until its next completion. Returns whether
+    /// the task exists and isn't frozen.
+    ///
+    /// The change is recorded in [`crate::ReviewLog::overrides`], and once the
+    /// task is reviewed on the manual schedule it no longer feeds `optimize`.
+    pub fn set_due(&mut self, id: TaskId, due: SystemTime) -> bool {
+        let found = self.override_due(id, OverrideKind::SetDue, |_| Some(due));
+        self.requeue(id);
+        found
+    }
+    /// Push due time of the task `by` later, see [`Facade::set_due`]. Returns
+    /// false without changes if the new due time isn't representable.
+    pub fn postpone(&mut self, id: TaskId, by: Duration) -> bool {
+        let found = self.override_due(id, OverrideKind::Postpone, |due| due.checked_add(by));
+        self.requeue(id);
+        found
+    }
+
+    /// Doesn't move the task between queues. Returns false without changes
+    /// if `new_due` gives `None`.
+    pub(crate) fn override_due(
+        &mut self,
+        id: TaskId,
+        kind: OverrideKind,
+        new_due: impl FnOnce(SystemTime) -> Option<SystemTime>,
+    ) -> bool {
+        let Some(task) = self.find_task(id).filter(|task| !self.is_frozen(task)) else {
+            return false;
+        };
+        let previous_due = self.next_repetition(task);
+        let Some(new_due) = new_due(previous_due) else {
+            return false;
+        };
+        let at = self.now();
+        let Some(task) = self.find_task_mut(id) else {
+            return false;
+        };
+        task.due_override = Some(new_due);
+        task.manual_due = true;
+        self.review_log.push_override(DueOverride {
+            task_id: id,
+            at,
+            previous_due,
+            new_due,
+            kind,
+        });
+        true
+    }
+    /// Move the task to the queue its due time now puts it in.
+    fn requeue(&mut self, id: TaskId) {
+        if let Some(task) = self.take_task(id) {
+            self.place_task(task);
+        }
+    }
+}
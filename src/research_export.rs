@@ -0,0 +1,55 @@
+use std::{
+    io::{self, Write},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use ssr_core::{task::Task, tasks_facade::TaskId};
+
+use crate::{Facade, content::fnv1a};
+
+fn millis(time: SystemTime) -> u128 {
+    time.duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis()
+}
+
+fn anonymize(salt: u64, id: TaskId) -> u64 {
+    fnv1a(salt.to_le_bytes().into_iter().chain(id.to_le_bytes()))
+}
+
+impl<'a, T: Task<'a>> Facade<'a, T> {
+    /// Write the review log as CSV for scheduler research: task ids are
+    /// replaced with hashes salted with `salt`, and neither task content,
+    /// tags nor reviewer names are included. Times are in milliseconds since
+    /// the Unix epoch.
+    ///
+    /// Keep `salt` secret and reuse it to make several exports of the same
+    /// collection joinable.
+    /// # Errors
+    /// If writing fails.
+    pub fn export_anonymized_log(&self, mut writer: impl Write, salt: u64) -> io::Result<()> {
+        writeln!(
+            writer,
+            "task,reviewed_at,duration_ms,previous_review,scheduled_due,next_due,\
+             desired_retention,outcome,source,confidence"
+        )?;
+        for record in self.review_log.iter() {
+            let previous_review = record.previous_review.map(millis);
+            writeln!(
+                writer,
+                "{:016x},{},{},{},{},{},{},{:?},{:?},{}",
+                anonymize(salt, record.task_id),
+                millis(record.reviewed_at),
+                record.duration.as_millis(),
+                previous_review.map(|x| x.to_string()).unwrap_or_default(),
+                millis(record.scheduled_due),
+                millis(record.next_due),
+                record.desired_retention,
+                record.outcome,
+                record.source,
+                record.confidence.map(|x| x.to_string()).unwrap_or_default(),
+            )?;
+        }
+        Ok(())
+    }
+}
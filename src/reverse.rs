@@ -0,0 +1,73 @@
+use std::time::Duration;
+
+use s_text_input_f::{Block, BlocksWithAnswer, ParagraphItem};
+use serde::{Deserialize, Serialize};
+use ssr_core::{task::Task, tasks_facade::TaskId};
+
+use crate::{Facade, RejectReason};
+
+/// When the reverse card of [`Facade::create_bidirectional`] is scheduled.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ReverseMode {
+    /// Both cards are introduced independently.
+    #[default]
+    Immediate,
+    /// The reverse card waits until the forward card is mature, see
+    /// [`Facade::set_prerequisite_maturity`].
+    AfterForwardMatures,
+}
+
+fn card(question: &str, answer: &str) -> BlocksWithAnswer {
+    BlocksWithAnswer {
+        blocks: vec![Block::Paragraph(vec![
+            ParagraphItem::Text(question.to_owned()),
+            ParagraphItem::Placeholder,
+        ])],
+        answer: vec![vec![answer.to_owned()]],
+    }
+}
+
+impl<'a, T: Task<'a>> Facade<'a, T> {
+    /// Create a `front` -> `back` task and its `back` -> `front` sibling.
+    /// Returns ids of the forward and the reverse task.
+    ///
+    /// # Errors
+    /// If either side exceeds the content limit; nothing is created then.
+    pub fn create_bidirectional(
+        &mut self,
+        front: &str,
+        back: &str,
+        mode: ReverseMode,
+    ) -> Result<(TaskId, TaskId), RejectReason> {
+        let forward = self.limit_content(card(front, back))?;
+        let reverse = self.limit_content(card(back, front))?;
+        let forward = self.push_new_task(T::new(forward)).id;
+        let reverse = self.push_new_task(T::new(reverse));
+        reverse.sibling = Some(forward);
+        if mode == ReverseMode::AfterForwardMatures {
+            reverse.prerequisites.insert(forward);
+        }
+        let reverse = reverse.id;
+        if let Some(task) = self.find_task_mut(forward) {
+            task.sibling = Some(reverse);
+        }
+        Ok((forward, reverse))
+    }
+    /// Minimum distance between due times of siblings, `None` if siblings
+    /// are scheduled independently.
+    pub fn sibling_spacing(&self) -> Option<Duration> {
+        self.sibling_spacing
+    }
+    /// Keep siblings due at least `spacing` apart, so both sides of a fact
+    /// don't meet in one session. Applied when a task is reviewed: its due
+    /// time is nudged to the nearer side of the sibling's one.
+    pub fn set_sibling_spacing(&mut self, spacing: Option<Duration>) {
+        self.sibling_spacing = spacing;
+    }
+    /// Other side of a task created by [`Facade::create_bidirectional`], if it
+    /// still exists.
+    pub fn sibling(&self, id: TaskId) -> Option<TaskId> {
+        let sibling = self.find_task(id)?.sibling?;
+        self.find_task(sibling).map(|task| task.id)
+    }
+}
@@ -0,0 +1,135 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, SystemTime},
+};
+
+use ssr_core::{task::Task, tasks_facade::TaskId};
+
+use crate::{Facade, ReviewSource};
+
+/// How far ahead of its due time a task may be pulled per week, by default.
+pub const DEFAULT_REVIEW_AHEAD_CAP: Duration = Duration::from_hours(2 * 24);
+
+/// Tasks due within this time are handed out as due now, by default.
+pub const DEFAULT_LEARN_AHEAD: Duration = Duration::from_secs(10);
+
+pub(crate) fn default_learn_ahead() -> Duration {
+    DEFAULT_LEARN_AHEAD
+}
+
+#[allow(clippy::unnecessary_wraps)] // serde default of an optional field
+pub(crate) fn default_review_ahead_cap() -> Option<Duration> {
+    Some(DEFAULT_REVIEW_AHEAD_CAP)
+}
+
+const WEEK: Duration = Duration::from_hours(7 * 24);
+
+impl<'a, T: Task<'a>> Facade<'a, T> {
+    pub fn learn_ahead(&self) -> Duration {
+        self.learn_ahead
+    }
+    /// Treat tasks due within `learn_ahead` as due now, e.g. zero for exact
+    /// due times or 20 minutes to finish short learning steps in one sitting.
+    pub fn set_learn_ahead(&mut self, learn_ahead: Duration) {
+        self.learn_ahead = learn_ahead;
+        self.reload_all_tasks_timings();
+    }
+
+    /// `None` means unlimited.
+    pub fn review_ahead_cap(&self) -> Option<Duration> {
+        self.review_ahead_cap
+    }
+    /// Limit total time each task is reviewed ahead of its due time by
+    /// review-ahead and cram reviews within any 7 days, so studying ahead
+    /// habitually doesn't compress all intervals.
+    pub fn set_review_ahead_cap(&mut self, cap: Option<Duration>) {
+        self.review_ahead_cap = cap;
+    }
+
+    /// Complete a due task, or if there is none, the task that becomes due
+    /// soonest within `horizon` and whose review-ahead cap allows it. The
+    /// review is logged as [`ReviewSource::ReviewAhead`].
+    /// # Errors
+    /// If interaction returns error, no task can be reviewed ahead or the
+    /// daily review limit is reached.
+    pub fn complete_task_ahead(
+        &mut self,
+        horizon: Duration,
+        interaction: &mut impl FnMut(
+            TaskId,
+            s_text_input_f::Blocks,
+        ) -> std::io::Result<s_text_input_f::Response>,
+    ) -> Result<(), ssr_core::tasks_facade::Error> {
+        self.find_tasks_to_recall();
+        if !self.tasks_to_recall.is_empty() {
+            return self.complete_task_as(ReviewSource::Normal, interaction);
+        }
+        self.check_daily_limit()?;
+        let now = self.now();
+        let pulled = self.pulled_ahead_since(now.checked_sub(WEEK).unwrap_or(now));
+        let immature = self.immature_prerequisites();
+        let candidate = self
+            .tasks_pool
+            .iter()
+            .enumerate()
+            .filter(|(_, task)| {
+                !immature
+                    .as_ref()
+                    .is_some_and(|immature| immature.blocks(task, &self.tag_prerequisites))
+            })
+            .filter_map(|(index, task)| {
+                let ahead = self
+                    .available_at(task)?
+                    .duration_since(now)
+                    .unwrap_or_default();
+                let allowed = self.review_ahead_cap.is_none_or(|cap| {
+                    pulled.get(&task.id).copied().unwrap_or_default() + ahead <= cap
+                });
+                (ahead <= horizon && allowed).then_some((ahead, index))
+            })
+            .min();
+        let Some((_, index)) = candidate else {
+            return Err(self.no_task_error());
+        };
+        let wrapper = self.tasks_pool.swap_remove(index);
+        self.complete_taken_task(wrapper, ReviewSource::ReviewAhead, interaction)
+    }
+
+    /// Review task `id` now, due or not, e.g. because the user wants to go
+    /// over it. The review is logged as [`ReviewSource::ReviewAhead`]: it
+    /// counts toward the review-ahead cap, and a next interval shorter than
+    /// the scheduled one isn't a lapse unless it's shorter than the time that
+    /// passed since the previous review.
+    /// # Errors
+    /// If the task doesn't exist, interaction returns error or the daily
+    /// review limit is reached.
+    pub fn complete_early(
+        &mut self,
+        id: TaskId,
+        interaction: &mut impl FnMut(
+            TaskId,
+            s_text_input_f::Blocks,
+        ) -> std::io::Result<s_text_input_f::Response>,
+    ) -> Result<(), ssr_core::tasks_facade::Error> {
+        self.check_daily_limit()?;
+        let Some(wrapper) = self.take_task(id) else {
+            return Err(ssr_core::tasks_facade::Error::NoTask);
+        };
+        self.complete_taken_task(wrapper, ReviewSource::ReviewAhead, interaction)
+    }
+
+    /// Per task, total time reviews after `since` happened ahead of due time.
+    fn pulled_ahead_since(&self, since: SystemTime) -> HashMap<TaskId, Duration> {
+        let mut pulled = HashMap::new();
+        for record in self.review_log.iter().filter(|r| {
+            r.reviewed_at > since
+                && matches!(r.source, ReviewSource::ReviewAhead | ReviewSource::Cram)
+        }) {
+            *pulled.entry(record.task_id).or_default() += record
+                .scheduled_due
+                .duration_since(record.reviewed_at)
+                .unwrap_or_default();
+        }
+        pulled
+    }
+}
@@ -0,0 +1,268 @@
+use std::{
+    collections::HashMap,
+    sync::OnceLock,
+    time::{Duration, SystemTime},
+};
+
+use serde::{Deserialize, Serialize};
+use ssr_core::{task::Task, tasks_facade::TaskId};
+
+use crate::{DueOverride, Facade};
+
+/// How a review went, judged by how the scheduler reacted to it: a review
+/// after which the interval shrank is a lapse.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Outcome {
+    /// First review of the task, or the first after its content was
+    /// replaced with a fresh task, see [`crate::Facade::edit_task`].
+    New,
+    Pass,
+    Lapse,
+}
+
+/// How the review happened.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ReviewSource {
+    /// Regular review of a due task.
+    #[default]
+    Normal,
+    /// Task was reviewed before it was due.
+    ReviewAhead,
+    /// Extra practice that doesn't reflect natural forgetting.
+    Cram,
+    /// Review in a session restricted to a subset of tasks.
+    Filtered,
+    /// Review replayed by undo/redo.
+    UndoRedo,
+    /// Review of a frozen task, which didn't change its schedule, see
+    /// [`Facade::freeze`].
+    Frozen,
+}
+
+impl ReviewSource {
+    /// Whether reviews of this kind reflect how well the scheduler works and
+    /// are used by statistics and optimizer input checks.
+    #[must_use]
+    pub fn is_scheduled(self) -> bool {
+        matches!(self, Self::Normal | Self::ReviewAhead | Self::Filtered)
+    }
+    /// Whether the review completed the task, changing its schedule.
+    #[must_use]
+    pub fn changes_schedule(self) -> bool {
+        self != Self::Frozen
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ReviewRecord {
+    pub task_id: TaskId,
+    pub reviewed_at: SystemTime,
+    /// Time spent in interaction.
+    pub duration: Duration,
+    /// Previous review of the same task.
+    pub previous_review: Option<SystemTime>,
+    /// When the task was due at the moment of review.
+    pub scheduled_due: SystemTime,
+    /// When the task became due after the review.
+    pub next_due: SystemTime,
+    /// Retention target the task was scheduled with.
+    pub desired_retention: f64,
+    pub outcome: Outcome,
+    #[serde(default)]
+    pub source: ReviewSource,
+    /// See [`Facade::set_reviewer`].
+    #[serde(default)]
+    pub reviewer: Option<String>,
+    /// `scheduled_due` was set by the user, see [`Facade::set_due`].
+    #[serde(default)]
+    pub manually_scheduled: bool,
+    /// Self-reported, see [`Facade::complete_task_with_confidence`].
+    #[serde(default)]
+    pub confidence: Option<f64>,
+}
+
+impl Outcome {
+    /// Judge a review of a task previously reviewed at `previous_review` by
+    /// the interval the scheduler assigned.
+    pub(crate) fn judge(
+        previous_review: Option<SystemTime>,
+        scheduled_due: SystemTime,
+        reviewed_at: SystemTime,
+        next_due: SystemTime,
+    ) -> Self {
+        let Some(previous_review) = previous_review else {
+            return Self::New;
+        };
+        let interval = scheduled_due
+            .duration_since(previous_review)
+            .unwrap_or_default();
+        let next_interval = next_due.duration_since(reviewed_at).unwrap_or_default();
+        if next_interval < interval {
+            Self::Lapse
+        } else {
+            Self::Pass
+        }
+    }
+}
+
+impl ReviewRecord {
+    /// Interval the task had when it was reviewed.
+    #[must_use]
+    pub fn interval(&self) -> Option<Duration> {
+        let previous_review = self.previous_review?;
+        Some(
+            self.scheduled_due
+                .duration_since(previous_review)
+                .unwrap_or_default(),
+        )
+    }
+    /// Recall probability expected at the moment of review, assuming
+    /// exponential forgetting that reaches `desired_retention` exactly at the
+    /// scheduled due time. `None` for the first review.
+    #[must_use]
+    pub fn predicted_recall(&self) -> Option<f64> {
+        let previous_review = self.previous_review?;
+        let interval = self.interval()?.as_secs_f64();
+        let elapsed = self
+            .reviewed_at
+            .duration_since(previous_review)
+            .unwrap_or_default()
+            .as_secs_f64();
+        if interval == 0. {
+            return Some(self.desired_retention);
+        }
+        Some(self.desired_retention.powf(elapsed / interval))
+    }
+    /// Interval assigned by this review.
+    #[must_use]
+    pub fn next_interval(&self) -> Duration {
+        self.next_due
+            .duration_since(self.reviewed_at)
+            .unwrap_or_default()
+    }
+}
+
+/// Chronological record of all completed reviews and manual due changes.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct ReviewLog {
+    records: Vec<ReviewRecord>,
+    #[serde(default)]
+    overrides: Vec<DueOverride>,
+    /// Indices of `records` by task, built on first use. Appending keeps it,
+    /// other changes drop it.
+    #[serde(skip)]
+    by_task: OnceLock<HashMap<TaskId, Vec<usize>>>,
+}
+
+impl ReviewLog {
+    #[must_use]
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &ReviewRecord> {
+        self.records.iter()
+    }
+    #[must_use]
+    pub fn by_reviewer<'s>(
+        &'s self,
+        reviewer: &'s str,
+    ) -> impl DoubleEndedIterator<Item = &'s ReviewRecord> {
+        self.records
+            .iter()
+            .filter(move |r| r.reviewer.as_deref() == Some(reviewer))
+    }
+    #[must_use]
+    pub fn for_task(&self, id: TaskId) -> impl DoubleEndedIterator<Item = &ReviewRecord> + Clone {
+        let indices = self
+            .by_task
+            .get_or_init(|| {
+                let mut by_task = HashMap::<_, Vec<_>>::new();
+                for (i, record) in self.records.iter().enumerate() {
+                    by_task.entry(record.task_id).or_default().push(i);
+                }
+                by_task
+            })
+            .get(&id)
+            .map_or(&[][..], Vec::as_slice);
+        indices.iter().map(|&i| &self.records[i])
+    }
+    /// Manual due changes, oldest first.
+    #[must_use]
+    pub fn overrides(&self) -> &[DueOverride] {
+        &self.overrides
+    }
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    pub(crate) fn last_review(&self, id: TaskId) -> Option<SystemTime> {
+        self.for_task(id)
+            .rfind(|r| r.source.changes_schedule())
+            .map(|r| r.reviewed_at)
+    }
+    pub(crate) fn push(&mut self, record: ReviewRecord) {
+        if let Some(by_task) = self.by_task.get_mut() {
+            by_task
+                .entry(record.task_id)
+                .or_default()
+                .push(self.records.len());
+        }
+        self.records.push(record);
+    }
+    pub(crate) fn last_mut(&mut self) -> Option<&mut ReviewRecord> {
+        self.records.last_mut()
+    }
+    /// Add the record keeping the log chronological; usually that's pushing.
+    pub(crate) fn insert(&mut self, record: ReviewRecord) {
+        if self
+            .records
+            .last()
+            .is_none_or(|last| last.reviewed_at <= record.reviewed_at)
+        {
+            self.push(record);
+            return;
+        }
+        self.by_task.take();
+        let index = self
+            .records
+            .partition_point(|r| r.reviewed_at <= record.reviewed_at);
+        self.records.insert(index, record);
+    }
+
+    pub(crate) fn push_override(&mut self, record: DueOverride) {
+        self.overrides.push(record);
+    }
+
+    /// Overrides of the task are dropped.
+    pub(crate) fn remove_task(&mut self, id: TaskId) -> Vec<ReviewRecord> {
+        self.overrides.retain(|r| r.task_id != id);
+        self.by_task.take();
+        self.records.extract_if(.., |r| r.task_id == id).collect()
+    }
+    pub(crate) fn take_overrides(&mut self, id: TaskId) -> Vec<DueOverride> {
+        self.overrides.extract_if(.., |r| r.task_id == id).collect()
+    }
+    /// Insert records keeping chronological order.
+    pub(crate) fn extend(&mut self, records: impl IntoIterator<Item = ReviewRecord>) {
+        self.by_task.take();
+        self.records.extend(records);
+        self.records.sort_by_key(|r| r.reviewed_at);
+    }
+    /// Insert overrides keeping chronological order.
+    pub(crate) fn extend_overrides(&mut self, records: impl IntoIterator<Item = DueOverride>) {
+        self.overrides.extend(records);
+        self.overrides.sort_by_key(|r| r.at);
+    }
+}
+
+impl<'a, T: Task<'a>> Facade<'a, T> {
+    pub fn review_log(&self) -> &ReviewLog {
+        &self.review_log
+    }
+    /// All reviews of one task, oldest first.
+    pub fn task_history(&self, id: TaskId) -> Vec<ReviewRecord> {
+        self.review_log.for_task(id).cloned().collect()
+    }
+}
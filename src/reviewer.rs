@@ -0,0 +1,78 @@
+use std::io;
+
+use s_text_input_f::Response;
+use ssr_core::task::Task;
+
+use crate::{Facade, ReviewRecord, TaskWrapper};
+
+impl<'a, T: Task<'a>> Facade<'a, T> {
+    /// Who is reviewing on this device now, `None` if unknown. Not
+    /// serialized, so a shared device doesn't attribute reviews to whoever
+    /// used it last.
+    pub fn reviewer(&self) -> Option<&str> {
+        self.reviewer.as_deref()
+    }
+    /// Label following reviews with `reviewer` in the review log.
+    ///
+    /// A labeled review also updates a separate copy of the task's state for
+    /// that reviewer, see [`Facade::set_schedule_reviewer`].
+    pub fn set_reviewer(&mut self, reviewer: Option<String>) {
+        self.reviewer = reviewer;
+    }
+
+    pub fn schedule_reviewer(&self) -> Option<&str> {
+        self.schedule_reviewer.as_deref()
+    }
+    /// Schedule tasks by the reviews of one reviewer only, `None` schedules
+    /// by all reviews.
+    ///
+    /// A reviewer's schedule of a task starts with their first review of it
+    /// labeled with [`Facade::set_reviewer`]; tasks they haven't reviewed
+    /// that way are new to them. Their copy of the task is completed with the
+    /// same answers after the shared one, so task types that learn shared
+    /// state on completion see those reviews twice. Daily limits and
+    /// statistics still count every review.
+    pub fn set_schedule_reviewer(&mut self, reviewer: Option<String>) {
+        self.schedule_reviewer = reviewer;
+        self.reload_all_tasks_timings();
+    }
+
+    pub fn stats_reviewer(&self) -> Option<&str> {
+        self.stats_reviewer.as_deref()
+    }
+    /// Restrict statistics to reviews of one reviewer, `None` includes all
+    /// reviews.
+    pub fn set_stats_reviewer(&mut self, reviewer: Option<String>) {
+        self.stats_reviewer = reviewer;
+    }
+    /// Log records statistics are computed from.
+    pub(crate) fn stats_records(&self) -> impl DoubleEndedIterator<Item = &ReviewRecord> {
+        self.review_log.iter().filter(|r| {
+            self.stats_reviewer
+                .as_ref()
+                .is_none_or(|reviewer| r.reviewer.as_ref() == Some(reviewer))
+        })
+    }
+}
+
+impl<'a, T: Task<'a>> TaskWrapper<T> {
+    /// Complete `reviewer`'s copy of the task with `responses` given to the
+    /// shared one. A copy asking for more answers than were given is left as
+    /// it was.
+    pub(crate) fn replay_review(
+        &mut self,
+        reviewer: &str,
+        state: &mut T::SharedState,
+        desired_retention: f64,
+        responses: Vec<Response>,
+    ) {
+        let task = (self.reviewer_tasks.entry(reviewer.to_owned()))
+            .or_insert_with(|| T::new(self.task.get_blocks()));
+        let mut responses = responses.into_iter();
+        let _unchanged = task.complete(state, desired_retention, &mut |_| {
+            responses
+                .next()
+                .ok_or_else(|| io::ErrorKind::UnexpectedEof.into())
+        });
+    }
+}
@@ -0,0 +1,169 @@
+use std::{fmt, io, time::SystemTime};
+
+use serde::{Deserialize, Serialize};
+use ssr_core::{task::Task, tasks_facade};
+
+use crate::{
+    Event, Facade, Outcome,
+    time::{local_day, next_local_midnight},
+};
+
+/// Reviews of the current local day.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DailyCounters {
+    pub reviews: usize,
+    /// First reviews of new tasks.
+    pub new: usize,
+}
+
+/// `complete_task` was called after [`Facade::reviews_per_day`] reviews
+/// today. Returned wrapped in [`io::ErrorKind::Other`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DailyLimitReached {
+    pub limit: usize,
+    /// Local midnight, when the limit resets.
+    pub resets_at: SystemTime,
+}
+
+impl fmt::Display for DailyLimitReached {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "daily limit of {} reviews reached", self.limit)
+    }
+}
+impl std::error::Error for DailyLimitReached {}
+
+/// Per-day bookkeeping, advanced by [`Facade::rollover_if_needed`].
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default)]
+pub(crate) struct DayState {
+    /// Local day number since the Unix epoch.
+    day: Option<i64>,
+    counters: DailyCounters,
+    /// Consecutive days with reviews, ending with the previous day.
+    streak: u32,
+    longest_streak: u32,
+}
+
+impl<'a, T: Task<'a>> Facade<'a, T> {
+    /// Apply per-day state transitions if a new local day started since the
+    /// last call: reset [`DailyCounters`], advance the review streak, unbury
+    /// tasks whose burial expired and emit [`Event::DayRolledOver`]. Meant to
+    /// be called periodically; `complete_task` calls it too. Returns whether
+    /// the day rolled over.
+    pub fn rollover_if_needed(&mut self) -> bool {
+        let now = self.observe_now();
+        let today = local_day(now, self.day_offset_secs());
+        let Some(previous_day) = self.day.day else {
+            self.day.day = Some(today);
+            return false;
+        };
+        if today <= previous_day {
+            return false;
+        }
+
+        let previous = self.day.counters;
+        self.day.streak = if previous.reviews == 0 || today - previous_day > 1 {
+            0
+        } else {
+            self.day.streak + 1
+        };
+        self.day.longest_streak = self.day.longest_streak.max(self.day.streak);
+        self.day.counters = DailyCounters::default();
+        self.day.day = Some(today);
+        // New tasks held back by the daily limit become eligible.
+        self.recall_check = None;
+
+        let epoch = self.next_epoch();
+        let mut unburied = 0;
+        for task in self
+            .tasks_pool
+            .iter_mut()
+            .chain(self.tasks_to_recall.iter_mut())
+            .filter(|task| task.buried_until.is_some_and(|until| until <= now))
+        {
+            task.buried_until = None;
+            task.modified = epoch;
+            unburied += 1;
+        }
+        self.find_tasks_to_recall();
+        self.events
+            .push(Event::DayRolledOver { previous, unburied });
+        true
+    }
+
+    pub fn today_counters(&self) -> DailyCounters {
+        self.day.counters
+    }
+    /// Consecutive local days with at least one review, including today if
+    /// something was reviewed already.
+    pub fn streak(&self) -> u32 {
+        self.day.streak + u32::from(self.day.counters.reviews > 0)
+    }
+    pub fn longest_streak(&self) -> u32 {
+        self.day.longest_streak.max(self.streak())
+    }
+
+    /// `None` means unlimited.
+    pub fn new_per_day(&self) -> Option<usize> {
+        self.new_per_day
+    }
+    /// Limit how many never-reviewed tasks are handed out per local day.
+    /// New tasks over the limit stay in the pool until the next day.
+    pub fn set_new_per_day(&mut self, limit: Option<usize>) {
+        self.new_per_day = limit;
+        self.reload_all_tasks_timings();
+    }
+    /// `None` means unlimited.
+    pub fn reviews_per_day(&self) -> Option<usize> {
+        self.reviews_per_day
+    }
+    /// Limit reviews per local day, so an overdue backlog isn't served in
+    /// one sitting. Once reached, `complete_task` fails with
+    /// [`DailyLimitReached`] until the day rolls over.
+    pub fn set_reviews_per_day(&mut self, limit: Option<usize>) {
+        self.reviews_per_day = limit;
+    }
+    /// Whether today's reviews reached [`Facade::reviews_per_day`], so
+    /// `complete_task` would fail with [`DailyLimitReached`].
+    pub fn daily_limit_reached(&self) -> bool {
+        let today = local_day(self.now(), self.day_offset_secs());
+        // Counters of a previous day are reset on the next rollover.
+        let counted_today = self.day.day.is_none_or(|day| day >= today);
+        self.reviews_per_day
+            .is_some_and(|limit| counted_today && self.day.counters.reviews >= limit)
+    }
+    pub(crate) fn check_daily_limit(&self) -> Result<(), tasks_facade::Error> {
+        match self.reviews_per_day {
+            Some(limit) if self.daily_limit_reached() => {
+                let resets_at = next_local_midnight(self.now(), self.day_offset_secs());
+                Err(io::Error::other(DailyLimitReached { limit, resets_at }).into())
+            }
+            _ => Ok(()),
+        }
+    }
+    /// New tasks that may still be handed out today, counting the ones
+    /// already waiting in the due queue. `None` if unlimited.
+    pub(crate) fn new_tasks_left_today(&self) -> Option<usize> {
+        let limit = self.new_per_day?;
+        let queued = self
+            .tasks_to_recall
+            .iter()
+            .filter(|t| self.review_log.for_task(t.id).next().is_none())
+            .count();
+        Some(limit.saturating_sub(self.day.counters.new + queued))
+    }
+
+    /// Reviews answered on an earlier day, e.g. offline, don't count toward
+    /// today's limits.
+    pub(crate) fn count_review(&mut self, outcome: Outcome, reviewed_at: SystemTime) {
+        self.rollover_if_needed();
+        if (self.day.day)
+            .is_some_and(|today| local_day(reviewed_at, self.day_offset_secs()) < today)
+        {
+            return;
+        }
+        self.day.counters.reviews += 1;
+        if outcome == Outcome::New {
+            self.day.counters.new += 1;
+        }
+    }
+}
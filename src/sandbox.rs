@@ -0,0 +1,80 @@
+use std::ops::{Deref, DerefMut};
+
+use ssr_core::task::Task;
+
+use crate::Facade;
+
+/// Guard that lets a session run against the facade while keeping a copy of
+/// the state it had when the sandbox was opened.
+///
+/// Dropping the guard without calling [`Sandbox::commit`] restores that copy,
+/// so demo modes and deck previews can't alter real progress.
+pub struct Sandbox<'f, 'a, T>
+where
+    T: Task<'a> + Clone,
+    T::SharedState: Clone,
+{
+    facade: &'f mut Facade<'a, T>,
+    snapshot: Option<Facade<'a, T>>,
+}
+
+impl<'a, T> Facade<'a, T>
+where
+    T: Task<'a> + Clone,
+    T::SharedState: Clone,
+{
+    /// Start a sandboxed session. All changes made through the returned guard
+    /// are discarded unless it is committed.
+    pub fn sandbox(&mut self) -> Sandbox<'_, 'a, T> {
+        Sandbox {
+            snapshot: Some(self.clone()),
+            facade: self,
+        }
+    }
+}
+
+impl<'a, T> Sandbox<'_, 'a, T>
+where
+    T: Task<'a> + Clone,
+    T::SharedState: Clone,
+{
+    /// Keep all changes made during the session.
+    pub fn commit(mut self) {
+        self.snapshot = None;
+    }
+    /// Throw away all changes made during the session.
+    pub fn discard(self) {}
+}
+
+impl<'a, T> Drop for Sandbox<'_, 'a, T>
+where
+    T: Task<'a> + Clone,
+    T::SharedState: Clone,
+{
+    fn drop(&mut self) {
+        if let Some(snapshot) = self.snapshot.take() {
+            *self.facade = snapshot;
+        }
+    }
+}
+
+impl<'a, T> Deref for Sandbox<'_, 'a, T>
+where
+    T: Task<'a> + Clone,
+    T::SharedState: Clone,
+{
+    type Target = Facade<'a, T>;
+
+    fn deref(&self) -> &Self::Target {
+        self.facade
+    }
+}
+impl<'a, T> DerefMut for Sandbox<'_, 'a, T>
+where
+    T: Task<'a> + Clone,
+    T::SharedState: Clone,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.facade
+    }
+}
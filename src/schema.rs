@@ -0,0 +1,15 @@
+use ssr_core::task::Task;
+
+use crate::Facade;
+
+const SCHEMA: &str = include_str!("../schema/facade.schema.json");
+
+impl<'a, T: Task<'a>> Facade<'a, T> {
+    /// JSON Schema (draft 2020-12) of the facade as serialized to JSON, for
+    /// third-party tools reading and writing facade files. Fields added in
+    /// newer versions are optional, so files of older versions stay valid.
+    #[must_use]
+    pub fn schema() -> &'static str {
+        SCHEMA
+    }
+}
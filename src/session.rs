@@ -0,0 +1,291 @@
+use std::{collections::HashSet, fmt, time::Duration};
+
+use ssr_core::{
+    task::Task,
+    tasks_facade::{self, TaskId},
+};
+
+use crate::{Facade, IdleTracker, Outcome, idle::ActiveTimer};
+
+/// Number of latest logged reviews used to estimate review duration before the
+/// session measured its own.
+const ESTIMATE_WINDOW: usize = 100;
+
+#[derive(Debug, Clone, Default)]
+pub struct SessionConfig {
+    /// Time budget, e.g. 15 minutes, excluding idle time. Stop handing out
+    /// tasks once another review is not expected to fit; what is left is
+    /// reported as [`SessionSummary::due_remaining`].
+    pub time_limit: Option<Duration>,
+    pub max_reviews: Option<usize>,
+    /// Maximum first reviews of new tasks. Once reached, only already
+    /// reviewed tasks are handed out.
+    pub max_new: Option<usize>,
+    pub new_task_mix: NewTaskMix,
+}
+
+/// How a [`Session`] interleaves new tasks with reviews of already reviewed
+/// ones while both are due.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum NewTaskMix {
+    /// By priority, new and reviewed tasks alike.
+    #[default]
+    ByPriority,
+    NewFirst,
+    ReviewsFirst,
+    /// One new task after every `reviews_per_new` reviews.
+    Ratio {
+        reviews_per_new: usize,
+    },
+}
+
+/// Tasks [`Facade::complete_preferred_task`] may take.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TaskKind {
+    Any,
+    New,
+    Reviewed,
+}
+
+/// Series of reviews driven through [`Session::complete_task`].
+pub struct Session<'f, 'a, T: Task<'a>> {
+    facade: &'f mut Facade<'a, T>,
+    config: SessionConfig,
+    /// Excludes time the user was idle, see [`Facade::idle_tracker`].
+    started: ActiveTimer,
+    reviews: usize,
+    review_time: Duration,
+    new: usize,
+    lapses: usize,
+    /// Reviews since the last new task, see [`NewTaskMix::Ratio`].
+    reviews_since_new: usize,
+    seen: HashSet<TaskId>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SessionProgress {
+    pub reviews: usize,
+    pub elapsed: Duration,
+    /// `None` if the session has no time limit.
+    pub time_left: Option<Duration>,
+    /// Tasks due now that the session hasn't reviewed yet.
+    pub due_remaining: usize,
+    /// Expected time to review everything that is due now.
+    pub estimated_remaining: Duration,
+}
+
+/// Outcome of a session, see [`Session::finish`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct SessionSummary {
+    pub reviews: usize,
+    /// Distinct tasks reviewed.
+    pub tasks_seen: usize,
+    /// First reviews of new tasks.
+    pub new: usize,
+    pub lapses: usize,
+    /// Part of the reviews of already reviewed tasks that lapsed, `None` if
+    /// there were none.
+    pub again_rate: Option<f64>,
+    /// Time the session was active, excluding idle time.
+    pub elapsed: Duration,
+    /// Part of `elapsed` spent answering tasks.
+    pub review_time: Duration,
+    /// Tasks still due when the summary was taken, e.g. left over by the
+    /// time limit.
+    pub due_remaining: usize,
+}
+
+#[derive(Debug)]
+pub enum SessionError {
+    /// Time limit doesn't allow another review.
+    TimeLimitReached,
+    /// [`SessionConfig::max_reviews`] reviews were made.
+    ReviewLimitReached,
+    /// Only new tasks are due and [`SessionConfig::max_new`] is reached.
+    NewLimitReached,
+    /// Every deck with due tasks reached its daily quota. Only returned by
+    /// [`crate::CollectionSession`].
+    QuotaReached,
+    Facade(tasks_facade::Error),
+}
+
+impl fmt::Display for SessionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TimeLimitReached => write!(f, "session time limit reached"),
+            Self::ReviewLimitReached => write!(f, "session review limit reached"),
+            Self::NewLimitReached => write!(f, "session limit of new tasks reached"),
+            Self::QuotaReached => write!(f, "daily quota of every deck reached"),
+            Self::Facade(err) => err.fmt(f),
+        }
+    }
+}
+impl std::error::Error for SessionError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::TimeLimitReached
+            | Self::ReviewLimitReached
+            | Self::NewLimitReached
+            | Self::QuotaReached => None,
+            Self::Facade(err) => Some(err),
+        }
+    }
+}
+impl From<tasks_facade::Error> for SessionError {
+    fn from(value: tasks_facade::Error) -> Self {
+        Self::Facade(value)
+    }
+}
+
+impl<'a, T: Task<'a>> Facade<'a, T> {
+    pub fn start_session(&mut self, config: SessionConfig) -> Session<'_, 'a, T> {
+        Session {
+            started: self.idle.start_timer(),
+            facade: self,
+            config,
+            reviews: 0,
+            review_time: Duration::ZERO,
+            new: 0,
+            lapses: 0,
+            reviews_since_new: 0,
+            seen: HashSet::new(),
+        }
+    }
+}
+
+impl<'a, T: Task<'a>> Session<'_, 'a, T> {
+    /// # Errors
+    /// If a limit is reached or the facade fails to complete a task.
+    pub fn complete_task(
+        &mut self,
+        interaction: &mut impl FnMut(
+            TaskId,
+            s_text_input_f::Blocks,
+        ) -> std::io::Result<s_text_input_f::Response>,
+    ) -> Result<SessionProgress, SessionError> {
+        if self
+            .config
+            .max_reviews
+            .is_some_and(|max| self.reviews >= max)
+        {
+            return Err(SessionError::ReviewLimitReached);
+        }
+        if let Some(time_limit) = self.config.time_limit
+            && self.started.elapsed() + self.estimated_review_duration() > time_limit
+        {
+            return Err(SessionError::TimeLimitReached);
+        }
+
+        let timer = self.facade.idle.start_timer();
+        if !self
+            .facade
+            .complete_preferred_task(self.preference(), interaction)?
+        {
+            return Err(SessionError::NewLimitReached);
+        }
+        self.review_time += timer.elapsed();
+        self.reviews += 1;
+        if let Some(record) = self.facade.review_log.iter().next_back() {
+            match record.outcome {
+                Outcome::New => {
+                    self.new += 1;
+                    self.reviews_since_new = 0;
+                }
+                Outcome::Lapse => {
+                    self.lapses += 1;
+                    self.reviews_since_new += 1;
+                }
+                Outcome::Pass => self.reviews_since_new += 1,
+            }
+            self.seen.insert(record.task_id);
+        }
+
+        Ok(self.progress())
+    }
+
+    /// Kinds of tasks to take next, in order of preference.
+    fn preference(&self) -> &'static [TaskKind] {
+        if self.config.max_new.is_some_and(|max| self.new >= max) {
+            return &[TaskKind::Reviewed];
+        }
+        let new_first = match self.config.new_task_mix {
+            NewTaskMix::ByPriority => return &[TaskKind::Any],
+            NewTaskMix::NewFirst => true,
+            NewTaskMix::ReviewsFirst => false,
+            NewTaskMix::Ratio { reviews_per_new } => self.reviews_since_new >= reviews_per_new,
+        };
+        if new_first {
+            &[TaskKind::New, TaskKind::Reviewed]
+        } else {
+            &[TaskKind::Reviewed, TaskKind::New]
+        }
+    }
+
+    #[must_use]
+    pub fn summary(&self) -> SessionSummary {
+        let graded = self.reviews - self.new;
+        #[allow(clippy::cast_precision_loss)] // review counts are far below 2^52
+        let again_rate = (graded > 0).then(|| self.lapses as f64 / graded as f64);
+        SessionSummary {
+            reviews: self.reviews,
+            tasks_seen: self.seen.len(),
+            new: self.new,
+            lapses: self.lapses,
+            again_rate,
+            elapsed: self.started.elapsed(),
+            review_time: self.review_time,
+            due_remaining: self.facade.tasks_to_recall.len(),
+        }
+    }
+    /// End the session, whether it ran out of tasks, hit a limit or was
+    /// abandoned.
+    #[must_use]
+    pub fn finish(self) -> SessionSummary {
+        self.summary()
+    }
+
+    /// Report idle and resume through it to pause the session's timers.
+    #[must_use]
+    pub fn idle_tracker(&self) -> IdleTracker {
+        self.facade.idle_tracker()
+    }
+
+    #[must_use]
+    pub fn progress(&self) -> SessionProgress {
+        let elapsed = self.started.elapsed();
+        let due_remaining = self.facade.tasks_to_recall.len();
+        SessionProgress {
+            reviews: self.reviews,
+            elapsed,
+            time_left: self
+                .config
+                .time_limit
+                .map(|limit| limit.saturating_sub(elapsed)),
+            due_remaining,
+            estimated_remaining: self.estimated_review_duration()
+                * u32::try_from(due_remaining).unwrap_or(u32::MAX),
+        }
+    }
+
+    /// Mean duration of reviews in this session, falling back to the latest
+    /// logged reviews.
+    fn estimated_review_duration(&self) -> Duration {
+        if let Ok(reviews @ 1..) = u32::try_from(self.reviews) {
+            return self.review_time / reviews;
+        }
+        let (count, total) = self
+            .facade
+            .review_log
+            .iter()
+            .rev()
+            .take(ESTIMATE_WINDOW)
+            .fold((0, Duration::ZERO), |(count, total), r| {
+                (count + 1, total + r.duration)
+            });
+        if count == 0 {
+            Duration::ZERO
+        } else {
+            total / count
+        }
+    }
+}
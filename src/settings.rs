@@ -0,0 +1,99 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use ssr_core::task::Task;
+
+use crate::Facade;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum SettingValue {
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Text(String),
+}
+
+impl From<bool> for SettingValue {
+    fn from(value: bool) -> Self {
+        Self::Bool(value)
+    }
+}
+impl From<i64> for SettingValue {
+    fn from(value: i64) -> Self {
+        Self::Int(value)
+    }
+}
+impl From<f64> for SettingValue {
+    fn from(value: f64) -> Self {
+        Self::Float(value)
+    }
+}
+impl From<String> for SettingValue {
+    fn from(value: String) -> Self {
+        Self::Text(value)
+    }
+}
+impl From<&str> for SettingValue {
+    fn from(value: &str) -> Self {
+        Self::Text(value.to_owned())
+    }
+}
+
+/// Front-end preferences stored with the facade. Keys are namespaced with
+/// dots, like `ui.theme`; the facade itself never reads them.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct Settings {
+    values: BTreeMap<String, SettingValue>,
+}
+
+impl Settings {
+    #[must_use]
+    pub fn get(&self, key: &str) -> Option<&SettingValue> {
+        self.values.get(key)
+    }
+    /// Returns the previous value.
+    pub fn set(
+        &mut self,
+        key: impl Into<String>,
+        value: impl Into<SettingValue>,
+    ) -> Option<SettingValue> {
+        self.values.insert(key.into(), value.into())
+    }
+    pub fn remove(&mut self, key: &str) -> Option<SettingValue> {
+        self.values.remove(key)
+    }
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &SettingValue)> {
+        self.values.iter().map(|(key, value)| (key.as_str(), value))
+    }
+    /// Settings under `namespace`, with keys relative to it.
+    pub fn namespace<'s>(
+        &'s self,
+        namespace: &'s str,
+    ) -> impl Iterator<Item = (&'s str, &'s SettingValue)> {
+        self.iter().filter_map(move |(key, value)| {
+            let rest = key.strip_prefix(namespace)?.strip_prefix('.')?;
+            Some((rest, value))
+        })
+    }
+    /// Remove all settings under `namespace`. Returns their number.
+    pub fn clear_namespace(&mut self, namespace: &str) -> usize {
+        let before = self.values.len();
+        self.values.retain(|key, _| {
+            !key.strip_prefix(namespace)
+                .is_some_and(|rest| rest.starts_with('.'))
+        });
+        before - self.values.len()
+    }
+}
+
+impl<'a, T: Task<'a>> Facade<'a, T> {
+    /// Read-only view, change settings through [`Facade::settings_mut`].
+    pub fn settings(&self) -> &Settings {
+        &self.settings
+    }
+    /// Changes apply at once and are saved with the facade. Settings never
+    /// affect scheduling, so tasks don't need to be re-timed.
+    pub fn settings_mut(&mut self) -> &mut Settings {
+        &mut self.settings
+    }
+}
@@ -0,0 +1,147 @@
+use std::{
+    collections::{HashMap, HashSet},
+    time::{Duration, SystemTime},
+};
+
+use ssr_core::{
+    task::Task,
+    tasks_facade::{TaskId, TasksFacade},
+};
+
+use crate::{
+    Facade, LocalDate, OverrideKind, Query,
+    time::{SECS_PER_DAY, from_local_secs, local_day, local_secs},
+};
+
+/// Direction and size of [`Facade::shift_due`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DueShift {
+    Earlier(Duration),
+    Later(Duration),
+}
+
+impl DueShift {
+    /// `None` if the shifted time isn't representable.
+    fn apply(self, due: SystemTime) -> Option<SystemTime> {
+        match self {
+            Self::Earlier(by) => Some(due.checked_sub(by).unwrap_or(SystemTime::UNIX_EPOCH)),
+            Self::Later(by) => due.checked_add(by),
+        }
+    }
+}
+
+impl<'a, T: Task<'a>> Facade<'a, T> {
+    /// Move due times of all tasks matching `query` by `shift`, e.g. to pull
+    /// everything tagged `exam::june` earlier. Recorded like
+    /// [`Facade::set_due`]; frozen tasks and tasks that would be due past the
+    /// representable time range are skipped. Returns the number of shifted
+    /// tasks.
+    ///
+    /// With `balance_days`, each task may move up to that many days further,
+    /// to the local day with the fewest tasks due, but never before today.
+    pub fn shift_due(
+        &mut self,
+        query: &Query,
+        shift: DueShift,
+        balance_days: Option<u32>,
+    ) -> usize {
+        let ids = self.query(query);
+        self.shift_due_of(ids, shift, balance_days)
+    }
+
+    /// Push due times of all tasks matching `pred` `by` later, e.g. to thin
+    /// out a heavy day. Like [`Facade::shift_due`], which selects tasks by
+    /// tag or state.
+    pub fn postpone_all(
+        &mut self,
+        mut pred: impl FnMut(&T, TaskId) -> bool,
+        by: Duration,
+    ) -> usize {
+        let ids = self
+            .iter()
+            .filter(|&(task, id)| pred(task, id))
+            .map(|(_, id)| id)
+            .collect();
+        self.shift_due_of(ids, DueShift::Later(by), None)
+    }
+    /// Pull due times of all tasks matching `pred` `by` earlier, e.g. to
+    /// review before a trip. Like [`Facade::shift_due`], which selects tasks
+    /// by tag or state.
+    pub fn advance_all(&mut self, mut pred: impl FnMut(&T, TaskId) -> bool, by: Duration) -> usize {
+        let ids = self
+            .iter()
+            .filter(|&(task, id)| pred(task, id))
+            .map(|(_, id)| id)
+            .collect();
+        self.shift_due_of(ids, DueShift::Earlier(by), None)
+    }
+
+    fn shift_due_of(
+        &mut self,
+        ids: Vec<TaskId>,
+        shift: DueShift,
+        balance_days: Option<u32>,
+    ) -> usize {
+        let now = self.now();
+        let today = local_day(now, self.day_offset_secs());
+        let selected: HashSet<_> = ids.iter().copied().collect();
+        let mut load = HashMap::<i64, usize>::new();
+        if balance_days.is_some() {
+            for task in self.tasks_pool.iter().chain(self.tasks_to_recall.iter()) {
+                if !selected.contains(&task.id)
+                    && let Some(available_at) = self.available_at(task)
+                {
+                    *load
+                        .entry(local_day(available_at, self.day_offset_secs()).max(today))
+                        .or_default() += 1;
+                }
+            }
+        }
+
+        let day_offset_secs = self.day_offset_secs();
+        let mut shifted = 0;
+        for id in ids {
+            let found = self.override_due(id, OverrideKind::Shift, |due| {
+                let due = shift.apply(due)?;
+                let Some(window) = balance_days.map(i64::from) else {
+                    return Some(due);
+                };
+                let day = local_day(due, day_offset_secs);
+                let best = (day - window..=day + window)
+                    .filter(|&candidate| candidate >= today)
+                    .min_by_key(|&candidate| {
+                        let load = load.get(&candidate).copied().unwrap_or(0);
+                        (load, (candidate - day).abs(), candidate)
+                    })
+                    .unwrap_or(day.max(today));
+                *load.entry(best).or_default() += 1;
+                Some(from_local_secs(
+                    local_secs(due, day_offset_secs).saturating_add((best - day) * SECS_PER_DAY),
+                    day_offset_secs,
+                ))
+            });
+            if found {
+                shifted += 1;
+            }
+        }
+        self.reload_all_tasks_timings();
+        shifted
+    }
+
+    /// Move due times of every task `offset` later, e.g. before a week away,
+    /// so the reviews don't pile up on return. Recorded like
+    /// [`Facade::shift_due`]; frozen tasks are skipped. Returns the number of
+    /// shifted tasks.
+    pub fn shift_schedule(&mut self, offset: Duration) -> usize {
+        self.shift_due(&Query::All, DueShift::Later(offset), None)
+    }
+    /// [`Facade::shift_schedule`] by the time left until the start of `date`,
+    /// so reviewing resumes on that day where it stopped now. Nothing moves
+    /// if `date` has already started.
+    pub fn pause_until(&mut self, date: LocalDate) -> usize {
+        match self.start_of_local_day(date).duration_since(self.now()) {
+            Ok(offset) if !offset.is_zero() => self.shift_schedule(offset),
+            _ => 0,
+        }
+    }
+}
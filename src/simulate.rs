@@ -0,0 +1,219 @@
+use std::{
+    cmp::Reverse,
+    collections::BinaryHeap,
+    time::{Duration, SystemTime},
+};
+
+use rand::{Rng, SeedableRng, rngs::StdRng};
+use s_text_input_f::{Block, BlocksWithAnswer, ParagraphItem};
+use ssr_core::task::Task;
+
+use crate::{
+    Facade, LocalDate, TaskWrapper,
+    time::{SECS_PER_DAY, from_local_secs, local_day},
+};
+
+/// Parameters of [`Facade::simulate`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SimulationConfig {
+    pub days: usize,
+    /// Placeholder tasks added on every simulated day.
+    pub new_per_day: usize,
+    /// Same seed, same facade and same grade model give the same result.
+    pub seed: u64,
+}
+
+/// What the grade model of [`Facade::simulate`] knows about a review.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SimulatedReview {
+    /// Reviews of the task so far, real and simulated.
+    pub reviews: usize,
+    /// Interval the task was reviewed after, `None` for its first review.
+    pub interval: Option<Duration>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SimulatedDay {
+    pub date: LocalDate,
+    pub reviews: usize,
+    /// First reviews of new tasks.
+    pub new: usize,
+    pub passed: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Simulation {
+    pub days: Vec<SimulatedDay>,
+    /// Share of passed reviews that weren't first reviews, `None` if there
+    /// were none.
+    pub retention: Option<f64>,
+}
+
+struct Simulated<T> {
+    task: TaskWrapper<T>,
+    reviews: usize,
+    last_review: Option<SystemTime>,
+    /// Interval assigned by the last review.
+    last_interval: Option<Duration>,
+}
+
+fn placeholder() -> BlocksWithAnswer {
+    BlocksWithAnswer {
+        blocks: vec![Block::Paragraph(vec![
+            ParagraphItem::Text("simulated".to_owned()),
+            ParagraphItem::Placeholder,
+        ])],
+        answer: vec![vec!["simulated".to_owned()]],
+    }
+}
+
+impl<'a, T: Task<'a> + Clone> Facade<'a, T>
+where
+    T::SharedState: Clone,
+{
+    /// Project daily review counts and retention by replaying the scheduler
+    /// on a copy of the facade; the facade itself isn't changed.
+    /// `grade_model` returns the probability that a review passes; values
+    /// outside `0..=1` are clamped and NaN counts as `0`. Passing reviews
+    /// answer with the task's answer, failing ones with empty text.
+    ///
+    /// Tasks timestamp reviews with the real clock, so intervals are taken
+    /// relative to the moment of simulation and applied to the simulated
+    /// day, and schedulers see no time passing between simulated reviews.
+    /// Suspension, burial and freezing are ignored.
+    pub fn simulate(
+        &self,
+        config: SimulationConfig,
+        mut grade_model: impl FnMut(SimulatedReview) -> f64,
+    ) -> Simulation {
+        let mut facade = self.clone();
+        let mut rng = StdRng::seed_from_u64(config.seed);
+        let real_now = SystemTime::now();
+        let today = local_day(self.now(), self.day_offset_secs());
+
+        let mut queue = BinaryHeap::new();
+        let mut tasks = Vec::new();
+        for task in (facade.tasks_pool.drain(..)).chain(facade.tasks_to_recall.drain(..)) {
+            if task.suspended {
+                continue;
+            }
+            let due = self.next_repetition(&task);
+            queue.push(Reverse((due, tasks.len())));
+            tasks.push(Simulated {
+                reviews: self.review_log.for_task(task.id).count(),
+                last_review: self.review_log.last_review(task.id),
+                last_interval: self.last_interval(task.id),
+                task,
+            });
+        }
+
+        let mut days = Vec::with_capacity(config.days);
+        let (mut graded, mut passed_graded) = (0_usize, 0_usize);
+        for day in (today..).take(config.days) {
+            let day_start = from_local_secs(day * SECS_PER_DAY, self.day_offset_secs());
+            let day_end = from_local_secs((day + 1) * SECS_PER_DAY, self.day_offset_secs());
+            for _ in 0..config.new_per_day {
+                let task = TaskWrapper::new(T::new(placeholder()), rand::random());
+                queue.push(Reverse((day_start, tasks.len())));
+                tasks.push(Simulated {
+                    task,
+                    reviews: 0,
+                    last_review: None,
+                    last_interval: None,
+                });
+            }
+            let mut summary = SimulatedDay {
+                date: LocalDate::from_day(day),
+                reviews: 0,
+                new: 0,
+                passed: 0,
+            };
+            while let Some(&Reverse((due, i))) = queue.peek()
+                && due < day_end
+            {
+                queue.pop();
+                let reviewed_at = due.max(day_start);
+                let simulated = &mut tasks[i];
+                let review = SimulatedReview {
+                    reviews: simulated.reviews,
+                    interval: simulated
+                        .last_review
+                        .map(|last| reviewed_at.duration_since(last).unwrap_or_default()),
+                };
+                let probability = grade_model(review);
+                let probability = if probability.is_nan() {
+                    0.
+                } else {
+                    probability.clamp(0., 1.)
+                };
+                let pass = rng.gen_bool(probability);
+                let Some(next_due) = facade.replay_review(
+                    &mut simulated.task,
+                    pass,
+                    reviewed_at,
+                    simulated.last_interval,
+                    real_now,
+                ) else {
+                    continue;
+                };
+
+                summary.reviews += 1;
+                if simulated.reviews == 0 {
+                    summary.new += 1;
+                } else {
+                    graded += 1;
+                    passed_graded += usize::from(pass);
+                }
+                summary.passed += usize::from(pass);
+                simulated.reviews += 1;
+                simulated.last_review = Some(reviewed_at);
+                simulated.last_interval = next_due.duration_since(reviewed_at).ok();
+                // Never review twice within a simulated day.
+                queue.push(Reverse((next_due.max(day_end), i)));
+            }
+            days.push(summary);
+        }
+
+        #[allow(clippy::cast_precision_loss)]
+        let retention = (graded > 0).then(|| passed_graded as f64 / graded as f64);
+        Simulation { days, retention }
+    }
+
+    /// Complete a simulated review, returning the next due time relative to
+    /// `reviewed_at`.
+    fn replay_review(
+        &mut self,
+        task: &mut TaskWrapper<T>,
+        pass: bool,
+        reviewed_at: SystemTime,
+        last_interval: Option<Duration>,
+        real_now: SystemTime,
+    ) -> Option<SystemTime> {
+        let answer = task.task.get_blocks().answer;
+        let group = self.parameter_groups.group_of(task).map(str::to_owned);
+        let state = self
+            .parameter_groups
+            .state_mut(&mut self.state, group.as_deref());
+        task.task
+            .complete(state, self.desired_retention, &mut |_| {
+                Ok(if pass {
+                    answer.clone()
+                } else {
+                    answer
+                        .iter()
+                        .map(|item| vec![String::new(); item.len()])
+                        .collect()
+                })
+            })
+            .ok()?;
+        let interval = task
+            .task
+            .next_repetition(
+                self.parameter_groups.state_for(&self.state, task),
+                self.desired_retention,
+            )
+            .duration_since(real_now)
+            .unwrap_or_default();
+        Some(self.adjust_next_due(task, reviewed_at, reviewed_at + interval, last_interval))
+    }
+}
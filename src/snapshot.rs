@@ -0,0 +1,46 @@
+use std::time::SystemTime;
+
+use ssr_core::{task::Task, tasks_facade::TaskId};
+
+use crate::{Facade, content::content_key};
+
+/// Owned copy of what a task list shows, taken by [`Facade::iter_owned`].
+///
+/// Holds no borrow of the facade, so it can outlive mutations; use
+/// [`Facade::is_current`] to find out whether the task changed since.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TaskSnapshot {
+    pub id: TaskId,
+    pub next_repetition: SystemTime,
+    /// `None` while suspended.
+    pub available_at: Option<SystemTime>,
+    /// Changes whenever task content changes.
+    pub content_digest: u64,
+    /// Facade epoch of the last change of the task.
+    pub modified: u64,
+}
+
+impl<'a, T: Task<'a>> Facade<'a, T> {
+    /// Snapshots of all tasks.
+    pub fn iter_owned(&self) -> std::vec::IntoIter<TaskSnapshot> {
+        self.tasks_pool
+            .iter()
+            .chain(self.tasks_to_recall.iter())
+            .map(|task| TaskSnapshot {
+                id: task.id,
+                next_repetition: self.next_repetition(task),
+                available_at: self.available_at(task),
+                content_digest: content_key(&task.task.get_blocks()),
+                modified: task.modified,
+            })
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+    /// Whether the task still exists and wasn't changed after `snapshot` was
+    /// taken. Due times may still shift with facade-wide scheduling changes
+    /// like optimization.
+    pub fn is_current(&self, snapshot: &TaskSnapshot) -> bool {
+        self.find_task(snapshot.id)
+            .is_some_and(|task| task.modified == snapshot.modified)
+    }
+}
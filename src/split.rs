@@ -0,0 +1,125 @@
+use ssr_core::{
+    task::Task,
+    tasks_facade::{TaskId, TasksFacade},
+};
+
+use crate::{Facade, MediaStore, ReviewRecord, TaskWrapper};
+
+impl<'a, T: Task<'a>> Facade<'a, T> {
+    /// Move tasks matching `pred`, with their ids, scheduling state and review
+    /// history, into a new facade named `name`. The new facade shares this
+    /// one's scheduler parameters, scheduling, daily limit, optimization,
+    /// tagging and trash settings, and templates. Front-end settings and the
+    /// reviewer aren't copied.
+    #[must_use = "split tasks are removed from this facade"]
+    pub fn split_off(
+        &mut self,
+        name: impl Into<String>,
+        mut pred: impl FnMut(&T, TaskId) -> bool,
+    ) -> Facade<'a, T>
+    where
+        T::SharedState: Clone,
+    {
+        let mut split = Facade::new(name.into(), self.desired_retention);
+        split.state = self.state.clone();
+        split.parameter_groups = self.parameter_groups.clone();
+        split.min_interval = self.min_interval;
+        split.max_interval_growth = self.max_interval_growth;
+        split.clock = self.clock;
+        split.deterministic_seed = self.deterministic_seed;
+        split.utc_offset_secs = self.utc_offset_secs;
+        split.rollover_hour = self.rollover_hour;
+        split.day_granularity = self.day_granularity;
+        split.id_generator = self.id_generator;
+        split.content_limit = self.content_limit;
+        split.prerequisite_maturity = self.prerequisite_maturity;
+        split.tag_prerequisites.clone_from(&self.tag_prerequisites);
+        split.sibling_spacing = self.sibling_spacing;
+        split
+            .tag_interval_multipliers
+            .clone_from(&self.tag_interval_multipliers);
+        split.learn_ahead = self.learn_ahead;
+        split.review_ahead_cap = self.review_ahead_cap;
+        split.new_per_day = self.new_per_day;
+        split.reviews_per_day = self.reviews_per_day;
+        split.min_reviews_for_optimize = self.min_reviews_for_optimize;
+        split
+            .optimization_excluded_tags
+            .clone_from(&self.optimization_excluded_tags);
+        split.auto_tag_policy.clone_from(&self.auto_tag_policy);
+        split.templates.clone_from(&self.templates);
+        split.trash_retention = self.trash_retention;
+
+        let mut ids = self
+            .tasks_pool
+            .iter()
+            .chain(self.tasks_to_recall.iter())
+            .filter(|task| pred(&task.task, task.id))
+            .map(|task| (task.position, task.id))
+            .collect::<Vec<_>>();
+        ids.sort_unstable();
+        for (_, id) in ids {
+            if let Some((task, history, media)) = self.detach_task(id) {
+                split.attach_task(task, history, media);
+            }
+        }
+        split.find_tasks_to_recall();
+        split
+    }
+
+    /// Move the task with its id, scheduling state and review history to
+    /// `dest`, after its existing tasks. Returns whether the task was moved;
+    /// nothing is changed if it doesn't exist here or its id is taken in
+    /// `dest`.
+    pub fn transfer(&mut self, id: TaskId, dest: &mut Facade<'a, T>) -> bool {
+        if dest.find_task(id).is_some() {
+            return false;
+        }
+        let Some((task, history, media)) = self.detach_task(id) else {
+            return false;
+        };
+        dest.attach_task(task, history, media);
+        true
+    }
+
+    /// Take the task out with its history and a copy of the media it refers
+    /// to, recording it as deleted for [`Facade::delta_since`].
+    pub(crate) fn detach_task(
+        &mut self,
+        id: TaskId,
+    ) -> Option<(TaskWrapper<T>, Vec<ReviewRecord>, MediaStore)> {
+        let task = self.take_task(id)?;
+        let history = self.review_log.remove_task(id);
+        let media = self.media.referenced_by(&task.task.get_blocks());
+        let epoch = self.next_epoch();
+        self.deleted.push((id, epoch));
+        Some((task, history, media))
+    }
+    /// Drop sibling links and prerequisites pointing at `id`.
+    pub(crate) fn unlink_task(&mut self, id: TaskId) {
+        let epoch = self.next_epoch();
+        for task in self
+            .tasks_pool
+            .iter_mut()
+            .chain(self.tasks_to_recall.iter_mut())
+        {
+            let unlinked = task.sibling.take_if(|sibling| *sibling == id).is_some();
+            if task.prerequisites.remove(&id) || unlinked {
+                task.modified = epoch;
+            }
+        }
+    }
+    /// Add a task detached from another facade after all existing ones.
+    pub(crate) fn attach_task(
+        &mut self,
+        mut task: TaskWrapper<T>,
+        history: Vec<ReviewRecord>,
+        media: MediaStore,
+    ) {
+        self.media.extend(media);
+        self.deleted.retain(|(id, _)| *id != task.id);
+        task.position = self.take_next_position();
+        self.place_task(task);
+        self.review_log.extend(history);
+    }
+}
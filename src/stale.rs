@@ -0,0 +1,71 @@
+use std::time::SystemTime;
+
+use ssr_core::{task::Task, tasks_facade::TaskId};
+
+use crate::{Facade, Outcome};
+
+/// Lapses a task needs in the window to count as "keeps being failed".
+const MIN_LAPSES: usize = 2;
+
+/// Candidate for rewriting, see [`Facade::stale_tasks`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct StaleTask {
+    pub id: TaskId,
+    /// When the content was created or last replaced, or the first review
+    /// for tasks saved before that was recorded.
+    pub content_since: Option<SystemTime>,
+    /// Scheduled reviews since `reviewed_since`, excluding first reviews.
+    pub graded_reviews: usize,
+    pub lapses: usize,
+    pub lapse_rate: f64,
+}
+
+impl<'a, T: Task<'a>> Facade<'a, T> {
+    /// Tasks whose content hasn't changed since before `edited_before` but
+    /// lapsed repeatedly in reviews since `reviewed_since`: old content that
+    /// keeps failing is often worth rewriting. Most lapses first.
+    pub fn stale_tasks(
+        &self,
+        edited_before: SystemTime,
+        reviewed_since: SystemTime,
+    ) -> Vec<StaleTask> {
+        let mut stale = self
+            .tasks_pool
+            .iter()
+            .chain(self.tasks_to_recall.iter())
+            .filter_map(|task| {
+                let history = self.review_log.for_task(task.id);
+                let content_since = task
+                    .content_changed_at
+                    .or_else(|| history.clone().next().map(|r| r.reviewed_at));
+                if content_since.is_none_or(|since| since >= edited_before) {
+                    return None;
+                }
+                let (graded_reviews, lapses) = history
+                    .filter(|r| r.reviewed_at >= reviewed_since && r.source.is_scheduled())
+                    .filter(|r| r.outcome != Outcome::New)
+                    .fold((0, 0), |(graded, lapses), r| {
+                        (
+                            graded + 1,
+                            lapses + usize::from(r.outcome == Outcome::Lapse),
+                        )
+                    });
+                #[allow(clippy::cast_precision_loss)] // review counts are far below 2^52
+                let lapse_rate = lapses as f64 / graded_reviews.max(1) as f64;
+                (lapses >= MIN_LAPSES).then_some(StaleTask {
+                    id: task.id,
+                    content_since,
+                    graded_reviews,
+                    lapses,
+                    lapse_rate,
+                })
+            })
+            .collect::<Vec<_>>();
+        stale.sort_by(|a, b| {
+            b.lapses
+                .cmp(&a.lapses)
+                .then(b.lapse_rate.total_cmp(&a.lapse_rate))
+        });
+        stale
+    }
+}
@@ -0,0 +1,265 @@
+use std::time::{Duration, SystemTime};
+
+use ssr_core::{task::Task, tasks_facade::TaskId};
+
+use crate::{
+    Facade, Outcome, ReviewRecord,
+    time::{local_hour, local_weekday},
+};
+
+/// Reviewed tasks with at least this interval count as
+/// [`FacadeStats::review`], shorter ones as [`FacadeStats::learning`].
+pub const MATURE_INTERVAL: Duration = Duration::from_hours(21 * 24);
+
+/// Overview of a facade, see [`Facade::stats`]. `new`, `learning`, `review`
+/// and `suspended` partition all tasks.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FacadeStats {
+    pub total: usize,
+    /// Never reviewed.
+    pub new: usize,
+    pub learning: usize,
+    pub review: usize,
+    pub suspended: usize,
+    /// Tasks buried at the moment, not suspended.
+    pub buried: usize,
+    /// Tasks `complete_task` can hand out now.
+    pub due: usize,
+    /// Mean current interval of reviewed tasks, `None` if none was reviewed.
+    pub average_interval: Option<Duration>,
+    /// Graded reviews in the retention window.
+    pub graded_reviews: usize,
+    /// Share of graded reviews in the window that passed, `None` without
+    /// graded reviews.
+    pub retention: Option<f64>,
+}
+
+/// Predicted recall probabilities in `lower..upper` against how often those
+/// reviews actually passed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CalibrationBin {
+    pub lower: f64,
+    pub upper: f64,
+    pub count: usize,
+    /// `None` if the bin is empty.
+    pub mean_predicted: Option<f64>,
+    /// `None` if the bin is empty.
+    pub actual: Option<f64>,
+}
+
+/// Aggregated reviews that fall into one time bucket.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PerformanceBucket {
+    pub reviews: usize,
+    /// Reviews that were pass or lapse, i.e. not the first review of a task.
+    pub graded: usize,
+    pub passed: usize,
+    pub total_duration: Duration,
+}
+
+impl PerformanceBucket {
+    /// `None` if there are no graded reviews.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn pass_rate(&self) -> Option<f64> {
+        (self.graded > 0).then(|| self.passed as f64 / self.graded as f64)
+    }
+    /// `None` if there are no reviews.
+    #[must_use]
+    pub fn mean_duration(&self) -> Option<Duration> {
+        let reviews = u32::try_from(self.reviews).ok().filter(|&x| x > 0)?;
+        Some(self.total_duration / reviews)
+    }
+}
+
+impl<'a, T: Task<'a>> Facade<'a, T> {
+    /// Task counts by state, mean interval and measured retention over the
+    /// last `retention_days` days. Retention follows
+    /// [`Facade::set_stats_reviewer`].
+    #[allow(clippy::cast_precision_loss)]
+    pub fn stats(&self, retention_days: u32) -> FacadeStats {
+        let now = self.now();
+        let immature = self.immature_prerequisites();
+        let mut stats = FacadeStats {
+            total: self.tasks_pool.len() + self.tasks_to_recall.len(),
+            new: 0,
+            learning: 0,
+            review: 0,
+            suspended: 0,
+            buried: 0,
+            due: 0,
+            average_interval: None,
+            graded_reviews: 0,
+            retention: None,
+        };
+        let mut total_interval = Duration::ZERO;
+        for task in self.tasks_pool.iter().chain(self.tasks_to_recall.iter()) {
+            if task.suspended {
+                stats.suspended += 1;
+                continue;
+            }
+            if task.buried_until.is_some_and(|until| until > now) {
+                stats.buried += 1;
+            }
+            let gated = immature
+                .as_ref()
+                .is_some_and(|immature| immature.blocks(task, &self.tag_prerequisites));
+            if !gated && self.is_due(task, now) {
+                stats.due += 1;
+            }
+            match self
+                .review_log
+                .for_task(task.id)
+                .rfind(|r| r.source.changes_schedule())
+            {
+                None => stats.new += 1,
+                Some(record) => {
+                    let interval = record.next_interval();
+                    total_interval += interval;
+                    if interval >= MATURE_INTERVAL {
+                        stats.review += 1;
+                    } else {
+                        stats.learning += 1;
+                    }
+                }
+            }
+        }
+        if let Ok(reviewed @ 1..) = u32::try_from(stats.learning + stats.review) {
+            stats.average_interval = Some(total_interval / reviewed);
+        }
+
+        let since = now
+            .checked_sub(Duration::from_hours(24) * retention_days)
+            .unwrap_or(SystemTime::UNIX_EPOCH);
+        let mut passed = 0;
+        for record in self
+            .stats_records()
+            .rev()
+            .take_while(|r| r.reviewed_at >= since)
+            .filter(|r| r.source.changes_schedule())
+        {
+            match record.outcome {
+                Outcome::New => {}
+                Outcome::Pass => {
+                    stats.graded_reviews += 1;
+                    passed += 1;
+                }
+                Outcome::Lapse => stats.graded_reviews += 1,
+            }
+        }
+        stats.retention =
+            (stats.graded_reviews > 0).then(|| f64::from(passed) / stats.graded_reviews as f64);
+        stats
+    }
+
+    /// Pass rate and answer speed by local hour of day.
+    pub fn performance_by_hour(&self) -> [PerformanceBucket; 24] {
+        self.performance_by(|record| local_hour(record.reviewed_at, self.utc_offset_secs))
+    }
+    /// Pass rate and answer speed by local day of week, starting from Monday.
+    pub fn performance_by_weekday(&self) -> [PerformanceBucket; 7] {
+        self.performance_by(|record| local_weekday(record.reviewed_at, self.day_offset_secs()))
+    }
+    fn performance_by<const N: usize>(
+        &self,
+        bucket: impl Fn(&ReviewRecord) -> usize,
+    ) -> [PerformanceBucket; N] {
+        let mut buckets = [PerformanceBucket::default(); N];
+        for record in self.stats_records() {
+            let bucket = &mut buckets[bucket(record)];
+            bucket.reviews += 1;
+            bucket.total_duration += record.duration;
+            match record.outcome {
+                Outcome::New => {}
+                Outcome::Pass => {
+                    bucket.graded += 1;
+                    bucket.passed += 1;
+                }
+                Outcome::Lapse => bucket.graded += 1,
+            }
+        }
+        buckets
+    }
+
+    /// Calibration curve of the scheduler over the review log, split into
+    /// `bins` equal-width probability bins. A well calibrated model has
+    /// `mean_predicted` close to `actual` in every bin.
+    ///
+    /// First reviews of tasks carry no prediction and are ignored, as are
+    /// reviews whose [`crate::ReviewSource`] isn't scheduled. Empty if `bins`
+    /// is zero.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn calibration(&self, bins: usize) -> Vec<CalibrationBin> {
+        if bins == 0 {
+            return Vec::new();
+        }
+        let mut sums = vec![(0usize, 0f64, 0usize); bins];
+        for record in self.stats_records().filter(|r| r.source.is_scheduled()) {
+            let Some(predicted) = record.predicted_recall() else {
+                continue;
+            };
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            let index = ((predicted * bins as f64) as usize).min(bins.saturating_sub(1));
+            let (count, predicted_sum, passed) = &mut sums[index];
+            *count += 1;
+            *predicted_sum += predicted;
+            *passed += usize::from(record.outcome == Outcome::Pass);
+        }
+
+        sums.into_iter()
+            .enumerate()
+            .map(|(i, (count, predicted_sum, passed))| CalibrationBin {
+                lower: i as f64 / bins as f64,
+                upper: (i + 1) as f64 / bins as f64,
+                count,
+                mean_predicted: (count > 0).then(|| predicted_sum / count as f64),
+                actual: (count > 0).then(|| passed as f64 / count as f64),
+            })
+            .collect()
+    }
+
+    /// Predicted recall probability of a task from now until `now + horizon`,
+    /// sampled every `step`.
+    ///
+    /// Assumes exponential forgetting since the last review that reaches the
+    /// desired retention exactly at the due time. Reviews that don't change
+    /// the schedule, like those of frozen tasks, don't restart the curve. `None` if there
+    /// is no such task or it was never reviewed on schedule.
+    pub fn forgetting_curve(
+        &self,
+        id: TaskId,
+        horizon: Duration,
+        step: Duration,
+    ) -> Option<Vec<(SystemTime, f64)>> {
+        let task = self.find_task(id)?;
+        let last_review = self.review_log.last_review(id)?;
+        let interval = self
+            .next_repetition(task)
+            .duration_since(last_review)
+            .unwrap_or_default()
+            .as_secs_f64();
+        let now = self.now();
+        let recall_at = |time: SystemTime| {
+            if interval == 0. {
+                return self.desired_retention;
+            }
+            let elapsed = time
+                .duration_since(last_review)
+                .unwrap_or_default()
+                .as_secs_f64();
+            self.desired_retention.powf(elapsed / interval)
+        };
+
+        let mut curve = Vec::new();
+        let mut offset = Duration::ZERO;
+        while offset <= horizon {
+            let time = now + offset;
+            curve.push((time, recall_at(time)));
+            if step.is_zero() {
+                break;
+            }
+            offset += step;
+        }
+        Some(curve)
+    }
+}
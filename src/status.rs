@@ -0,0 +1,209 @@
+use std::time::SystemTime;
+
+use ssr_core::{task::Task, tasks_facade::TasksFacade};
+
+use crate::{
+    Facade, LocalDate,
+    time::{local_day, next_local_midnight},
+};
+
+/// Why nothing can be reviewed right now and what comes next, for screens
+/// like "Done for now — 14 more tonight at 19:00".
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NoTaskContext {
+    /// `None` if no task will become due.
+    pub next_due: Option<SystemTime>,
+    /// Tasks becoming due before local midnight.
+    pub due_later_today: usize,
+    pub suspended: usize,
+    /// Tasks buried at the moment.
+    pub buried: usize,
+}
+
+/// What the home screen should show, see [`Facade::status`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FacadeStatus {
+    /// There are no tasks at all.
+    Empty,
+    /// No task is due; `until` is when the next one becomes due, `None` if
+    /// none will.
+    AllDone {
+        until: Option<SystemTime>,
+    },
+    Due(usize),
+}
+
+/// How [`Facade::due_forecast_with`] treats a kind of hidden tasks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ForecastInclusion {
+    Excluded,
+    /// Counted with regular tasks.
+    Included,
+    /// Counted in a separate column of [`ForecastDay`].
+    Separate,
+}
+
+/// Which hidden tasks [`Facade::due_forecast_with`] counts. Suspended tasks
+/// are counted on the day they would be due if unsuspended, buried tasks on
+/// the day their burial ends or later.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ForecastOptions {
+    pub suspended: ForecastInclusion,
+    pub buried: ForecastInclusion,
+}
+
+impl Default for ForecastOptions {
+    /// What [`Facade::due_forecast`] counts.
+    fn default() -> Self {
+        Self {
+            suspended: ForecastInclusion::Excluded,
+            buried: ForecastInclusion::Included,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ForecastDay {
+    pub date: LocalDate,
+    pub due: usize,
+    /// Only counted with [`ForecastInclusion::Separate`].
+    pub suspended: usize,
+    /// Only counted with [`ForecastInclusion::Separate`].
+    pub buried: usize,
+}
+
+impl<'a, T: Task<'a>> Facade<'a, T> {
+    pub fn is_empty(&self) -> bool {
+        self.tasks_total() == 0
+    }
+    /// Whether `complete_task` would find a task right now, here or in a
+    /// sub-deck.
+    pub fn has_due(&self) -> bool {
+        (!self.daily_limit_reached() && self.until_next_repetition().is_some_and(|x| x.is_zero()))
+            || self.children.values().any(Self::has_due)
+    }
+    /// Status of this facade's own tasks, sub-decks have their own.
+    pub fn status(&self) -> FacadeStatus {
+        if self.tasks_pool.is_empty() && self.tasks_to_recall.is_empty() {
+            return FacadeStatus::Empty;
+        }
+        let now = self.now();
+        let immature = self.immature_prerequisites();
+        // Counted like `find_tasks_to_recall` would move them to the queue.
+        let mut new_left = self.new_tasks_left_today();
+        let due = self.tasks_to_recall.len()
+            + self
+                .tasks_pool
+                .iter()
+                .filter(|task| {
+                    !immature
+                        .as_ref()
+                        .is_some_and(|immature| immature.blocks(task, &self.tag_prerequisites))
+                        && self.is_due(task, now + self.learn_ahead)
+                })
+                .filter(|task| match new_left {
+                    Some(left) if self.review_log.for_task(task.id).next().is_none() => {
+                        new_left = Some(left.saturating_sub(1));
+                        left > 0
+                    }
+                    _ => true,
+                })
+                .count();
+        if due > 0 {
+            FacadeStatus::Due(due)
+        } else {
+            FacadeStatus::AllDone {
+                until: self.until_next_repetition().map(|x| now + x),
+            }
+        }
+    }
+
+    /// Context for the [`ssr_core::tasks_facade::Error::NoTaskToComplete`] and
+    /// [`ssr_core::tasks_facade::Error::NoTask`] outcomes of `complete_task`.
+    pub fn no_task_context(&self) -> NoTaskContext {
+        let now = self.now();
+        let midnight = next_local_midnight(now, self.day_offset_secs());
+        let mut context = NoTaskContext {
+            next_due: None,
+            due_later_today: 0,
+            suspended: 0,
+            buried: 0,
+        };
+        for task in self.tasks_pool.iter().chain(self.tasks_to_recall.iter()) {
+            if task.suspended {
+                context.suspended += 1;
+                continue;
+            }
+            if task.buried_until.is_some_and(|until| until > now) {
+                context.buried += 1;
+            }
+            let Some(available_at) = self.available_at(task) else {
+                continue;
+            };
+            if available_at > now && available_at < midnight {
+                context.due_later_today += 1;
+            }
+            context.next_due = Some(
+                context
+                    .next_due
+                    .map_or(available_at, |x| x.min(available_at)),
+            );
+        }
+        context
+    }
+
+    /// Number of tasks becoming available on each of `days` local days
+    /// starting today; overdue tasks count for today.
+    pub fn due_forecast(&self, days: usize) -> Vec<(LocalDate, usize)> {
+        self.due_forecast_with(days, ForecastOptions::default())
+            .into_iter()
+            .map(|day| (day.date, day.due))
+            .collect()
+    }
+    /// [`Facade::due_forecast`] with control over suspended and buried tasks,
+    /// e.g. to plan for material that will be unsuspended before an exam.
+    pub fn due_forecast_with(&self, days: usize, options: ForecastOptions) -> Vec<ForecastDay> {
+        let now = self.now();
+        let today = local_day(now, self.day_offset_secs());
+        let mut forecast = (today..)
+            .take(days)
+            .map(|day| ForecastDay {
+                date: LocalDate::from_day(day),
+                due: 0,
+                suspended: 0,
+                buried: 0,
+            })
+            .collect::<Vec<_>>();
+        for task in self.tasks_pool.iter().chain(self.tasks_to_recall.iter()) {
+            let (inclusion, available_at) = if task.suspended {
+                let due = [task.buried_until, task.frozen_until]
+                    .into_iter()
+                    .flatten()
+                    .fold(self.next_repetition(task), SystemTime::max);
+                (Some(options.suspended), Some(due))
+            } else if task.buried_until.is_some_and(|until| until > now) {
+                (Some(options.buried), self.available_at(task))
+            } else {
+                (None, self.available_at(task))
+            };
+            let Some(available_at) = available_at else {
+                continue;
+            };
+            let Ok(day) =
+                usize::try_from((local_day(available_at, self.day_offset_secs()) - today).max(0))
+            else {
+                continue;
+            };
+            let Some(day) = forecast.get_mut(day) else {
+                continue;
+            };
+            match inclusion {
+                None | Some(ForecastInclusion::Included) => day.due += 1,
+                Some(ForecastInclusion::Excluded) => {}
+                Some(ForecastInclusion::Separate) if task.suspended => day.suspended += 1,
+                Some(ForecastInclusion::Separate) => day.buried += 1,
+            }
+        }
+        forecast
+    }
+}
@@ -0,0 +1,94 @@
+use std::{
+    cmp::Reverse,
+    io::{self, Write},
+    time::{Duration, SystemTime},
+};
+
+use ssr_core::task::Task;
+
+use crate::Facade;
+
+/// Backup storage for platforms without filesystem paths, e.g. a mobile
+/// key-value store. Backups are identified by their creation time.
+pub trait BackupStore {
+    /// # Errors
+    /// If the store can't be read.
+    fn list(&self) -> io::Result<Vec<SystemTime>>;
+    /// # Errors
+    /// If the snapshot can't be stored.
+    fn put(&mut self, created: SystemTime, bytes: Vec<u8>) -> io::Result<()>;
+    /// # Errors
+    /// If the snapshot doesn't exist or can't be read.
+    fn get(&self, created: SystemTime) -> io::Result<Vec<u8>>;
+    /// # Errors
+    /// If the snapshot can't be removed.
+    fn remove(&mut self, created: SystemTime) -> io::Result<()>;
+}
+
+impl<'a, T: Task<'a>> Facade<'a, T> {
+    /// Serialize the facade with `write` into memory and hand the complete
+    /// bytes to `store`, so a failed serialization never reaches storage.
+    /// # Errors
+    /// If `write` or `store` fails.
+    pub fn save_with(
+        &self,
+        write: impl FnOnce(&Self, &mut dyn Write) -> io::Result<()>,
+        store: impl FnOnce(Vec<u8>) -> io::Result<()>,
+    ) -> io::Result<()> {
+        let mut bytes = Vec::new();
+        write(self, &mut bytes)?;
+        store(bytes)
+    }
+    /// [`Facade::load`] from bytes returned by `load`, using `read` to
+    /// deserialize them.
+    /// # Errors
+    /// If `load` or `read` fails, or the bytes were written by a newer version
+    /// of the crate; the latter is [`io::ErrorKind::InvalidData`] wrapping
+    /// [`NewerFormatError`](crate::NewerFormatError).
+    pub fn load_with(
+        load: impl FnOnce() -> io::Result<Vec<u8>>,
+        read: impl FnOnce(&mut dyn io::Read) -> io::Result<Self>,
+    ) -> io::Result<Self> {
+        let bytes = load()?;
+        let (facade, _) = Self::load(bytes.as_slice(), read)?;
+        Ok(facade)
+    }
+
+    /// [`Facade::backup`] into `store`. Returns creation time of the new
+    /// backup. If a backup with the same creation time exists, the new one is
+    /// stored at the next free nanosecond instead of replacing it.
+    /// # Errors
+    /// If `write` or the store fails.
+    pub fn backup_with(
+        &self,
+        store: &mut impl BackupStore,
+        keep_n: usize,
+        write: impl FnOnce(&Self, &mut dyn Write) -> io::Result<()>,
+    ) -> io::Result<SystemTime> {
+        let existing = store.list()?;
+        let mut created = self.now();
+        while existing.contains(&created) {
+            created += Duration::from_nanos(1);
+        }
+        self.save_with(write, |bytes| store.put(created, bytes))?;
+        let mut backups = store.list()?;
+        backups.retain(|backup| *backup != created);
+        backups.sort_by_key(|created| Reverse(*created));
+        for old in backups.into_iter().skip(keep_n.saturating_sub(1)) {
+            store.remove(old)?;
+        }
+        Ok(created)
+    }
+    /// Load a backup written by [`Facade::backup_with`], see
+    /// [`Facade::load_with`].
+    /// # Errors
+    /// If the backup can't be read from `store`, `read` fails or the backup
+    /// was written by a newer version of the crate.
+    pub fn restore_backup_with(
+        store: &impl BackupStore,
+        created: SystemTime,
+        read: impl FnOnce(&mut dyn io::Read) -> io::Result<Self>,
+    ) -> io::Result<Self> {
+        Self::load_with(|| store.get(created), read)
+    }
+}
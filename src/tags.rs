@@ -0,0 +1,29 @@
+use std::collections::BTreeSet;
+
+use ssr_core::{task::Task, tasks_facade::TaskId};
+
+use crate::Facade;
+
+impl<'a, T: Task<'a>> Facade<'a, T> {
+    /// Returns whether the tag was added, i.e. task exists and didn't have it.
+    pub fn add_tag(&mut self, id: TaskId, tag: impl Into<String>) -> bool {
+        self.find_task_mut(id)
+            .is_some_and(|task| task.tags.insert(tag.into()))
+    }
+    /// Returns whether the tag was present.
+    pub fn remove_tag(&mut self, id: TaskId, tag: &str) -> bool {
+        self.find_task_mut(id)
+            .is_some_and(|task| task.tags.remove(tag))
+    }
+    /// Returns `None` if there is no such task.
+    pub fn tags(&self, id: TaskId) -> Option<&BTreeSet<String>> {
+        self.find_task(id).map(|task| &task.tags)
+    }
+    pub fn iter_by_tag<'t>(&'t self, tag: &'t str) -> impl Iterator<Item = (&'t T, TaskId)> {
+        self.tasks_pool
+            .iter()
+            .chain(self.tasks_to_recall.iter())
+            .filter(move |task| task.tags.contains(tag))
+            .map(|task| (&task.task, task.id))
+    }
+}
@@ -0,0 +1,108 @@
+use std::{collections::BTreeSet, time::SystemTime};
+
+use ssr_core::{task::Task, tasks_facade::TaskId};
+
+use crate::{Facade, Outcome, ReviewRecord, TaskWrapper};
+
+/// Read-only view of one task with everything the facade knows about it.
+pub struct TaskRef<'f, 'a, T: Task<'a>> {
+    facade: &'f Facade<'a, T>,
+    wrapper: &'f TaskWrapper<T>,
+}
+
+impl<'a, T: Task<'a>> Clone for TaskRef<'_, 'a, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<'a, T: Task<'a>> Copy for TaskRef<'_, 'a, T> {}
+
+impl<'f, 'a, T: Task<'a>> TaskRef<'f, 'a, T> {
+    #[must_use]
+    pub fn id(&self) -> TaskId {
+        self.wrapper.id
+    }
+    #[must_use]
+    pub fn task(&self) -> &'f T {
+        &self.wrapper.task
+    }
+    #[must_use]
+    pub fn blocks(&self) -> s_text_input_f::BlocksWithAnswer {
+        self.wrapper.task.get_blocks()
+    }
+
+    #[must_use]
+    pub fn next_repetition(&self) -> SystemTime {
+        self.facade.next_repetition(self.wrapper)
+    }
+    /// When the task will be handed out for review, `None` while suspended.
+    #[must_use]
+    pub fn available_at(&self) -> Option<SystemTime> {
+        self.facade.available_at(self.wrapper)
+    }
+    #[must_use]
+    pub fn is_due(&self) -> bool {
+        self.facade.is_due(self.wrapper, self.facade.now())
+    }
+
+    #[must_use]
+    pub fn tags(&self) -> &'f BTreeSet<String> {
+        &self.wrapper.tags
+    }
+    #[must_use]
+    pub fn is_suspended(&self) -> bool {
+        self.wrapper.suspended
+    }
+    #[must_use]
+    pub fn buried_until(&self) -> Option<SystemTime> {
+        self.wrapper.buried_until
+    }
+    #[must_use]
+    pub fn priority(&self) -> i32 {
+        self.wrapper.priority
+    }
+    /// Automatic boost from [`crate::PriorityRules`], added to `priority`.
+    #[must_use]
+    pub fn priority_boost(&self) -> i32 {
+        self.wrapper.priority_boost
+    }
+
+    /// Reviews of the task, oldest first.
+    #[must_use]
+    pub fn history(&self) -> impl DoubleEndedIterator<Item = &'f ReviewRecord> + use<'f, 'a, T> {
+        self.facade.review_log.for_task(self.wrapper.id)
+    }
+    #[must_use]
+    pub fn reviews(&self) -> usize {
+        self.history().count()
+    }
+    #[must_use]
+    pub fn lapses(&self) -> usize {
+        self.history()
+            .filter(|r| r.outcome == Outcome::Lapse)
+            .count()
+    }
+    #[must_use]
+    pub fn last_review(&self) -> Option<SystemTime> {
+        self.history().next_back().map(|r| r.reviewed_at)
+    }
+}
+
+impl<'a, T: Task<'a>> Facade<'a, T> {
+    pub fn get(&self, id: TaskId) -> Option<TaskRef<'_, 'a, T>> {
+        let wrapper = self.find_task(id)?;
+        Some(TaskRef {
+            facade: self,
+            wrapper,
+        })
+    }
+    pub fn iter_refs(&self) -> impl Iterator<Item = TaskRef<'_, 'a, T>> {
+        self.tasks_pool
+            .iter()
+            .chain(self.tasks_to_recall.iter())
+            .map(|wrapper| TaskRef {
+                facade: self,
+                wrapper,
+            })
+    }
+}
@@ -0,0 +1,99 @@
+use std::time::SystemTime;
+
+use ssr_core::{task::Task, tasks_facade::TaskId};
+
+use crate::{Facade, TaskWrapper};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TaskState {
+    Active,
+    Suspended,
+    /// Buried until some moment in the future.
+    Buried,
+    /// Removed, but still restorable, see [`Facade::restore`].
+    Trashed,
+}
+
+/// Which task states [`Facade::iter_with`] and [`Facade::count_with`]
+/// include.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct TaskStates {
+    pub active: bool,
+    pub suspended: bool,
+    pub buried: bool,
+    pub trashed: bool,
+}
+
+impl TaskStates {
+    /// Tasks the scheduler works with.
+    pub const ACTIVE: Self = Self {
+        active: true,
+        suspended: false,
+        buried: false,
+        trashed: false,
+    };
+    /// Everything a browser may show.
+    pub const ALL: Self = Self {
+        active: true,
+        suspended: true,
+        buried: true,
+        trashed: true,
+    };
+
+    #[must_use]
+    pub fn contains(self, state: TaskState) -> bool {
+        match state {
+            TaskState::Active => self.active,
+            TaskState::Suspended => self.suspended,
+            TaskState::Buried => self.buried,
+            TaskState::Trashed => self.trashed,
+        }
+    }
+}
+
+impl<T> TaskWrapper<T> {
+    /// State of a task that is not in the trash.
+    fn state(&self, now: SystemTime) -> TaskState {
+        if self.suspended {
+            TaskState::Suspended
+        } else if self.buried_until.is_some_and(|until| until > now) {
+            TaskState::Buried
+        } else {
+            TaskState::Active
+        }
+    }
+}
+
+impl<'a, T: Task<'a>> Facade<'a, T> {
+    /// `None` if there is no such task.
+    pub fn task_state(&self, id: TaskId) -> Option<TaskState> {
+        if let Some(task) = self.find_task(id) {
+            return Some(task.state(self.now()));
+        }
+        self.trash
+            .iter()
+            .any(|t| t.task().id == id)
+            .then_some(TaskState::Trashed)
+    }
+
+    /// Tasks in any of `states` with their state.
+    pub fn iter_with(&self, states: TaskStates) -> impl Iterator<Item = (&T, TaskId, TaskState)> {
+        let now = self.now();
+        let trashed = self
+            .trash
+            .iter()
+            .filter(move |_| states.trashed)
+            .map(|t| (t.task(), TaskState::Trashed));
+        self.tasks_pool
+            .iter()
+            .chain(self.tasks_to_recall.iter())
+            .map(move |task| (task, task.state(now)))
+            .chain(trashed)
+            .filter(move |(_, state)| states.contains(*state))
+            .map(|(task, state)| (&task.task, task.id, state))
+    }
+    pub fn count_with(&self, states: TaskStates) -> usize {
+        self.iter_with(states).count()
+    }
+}
@@ -0,0 +1,286 @@
+use std::{collections::BTreeMap, fmt};
+
+use s_text_input_f::{Block, BlocksWithAnswer, ParagraphItem};
+use serde::{Deserialize, Serialize};
+use ssr_core::{task::Task, tasks_facade::TaskId};
+
+use crate::{Facade, MediaStore, RejectReason, content::content_key};
+
+/// Values of template variables, by variable name.
+pub type TemplateRow = BTreeMap<String, String>;
+
+/// Task content with `{variable}` references, filled in from a
+/// [`TemplateRow`]. Write `{{` and `}}` for literal braces.
+///
+/// Variables are substituted in paragraph texts, choice items and answers.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Template {
+    pub blocks: Vec<Block>,
+    pub answer: Vec<Vec<String>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemplateError {
+    UnknownTemplate(String),
+    /// Row at `row` doesn't have a value for `variable`.
+    MissingVariable {
+        row: usize,
+        variable: String,
+    },
+    /// Brace without a pair, or a block type templates don't support.
+    Malformed,
+    Rejected {
+        row: usize,
+        reason: RejectReason,
+    },
+}
+
+impl fmt::Display for TemplateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownTemplate(name) => write!(f, "unknown template: {name}"),
+            Self::MissingVariable { row, variable } => {
+                write!(f, "row {row} has no value for `{variable}`")
+            }
+            Self::Malformed => write!(f, "malformed template"),
+            Self::Rejected { row, reason } => write!(f, "row {row}: {reason}"),
+        }
+    }
+}
+impl std::error::Error for TemplateError {}
+
+/// Link of a task to the template it was generated from.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Derivation {
+    template: String,
+    row: TemplateRow,
+}
+
+impl Derivation {
+    pub(crate) fn new(template: &str, row: TemplateRow) -> Self {
+        Self {
+            template: template.to_owned(),
+            row,
+        }
+    }
+}
+
+/// Substitution failure, before the row index is known.
+enum RenderError {
+    Missing(String),
+    Malformed,
+}
+
+fn render_text(text: &str, row: &TemplateRow) -> Result<String, RenderError> {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(i) = rest.find(['{', '}']) {
+        out.push_str(&rest[..i]);
+        let brace = rest.as_bytes()[i];
+        rest = &rest[i + 1..];
+        if let Some(after) = rest.strip_prefix(char::from(brace)) {
+            out.push(char::from(brace));
+            rest = after;
+        } else if brace == b'{' {
+            let end = rest.find('}').ok_or(RenderError::Malformed)?;
+            let variable = &rest[..end];
+            let value = row
+                .get(variable)
+                .ok_or_else(|| RenderError::Missing(variable.to_owned()))?;
+            out.push_str(value);
+            rest = &rest[end + 1..];
+        } else {
+            return Err(RenderError::Malformed);
+        }
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+fn render_all(items: &[String], row: &TemplateRow) -> Result<Vec<String>, RenderError> {
+    items.iter().map(|item| render_text(item, row)).collect()
+}
+
+impl Template {
+    fn render_with(&self, row: &TemplateRow) -> Result<BlocksWithAnswer, RenderError> {
+        let blocks = self
+            .blocks
+            .iter()
+            .map(|block| {
+                Ok(match block {
+                    Block::Order(items) => Block::Order(render_all(items, row)?),
+                    Block::AnyOf(items) => Block::AnyOf(render_all(items, row)?),
+                    Block::OneOf(items) => Block::OneOf(render_all(items, row)?),
+                    Block::Paragraph(items) => Block::Paragraph(
+                        items
+                            .iter()
+                            .map(|item| {
+                                Ok(match item {
+                                    ParagraphItem::Text(text) => {
+                                        ParagraphItem::Text(render_text(text, row)?)
+                                    }
+                                    ParagraphItem::Placeholder => ParagraphItem::Placeholder,
+                                })
+                            })
+                            .collect::<Result<_, _>>()?,
+                    ),
+                    _ => return Err(RenderError::Malformed),
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        let answer = self
+            .answer
+            .iter()
+            .map(|item| render_all(item, row))
+            .collect::<Result<_, _>>()?;
+        Ok(BlocksWithAnswer { blocks, answer })
+    }
+
+    /// Task content for `row`. Errors report the row as 0.
+    ///
+    /// # Errors
+    /// If the row misses a variable or the template is malformed.
+    pub fn render(&self, row: &TemplateRow) -> Result<BlocksWithAnswer, TemplateError> {
+        self.render_with(row).map_err(|err| match err {
+            RenderError::Missing(variable) => TemplateError::MissingVariable { row: 0, variable },
+            RenderError::Malformed => TemplateError::Malformed,
+        })
+    }
+}
+
+impl<'a, T: Task<'a>> Facade<'a, T> {
+    /// Rendered rows after applying the content limit. Text moved out of
+    /// line goes to `media`, so nothing is stored if some row fails.
+    fn render_rows<'r>(
+        &self,
+        template: &Template,
+        rows: impl IntoIterator<Item = &'r TemplateRow>,
+        media: &mut MediaStore,
+    ) -> Result<Vec<BlocksWithAnswer>, TemplateError> {
+        rows.into_iter()
+            .enumerate()
+            .map(|(i, row)| {
+                let content = template.render_with(row).map_err(|err| match err {
+                    RenderError::Missing(variable) => {
+                        TemplateError::MissingVariable { row: i, variable }
+                    }
+                    RenderError::Malformed => TemplateError::Malformed,
+                })?;
+                match self.content_limit {
+                    Some(limit) => limit.apply(content, media),
+                    None => Ok(content),
+                }
+                .map_err(|reason| TemplateError::Rejected { row: i, reason })
+            })
+            .collect()
+    }
+
+    pub fn template(&self, name: &str) -> Option<&Template> {
+        self.templates.get(name)
+    }
+    pub fn templates(&self) -> impl Iterator<Item = (&str, &Template)> {
+        self.templates
+            .iter()
+            .map(|(name, template)| (name.as_str(), template))
+    }
+    /// Add a template, or replace it and regenerate every task created from
+    /// it. Returns the number of regenerated tasks.
+    ///
+    /// Tasks are regenerated like with [`Facade::edit_task`]; tasks whose
+    /// content didn't change are kept as is.
+    ///
+    /// # Errors
+    /// If the template can't be rendered for some derived task; nothing is
+    /// changed then.
+    pub fn set_template(
+        &mut self,
+        name: impl Into<String>,
+        template: Template,
+    ) -> Result<usize, TemplateError> {
+        let name = name.into();
+        let derived = self
+            .tasks_pool
+            .iter()
+            .chain(self.tasks_to_recall.iter())
+            .filter_map(|task| {
+                let derivation = task.derivation.as_ref()?;
+                (derivation.template == name).then_some((task.id, &derivation.row))
+            })
+            .collect::<Vec<_>>();
+        let mut media = MediaStore::default();
+        let contents =
+            self.render_rows(&template, derived.iter().map(|(_, row)| *row), &mut media)?;
+        self.media.extend(media);
+        let ids = derived.into_iter().map(|(id, _)| id).collect::<Vec<_>>();
+        self.templates.insert(name, template);
+
+        let mut regenerated = 0;
+        for (id, content) in ids.into_iter().zip(contents) {
+            let unchanged = self
+                .find_task(id)
+                .is_some_and(|task| content_key(&task.task.get_blocks()) == content_key(&content));
+            if unchanged {
+                continue;
+            }
+            if self.replace_content(id, content) {
+                regenerated += 1;
+            }
+        }
+        if regenerated > 0 {
+            self.reload_all_tasks_timings();
+        }
+        Ok(regenerated)
+    }
+    /// Forget the template. Tasks created from it stay, but are no longer
+    /// regenerated.
+    pub fn remove_template(&mut self, name: &str) -> Option<Template> {
+        let template = self.templates.remove(name)?;
+        for task in self
+            .tasks_pool
+            .iter_mut()
+            .chain(self.tasks_to_recall.iter_mut())
+        {
+            if task
+                .derivation
+                .as_ref()
+                .is_some_and(|derivation| derivation.template == name)
+            {
+                task.derivation = None;
+            }
+        }
+        Some(template)
+    }
+    /// Create a task for every row from the stored template `name`. Returns
+    /// ids of created tasks in row order.
+    ///
+    /// # Errors
+    /// If any row can't be rendered; no task is created then. Use
+    /// [`crate::Importer::create_from_template`] to import the valid rows
+    /// only.
+    pub fn create_from_template(
+        &mut self,
+        name: &str,
+        rows: impl IntoIterator<Item = TemplateRow>,
+    ) -> Result<Vec<TaskId>, TemplateError> {
+        let template = self
+            .templates
+            .get(name)
+            .ok_or_else(|| TemplateError::UnknownTemplate(name.to_owned()))?;
+        let rows = rows.into_iter().collect::<Vec<_>>();
+        let mut media = MediaStore::default();
+        let contents = self.render_rows(template, &rows, &mut media)?;
+        self.media.extend(media);
+        let mut ids = Vec::with_capacity(rows.len());
+        for (row, content) in rows.into_iter().zip(contents) {
+            let wrapper = self.push_new_task(T::new(content));
+            wrapper.derivation = Some(Derivation::new(name, row));
+            ids.push(wrapper.id);
+        }
+        Ok(ids)
+    }
+    /// Template name and row the task was created from.
+    pub fn task_template(&self, id: TaskId) -> Option<(&str, &TemplateRow)> {
+        let derivation = self.find_task(id)?.derivation.as_ref()?;
+        Some((derivation.template.as_str(), &derivation.row))
+    }
+}
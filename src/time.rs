@@ -0,0 +1,192 @@
+use std::{
+    fmt,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use ssr_core::task::Task;
+
+use crate::Facade;
+
+pub(crate) const SECS_PER_DAY: i64 = 24 * 60 * 60;
+
+/// Seconds since the Unix epoch as seen on a wall clock `utc_offset_secs` away
+/// from UTC, saturating at the ends of `i64`.
+pub(crate) fn local_secs(time: SystemTime, utc_offset_secs: i32) -> i64 {
+    let secs = match time.duration_since(UNIX_EPOCH) {
+        Ok(after) => i64::try_from(after.as_secs()).unwrap_or(i64::MAX),
+        Err(before) => -i64::try_from(before.duration().as_secs()).unwrap_or(i64::MAX),
+    };
+    secs.saturating_add(i64::from(utc_offset_secs))
+}
+
+/// Inverse of [`local_secs`], clamped to the range `SystemTime` can represent.
+pub(crate) fn from_local_secs(local_secs: i64, utc_offset_secs: i32) -> SystemTime {
+    let secs = local_secs.saturating_sub(i64::from(utc_offset_secs));
+    let from_epoch = |magnitude: u64| {
+        let magnitude = Duration::from_secs(magnitude);
+        if secs >= 0 {
+            UNIX_EPOCH.checked_add(magnitude)
+        } else {
+            UNIX_EPOCH.checked_sub(magnitude)
+        }
+    };
+    if let Some(time) = from_epoch(secs.unsigned_abs()) {
+        return time;
+    }
+    // Farthest representable time in that direction, by bisection.
+    let (mut low, mut high) = (0, secs.unsigned_abs());
+    while low < high {
+        let mid = low + (high - low).div_ceil(2);
+        if from_epoch(mid).is_some() {
+            low = mid;
+        } else {
+            high = mid - 1;
+        }
+    }
+    from_epoch(low).unwrap_or(UNIX_EPOCH)
+}
+
+/// Local day number since the Unix epoch.
+pub(crate) fn local_day(time: SystemTime, utc_offset_secs: i32) -> i64 {
+    local_secs(time, utc_offset_secs).div_euclid(SECS_PER_DAY)
+}
+
+/// Calendar date in the facade's local time, see
+/// [`Facade::set_utc_offset_secs`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LocalDate {
+    pub year: i64,
+    /// `1..=12`.
+    pub month: u8,
+    /// `1..=31`.
+    pub day: u8,
+}
+
+impl fmt::Display for LocalDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+impl LocalDate {
+    /// Date of a local day number since the Unix epoch, proleptic Gregorian.
+    pub(crate) fn from_day(day: i64) -> Self {
+        let shifted = day + 719_468;
+        let era = shifted.div_euclid(146_097);
+        let day_of_era = shifted.rem_euclid(146_097);
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        // Months counted from March.
+        let month = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * month + 2) / 5 + 1;
+        let month = if month < 10 { month + 3 } else { month - 9 };
+        Self {
+            year: year_of_era + era * 400 + i64::from(month <= 2),
+            month: u8::try_from(month).unwrap_or_default(),
+            day: u8::try_from(day).unwrap_or_default(),
+        }
+    }
+    /// Inverse of [`LocalDate::from_day`].
+    pub(crate) fn to_day(self) -> i64 {
+        let (month, day) = (i64::from(self.month), i64::from(self.day));
+        let year = if month <= 2 { self.year - 1 } else { self.year };
+        let era = year.div_euclid(400);
+        let year_of_era = year.rem_euclid(400);
+        let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        era * 146_097 + day_of_era - 719_468
+    }
+}
+
+/// Start of the local day following the one `time` belongs to.
+pub(crate) fn next_local_midnight(time: SystemTime, utc_offset_secs: i32) -> SystemTime {
+    let day = local_day(time, utc_offset_secs);
+    from_local_secs(
+        day.saturating_add(1).saturating_mul(SECS_PER_DAY),
+        utc_offset_secs,
+    )
+}
+
+/// Local hour of day, `0..24`.
+pub(crate) fn local_hour(time: SystemTime, utc_offset_secs: i32) -> usize {
+    let hour = local_secs(time, utc_offset_secs).rem_euclid(SECS_PER_DAY) / 3600;
+    usize::try_from(hour).expect("hour is in 0..24")
+}
+
+/// Local day of week, `0..7` starting from Monday.
+pub(crate) fn local_weekday(time: SystemTime, utc_offset_secs: i32) -> usize {
+    // 1970-01-01 was a Thursday.
+    let weekday = (local_day(time, utc_offset_secs) + 3).rem_euclid(7);
+    usize::try_from(weekday).expect("weekday is in 0..7")
+}
+
+/// When a task available at `available_at` becomes due: at once, or with
+/// `day_offset_secs` of day granularity mode at the start of that day.
+pub(crate) fn due_from(available_at: SystemTime, day_offset_secs: Option<i32>) -> SystemTime {
+    match day_offset_secs {
+        Some(offset) => from_local_secs(local_day(available_at, offset) * SECS_PER_DAY, offset),
+        None => available_at,
+    }
+}
+
+impl<'a, T: Task<'a>> Facade<'a, T> {
+    /// Offset of the user's local time from UTC, used to find where local
+    /// days start.
+    pub fn utc_offset_secs(&self) -> i32 {
+        self.utc_offset_secs
+    }
+    /// Day boundaries used by statistics, forecasts and daily counters follow
+    /// this setting rather than the device timezone, so they don't shift
+    /// while travelling.
+    pub fn set_utc_offset_secs(&mut self, utc_offset_secs: i32) {
+        self.utc_offset_secs = utc_offset_secs;
+        self.reload_all_tasks_timings();
+    }
+    pub fn rollover_hour(&self) -> u8 {
+        self.rollover_hour
+    }
+    /// Start local days at `hour` instead of midnight, e.g. 4 so reviews
+    /// after midnight still count for the evening. Hours above 23 are
+    /// clamped.
+    pub fn set_rollover_hour(&mut self, hour: u8) {
+        self.rollover_hour = hour.min(23);
+        self.reload_all_tasks_timings();
+    }
+    pub fn day_granularity(&self) -> bool {
+        self.day_granularity
+    }
+    /// Treat a task as due for the whole local day its due time falls on, so
+    /// "due today" doesn't depend on the time of day it was scheduled for.
+    pub fn set_day_granularity(&mut self, enabled: bool) {
+        self.day_granularity = enabled;
+        self.reload_all_tasks_timings();
+    }
+
+    /// Offset that puts day boundaries at local midnight: the UTC offset
+    /// moved back by the rollover hour.
+    pub(crate) fn day_offset_secs(&self) -> i32 {
+        self.utc_offset_secs - i32::from(self.rollover_hour) * 3600
+    }
+    /// `Some` with [`Facade::day_offset_secs`] in day granularity mode, for
+    /// [`due_from`].
+    pub(crate) fn day_granularity_offset(&self) -> Option<i32> {
+        self.day_granularity.then(|| self.day_offset_secs())
+    }
+    /// When a task available at `available_at` becomes due, see [`due_from`].
+    pub(crate) fn due_from(&self, available_at: SystemTime) -> SystemTime {
+        due_from(available_at, self.day_granularity_offset())
+    }
+
+    /// Local day `time` belongs to, with days starting at the rollover hour.
+    pub fn local_date(&self, time: SystemTime) -> LocalDate {
+        LocalDate::from_day(local_day(time, self.day_offset_secs()))
+    }
+    pub fn today(&self) -> LocalDate {
+        self.local_date(self.now())
+    }
+    /// First moment of `date`, at the rollover hour.
+    pub fn start_of_local_day(&self, date: LocalDate) -> SystemTime {
+        from_local_secs(date.to_day() * SECS_PER_DAY, self.day_offset_secs())
+    }
+}
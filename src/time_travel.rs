@@ -0,0 +1,82 @@
+use std::{collections::HashMap, time::SystemTime};
+
+use ssr_core::{task::Task, tasks_facade::TaskId};
+
+use crate::{Facade, trash::TrashedTask};
+
+/// Queues as they were at a past moment, see [`Facade::state_at`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PastState {
+    pub at: SystemTime,
+    /// Reviewed before, due at `at`.
+    pub due: Vec<TaskId>,
+    /// Not reviewed before `at`.
+    pub new: Vec<TaskId>,
+    /// Reviewed before, due after `at`.
+    pub waiting: Vec<TaskId>,
+}
+
+impl PastState {
+    /// Tasks a review session would have handed out.
+    #[must_use]
+    pub fn due_count(&self) -> usize {
+        self.due.len() + self.new.len()
+    }
+}
+
+impl<'a, T: Task<'a>> Facade<'a, T> {
+    /// Reconstruct queues at `at` from review history and due overrides, to
+    /// investigate reports like "yesterday it said 40 due, today 400".
+    ///
+    /// Trashed tasks count until their removal. Creation, suspension, burial
+    /// and freezing aren't journaled, so every task that exists now counts as
+    /// existing and active then.
+    pub fn state_at(&self, at: SystemTime) -> PastState {
+        let mut due_at = HashMap::<TaskId, (SystemTime, SystemTime)>::new();
+        let mut record = |id: TaskId, changed_at: SystemTime, due: SystemTime| {
+            if changed_at > at {
+                return;
+            }
+            let entry = due_at.entry(id).or_insert((changed_at, due));
+            if changed_at >= entry.0 {
+                *entry = (changed_at, due);
+            }
+        };
+        let trashed = self
+            .trash
+            .iter()
+            .filter(|trashed| trashed.removed_at() > at);
+        for r in self
+            .review_log
+            .iter()
+            .chain(trashed.clone().flat_map(TrashedTask::history))
+            .filter(|r| r.source.changes_schedule())
+        {
+            record(r.task_id, r.reviewed_at, r.next_due);
+        }
+        for r in self.review_log.overrides() {
+            record(r.task_id, r.at, r.new_due);
+        }
+
+        let mut state = PastState {
+            at,
+            due: Vec::new(),
+            new: Vec::new(),
+            waiting: Vec::new(),
+        };
+        for id in self
+            .tasks_pool
+            .iter()
+            .chain(self.tasks_to_recall.iter())
+            .chain(trashed.map(TrashedTask::task))
+            .map(|task| task.id)
+        {
+            match due_at.get(&id) {
+                None => state.new.push(id),
+                Some(&(_, due)) if due <= at => state.due.push(id),
+                Some(_) => state.waiting.push(id),
+            }
+        }
+        state
+    }
+}
@@ -0,0 +1,125 @@
+use std::time::{Duration, SystemTime};
+
+use serde::{Deserialize, Serialize};
+use ssr_core::{task::Task, tasks_facade::TaskId};
+
+use crate::{DueOverride, Facade, ReviewRecord, TaskWrapper};
+
+/// How long removed tasks stay restorable by default.
+pub const DEFAULT_TRASH_RETENTION: Duration = Duration::from_hours(30 * 24);
+
+pub(crate) fn default_trash_retention() -> Duration {
+    DEFAULT_TRASH_RETENTION
+}
+
+/// Removed task with its review history and due overrides.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(bound(deserialize = "T: Task<'de>"))]
+pub(crate) struct TrashedTask<T> {
+    task: TaskWrapper<T>,
+    history: Vec<ReviewRecord>,
+    #[serde(default)]
+    overrides: Vec<DueOverride>,
+    removed_at: SystemTime,
+}
+
+impl<T> TrashedTask<T> {
+    pub(crate) fn task(&self) -> &TaskWrapper<T> {
+        &self.task
+    }
+    pub(crate) fn history(&self) -> &[ReviewRecord] {
+        &self.history
+    }
+    pub(crate) fn removed_at(&self) -> SystemTime {
+        self.removed_at
+    }
+    pub(crate) fn map_task<U>(&self, convert: impl FnMut(&T) -> U) -> TrashedTask<U> {
+        TrashedTask {
+            task: self.task.map_task(convert),
+            history: self.history.clone(),
+            overrides: self.overrides.clone(),
+            removed_at: self.removed_at,
+        }
+    }
+}
+
+impl<'a, T: Task<'a>> Facade<'a, T> {
+    /// Removed tasks are kept in the trash for this long.
+    pub fn trash_retention(&self) -> Duration {
+        self.trash_retention
+    }
+    pub fn set_trash_retention(&mut self, retention: Duration) {
+        self.trash_retention = retention;
+        self.purge_expired_trash();
+    }
+
+    /// Ids of restorable tasks with time of removal.
+    pub fn trash(&self) -> impl Iterator<Item = (TaskId, SystemTime)> {
+        self.trash.iter().map(|t| (t.task.id, t.removed_at))
+    }
+    /// Bring removed task back with its review history and due overrides.
+    /// Returns whether the task was in the trash and didn't exist in the
+    /// facade again.
+    pub fn restore(&mut self, id: TaskId) -> bool {
+        if self.find_task(id).is_some() {
+            return false;
+        }
+        let Some(index) = self.trash.iter().position(|t| t.task.id == id) else {
+            return false;
+        };
+        let TrashedTask {
+            task,
+            history,
+            overrides,
+            ..
+        } = self.trash.swap_remove(index);
+        self.review_log.extend(history);
+        self.review_log.extend_overrides(overrides);
+        self.place_task(task);
+        true
+    }
+    /// Permanently delete all removed tasks. Returns their number.
+    pub fn empty_trash(&mut self) -> usize {
+        let count = self.trash.len();
+        self.trash.clear();
+        count
+    }
+
+    /// Move task into the trash, returns whether it existed.
+    pub(crate) fn trash_task(&mut self, id: TaskId) -> bool {
+        if self.find_task(id).is_none() {
+            return false;
+        }
+        let removed_at = self.now();
+        let mut overrides = self.review_log.take_overrides(id);
+        let mut history = self.review_log.remove_task(id);
+        let mut removed = false;
+        while let Some(task) = self.take_task(id) {
+            self.trash.push(TrashedTask {
+                task,
+                history: std::mem::take(&mut history),
+                overrides: std::mem::take(&mut overrides),
+                removed_at,
+            });
+            removed = true;
+        }
+        self.purge_expired_trash();
+        removed
+    }
+    /// Permanently delete tasks removed longer than retention ago. Returns
+    /// their number.
+    pub(crate) fn purge_expired_trash(&mut self) -> usize {
+        let now = self.now();
+        let retention = self.trash_retention;
+        let before = self.trash.len();
+        self.trash.retain(|t| {
+            !now.duration_since(t.removed_at)
+                .is_ok_and(|elapsed| elapsed >= retention)
+        });
+        before - self.trash.len()
+    }
+    /// Forget trashed copy of a task that exists again.
+    pub(crate) fn forget_trashed(&mut self, id: TaskId) {
+        self.trash.retain(|t| t.task.id != id);
+    }
+}
@@ -0,0 +1,372 @@
+use std::{collections::BTreeMap, fmt};
+
+use serde::{
+    Deserialize, Deserializer, Serialize, Serializer,
+    de::{
+        DeserializeSeed, EnumAccess, IntoDeserializer, MapAccess, SeqAccess, VariantAccess, Visitor,
+    },
+    forward_to_deserialize_any,
+    ser::{self, Impossible, SerializeMap, SerializeStruct},
+};
+
+/// Top-level fields of a loaded facade this version doesn't know, by name.
+pub(crate) type UnknownFields = BTreeMap<String, UnknownValue>;
+
+/// Value of an unknown field, as described by a self-describing format.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum UnknownValue {
+    Unit,
+    Bool(bool),
+    I64(i64),
+    U64(u64),
+    I128(i128),
+    U128(u128),
+    F64(f64),
+    Str(String),
+    Bytes(Vec<u8>),
+    None,
+    Some(Box<UnknownValue>),
+    Seq(Vec<UnknownValue>),
+    Map(Vec<(UnknownValue, UnknownValue)>),
+    /// Written back as a single-entry map, like JSON represents enums.
+    Variant(String, Box<UnknownValue>),
+}
+
+impl Serialize for UnknownValue {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::Unit => serializer.serialize_unit(),
+            Self::Bool(v) => serializer.serialize_bool(*v),
+            Self::I64(v) => serializer.serialize_i64(*v),
+            Self::U64(v) => serializer.serialize_u64(*v),
+            Self::I128(v) => serializer.serialize_i128(*v),
+            Self::U128(v) => serializer.serialize_u128(*v),
+            Self::F64(v) => serializer.serialize_f64(*v),
+            Self::Str(v) => serializer.serialize_str(v),
+            Self::Bytes(v) => serializer.serialize_bytes(v),
+            Self::None => serializer.serialize_none(),
+            Self::Some(v) => serializer.serialize_some(v),
+            Self::Seq(items) => serializer.collect_seq(items),
+            Self::Map(entries) => serializer.collect_map(entries.iter().map(|(k, v)| (k, v))),
+            Self::Variant(name, value) => serializer.collect_map([(name, value)]),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for UnknownValue {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(UnknownValueVisitor)
+    }
+}
+
+struct UnknownValueVisitor;
+
+impl<'de> Visitor<'de> for UnknownValueVisitor {
+    type Value = UnknownValue;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("any value")
+    }
+    fn visit_bool<E>(self, v: bool) -> Result<UnknownValue, E> {
+        Ok(UnknownValue::Bool(v))
+    }
+    fn visit_i64<E>(self, v: i64) -> Result<UnknownValue, E> {
+        Ok(UnknownValue::I64(v))
+    }
+    fn visit_u64<E>(self, v: u64) -> Result<UnknownValue, E> {
+        Ok(UnknownValue::U64(v))
+    }
+    fn visit_i128<E>(self, v: i128) -> Result<UnknownValue, E> {
+        Ok(UnknownValue::I128(v))
+    }
+    fn visit_u128<E>(self, v: u128) -> Result<UnknownValue, E> {
+        Ok(UnknownValue::U128(v))
+    }
+    fn visit_f64<E>(self, v: f64) -> Result<UnknownValue, E> {
+        Ok(UnknownValue::F64(v))
+    }
+    fn visit_str<E>(self, v: &str) -> Result<UnknownValue, E> {
+        Ok(UnknownValue::Str(v.to_owned()))
+    }
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<UnknownValue, E> {
+        Ok(UnknownValue::Bytes(v.to_owned()))
+    }
+    fn visit_unit<E>(self) -> Result<UnknownValue, E> {
+        Ok(UnknownValue::Unit)
+    }
+    fn visit_none<E>(self) -> Result<UnknownValue, E> {
+        Ok(UnknownValue::None)
+    }
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<UnknownValue, D::Error> {
+        Ok(UnknownValue::Some(Box::new(UnknownValue::deserialize(
+            deserializer,
+        )?)))
+    }
+    fn visit_newtype_struct<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<UnknownValue, D::Error> {
+        UnknownValue::deserialize(deserializer)
+    }
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<UnknownValue, A::Error> {
+        let mut items = Vec::new();
+        while let Some(item) = seq.next_element()? {
+            items.push(item);
+        }
+        Ok(UnknownValue::Seq(items))
+    }
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<UnknownValue, A::Error> {
+        let mut entries = Vec::new();
+        while let Some(entry) = map.next_entry()? {
+            entries.push(entry);
+        }
+        Ok(UnknownValue::Map(entries))
+    }
+    fn visit_enum<A: EnumAccess<'de>>(self, data: A) -> Result<UnknownValue, A::Error> {
+        let (name, variant) = data.variant::<String>()?;
+        let value = variant.newtype_variant()?;
+        Ok(UnknownValue::Variant(name, Box::new(value)))
+    }
+}
+
+/// Deserializer handing the known fields of a struct to its derived
+/// implementation and collecting the rest into `unknown`.
+pub(crate) struct Capturing<'u, D> {
+    inner: D,
+    unknown: &'u mut UnknownFields,
+}
+
+impl<'u, D> Capturing<'u, D> {
+    pub(crate) fn new(inner: D, unknown: &'u mut UnknownFields) -> Self {
+        Self { inner, unknown }
+    }
+}
+
+impl<'de, D: Deserializer<'de>> Deserializer<'de> for Capturing<'_, D> {
+    type Error = D::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, D::Error> {
+        self.inner.deserialize_any(visitor)
+    }
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, D::Error> {
+        self.inner.deserialize_struct(
+            name,
+            fields,
+            CapturingVisitor {
+                inner: visitor,
+                fields,
+                unknown: self.unknown,
+            },
+        )
+    }
+    fn is_human_readable(&self) -> bool {
+        self.inner.is_human_readable()
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map enum identifier ignored_any
+    }
+}
+
+struct CapturingVisitor<'u, V> {
+    inner: V,
+    fields: &'static [&'static str],
+    unknown: &'u mut UnknownFields,
+}
+
+impl<'de, V: Visitor<'de>> Visitor<'de> for CapturingVisitor<'_, V> {
+    type Value = V::Value;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.inner.expecting(f)
+    }
+    /// Formats writing structs as sequences have no field names to keep.
+    fn visit_seq<A: SeqAccess<'de>>(self, seq: A) -> Result<V::Value, A::Error> {
+        self.inner.visit_seq(seq)
+    }
+    fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<V::Value, A::Error> {
+        self.inner.visit_map(CapturingMap {
+            inner: map,
+            fields: self.fields,
+            unknown: self.unknown,
+            key: None,
+        })
+    }
+}
+
+struct CapturingMap<'u, A> {
+    inner: A,
+    fields: &'static [&'static str],
+    unknown: &'u mut UnknownFields,
+    /// Key of the entry whose value is read next.
+    key: Option<String>,
+}
+
+impl<'de, A: MapAccess<'de>> MapAccess<'de> for CapturingMap<'_, A> {
+    type Error = A::Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, A::Error> {
+        let Some(key) = self.inner.next_key::<String>()? else {
+            return Ok(None);
+        };
+        let value = seed.deserialize(key.as_str().into_deserializer())?;
+        self.key = Some(key);
+        Ok(Some(value))
+    }
+    fn next_value_seed<S: DeserializeSeed<'de>>(&mut self, seed: S) -> Result<S::Value, A::Error> {
+        match self.key.take() {
+            Some(key) if !self.fields.contains(&key.as_str()) => {
+                let value = self.inner.next_value()?;
+                self.unknown.insert(key, value);
+                // The derived implementation ignores whatever it gets here.
+                seed.deserialize(().into_deserializer())
+            }
+            _ => self.inner.next_value_seed(seed),
+        }
+    }
+    fn size_hint(&self) -> Option<usize> {
+        self.inner.size_hint()
+    }
+}
+
+/// Serializer writing a struct with `unknown` fields appended. Without
+/// unknown fields the struct is written unchanged, otherwise as a map, since
+/// struct field names must be static.
+pub(crate) struct WithUnknown<'u, S> {
+    inner: S,
+    unknown: &'u UnknownFields,
+}
+
+impl<'u, S> WithUnknown<'u, S> {
+    pub(crate) fn new(inner: S, unknown: &'u UnknownFields) -> Self {
+        Self { inner, unknown }
+    }
+}
+
+pub(crate) enum StructWithUnknown<'u, S: Serializer> {
+    Struct(S::SerializeStruct),
+    Map(S::SerializeMap, &'u UnknownFields),
+}
+
+impl<S: Serializer> SerializeStruct for StructWithUnknown<'_, S> {
+    type Ok = S::Ok;
+    type Error = S::Error;
+
+    fn serialize_field<V: ?Sized + Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &V,
+    ) -> Result<(), S::Error> {
+        match self {
+            Self::Struct(inner) => inner.serialize_field(key, value),
+            Self::Map(inner, _) => inner.serialize_entry(key, value),
+        }
+    }
+    fn end(self) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::Struct(inner) => inner.end(),
+            Self::Map(mut inner, unknown) => {
+                for (key, value) in unknown {
+                    inner.serialize_entry(key, value)?;
+                }
+                inner.end()
+            }
+        }
+    }
+}
+
+/// Methods for values other than structs, which [`WithUnknown`] never gets.
+macro_rules! not_a_struct {
+    ($($method:ident($($arg:ty),*) -> $ok:ty;)*) => {$(
+        fn $method(self, $(_: $arg),*) -> Result<$ok, S::Error> {
+            Err(ser::Error::custom("expected a struct"))
+        }
+    )*};
+}
+
+impl<'u, S: Serializer> Serializer for WithUnknown<'u, S> {
+    type Ok = S::Ok;
+    type Error = S::Error;
+    type SerializeSeq = Impossible<S::Ok, S::Error>;
+    type SerializeTuple = Impossible<S::Ok, S::Error>;
+    type SerializeTupleStruct = Impossible<S::Ok, S::Error>;
+    type SerializeTupleVariant = Impossible<S::Ok, S::Error>;
+    type SerializeMap = Impossible<S::Ok, S::Error>;
+    type SerializeStruct = StructWithUnknown<'u, S>;
+    type SerializeStructVariant = Impossible<S::Ok, S::Error>;
+
+    fn serialize_struct(
+        self,
+        name: &'static str,
+        len: usize,
+    ) -> Result<StructWithUnknown<'u, S>, S::Error> {
+        if self.unknown.is_empty() {
+            return Ok(StructWithUnknown::Struct(
+                self.inner.serialize_struct(name, len)?,
+            ));
+        }
+        let map = self.inner.serialize_map(Some(len + self.unknown.len()))?;
+        Ok(StructWithUnknown::Map(map, self.unknown))
+    }
+    fn is_human_readable(&self) -> bool {
+        self.inner.is_human_readable()
+    }
+
+    not_a_struct! {
+        serialize_bool(bool) -> S::Ok;
+        serialize_i8(i8) -> S::Ok;
+        serialize_i16(i16) -> S::Ok;
+        serialize_i32(i32) -> S::Ok;
+        serialize_i64(i64) -> S::Ok;
+        serialize_u8(u8) -> S::Ok;
+        serialize_u16(u16) -> S::Ok;
+        serialize_u32(u32) -> S::Ok;
+        serialize_u64(u64) -> S::Ok;
+        serialize_f32(f32) -> S::Ok;
+        serialize_f64(f64) -> S::Ok;
+        serialize_char(char) -> S::Ok;
+        serialize_str(&str) -> S::Ok;
+        serialize_bytes(&[u8]) -> S::Ok;
+        serialize_none() -> S::Ok;
+        serialize_unit() -> S::Ok;
+        serialize_unit_struct(&'static str) -> S::Ok;
+        serialize_unit_variant(&'static str, u32, &'static str) -> S::Ok;
+        serialize_seq(Option<usize>) -> Self::SerializeSeq;
+        serialize_tuple(usize) -> Self::SerializeTuple;
+        serialize_tuple_struct(&'static str, usize) -> Self::SerializeTupleStruct;
+        serialize_tuple_variant(&'static str, u32, &'static str, usize)
+            -> Self::SerializeTupleVariant;
+        serialize_map(Option<usize>) -> Self::SerializeMap;
+        serialize_struct_variant(&'static str, u32, &'static str, usize)
+            -> Self::SerializeStructVariant;
+    }
+    fn serialize_some<V: ?Sized + Serialize>(self, _: &V) -> Result<S::Ok, S::Error> {
+        Err(ser::Error::custom("expected a struct"))
+    }
+    fn serialize_newtype_struct<V: ?Sized + Serialize>(
+        self,
+        _: &'static str,
+        _: &V,
+    ) -> Result<S::Ok, S::Error> {
+        Err(ser::Error::custom("expected a struct"))
+    }
+    fn serialize_newtype_variant<V: ?Sized + Serialize>(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: &V,
+    ) -> Result<S::Ok, S::Error> {
+        Err(ser::Error::custom("expected a struct"))
+    }
+}
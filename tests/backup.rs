@@ -0,0 +1,90 @@
+mod support;
+
+use std::{
+    collections::BTreeMap,
+    io,
+    time::{Duration, SystemTime},
+};
+
+use ssr_core::tasks_facade::TasksFacade;
+use ssr_facade::{BackupStore, Facade};
+use support::Dummy;
+
+#[test]
+fn backups_in_the_same_millisecond_are_kept_apart() {
+    let dir = std::env::temp_dir().join(format!("ssr-facade-backup-{}", std::process::id()));
+    let mut facade = Facade::<Dummy>::new("backup".to_owned(), 0.9);
+    facade.set_clock(Some(|| {
+        SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000)
+    }));
+    let write = |name: &'static str| {
+        move |_: &Facade<Dummy>, writer: &mut dyn io::Write| writer.write_all(name.as_bytes())
+    };
+    let first = facade.backup(&dir, 5, write("first")).unwrap();
+    let second = facade.backup(&dir, 5, write("second")).unwrap();
+    assert_ne!(first, second);
+
+    let backups = Facade::<Dummy>::list_backups(&dir).unwrap();
+    let paths = backups.iter().map(|b| &b.path).collect::<Vec<_>>();
+    assert_eq!(paths, [&second, &first]);
+    assert_eq!(std::fs::read_to_string(&first).unwrap(), "first");
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn zero_keep_n_keeps_only_the_new_backup() {
+    let dir = std::env::temp_dir().join(format!("ssr-facade-keep-{}", std::process::id()));
+    let facade = Facade::<Dummy>::new("backup".to_owned(), 0.9);
+    let write = |_: &Facade<Dummy>, writer: &mut dyn io::Write| writer.write_all(b"backup");
+    facade.backup(&dir, 5, write).unwrap();
+    let path = facade.backup(&dir, 0, write).unwrap();
+
+    let backups = Facade::<Dummy>::list_backups(&dir).unwrap();
+    assert_eq!(backups.len(), 1);
+    assert_eq!(backups[0].path, path);
+    assert!(path.exists());
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[derive(Default)]
+struct MemoryStore(BTreeMap<SystemTime, Vec<u8>>);
+
+impl BackupStore for MemoryStore {
+    fn list(&self) -> io::Result<Vec<SystemTime>> {
+        Ok(self.0.keys().copied().collect())
+    }
+    fn put(&mut self, created: SystemTime, bytes: Vec<u8>) -> io::Result<()> {
+        self.0.insert(created, bytes);
+        Ok(())
+    }
+    fn get(&self, created: SystemTime) -> io::Result<Vec<u8>> {
+        self.0
+            .get(&created)
+            .cloned()
+            .ok_or(io::ErrorKind::NotFound.into())
+    }
+    fn remove(&mut self, created: SystemTime) -> io::Result<()> {
+        self.0.remove(&created);
+        Ok(())
+    }
+}
+
+#[test]
+fn stored_backups_at_the_same_time_are_kept_apart() {
+    let mut facade = Facade::<Dummy>::new("backup".to_owned(), 0.9);
+    facade.set_clock(Some(|| {
+        SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000)
+    }));
+    let write = |name: &'static str| {
+        move |_: &Facade<Dummy>, writer: &mut dyn io::Write| writer.write_all(name.as_bytes())
+    };
+    let mut store = MemoryStore::default();
+    let first = facade.backup_with(&mut store, 5, write("first")).unwrap();
+    let second = facade.backup_with(&mut store, 5, write("second")).unwrap();
+    assert!(second > first);
+    assert_eq!(store.get(first).unwrap(), b"first");
+    assert_eq!(store.get(second).unwrap(), b"second");
+
+    let third = facade.backup_with(&mut store, 0, write("third")).unwrap();
+    assert_eq!(store.list().unwrap(), [third]);
+}
@@ -0,0 +1,78 @@
+mod support;
+
+use s_text_input_f::{Block, Blocks, ParagraphItem};
+use ssr_core::tasks_facade::{Error, TasksFacade};
+use ssr_facade::Facade;
+use support::{
+    Dummy, answer, card,
+    value::{from_value, to_value},
+};
+
+fn deck(name: &str, questions: &[&str]) -> Facade<'static, Dummy> {
+    let mut facade = Facade::new(name.to_owned(), 0.9);
+    for question in questions {
+        facade.create_task(card(question));
+    }
+    facade
+}
+
+fn question(blocks: &Blocks) -> String {
+    match &blocks[0] {
+        Block::Paragraph(items) => match &items[0] {
+            ParagraphItem::Text(text) => text.clone(),
+            ParagraphItem::Placeholder => String::new(),
+        },
+        _ => String::new(),
+    }
+}
+
+#[test]
+fn sub_decks_are_counted_and_reviewed_after_their_parent() {
+    let mut languages = deck("languages", &["hallo"]);
+    let mut german = deck("german", &["haus"]);
+    german.insert_child(deck("verbs", &["gehen"]));
+    languages.insert_child(german);
+    languages.find_tasks_to_recall();
+    assert_eq!(languages.tasks_total(), 3);
+    assert_eq!(languages.tasks_to_complete(), 3);
+    assert_eq!(languages.child("german").unwrap().tasks_total(), 2);
+    assert!(languages.has_due());
+
+    let mut reviewed = Vec::new();
+    for _ in 0..3 {
+        languages
+            .complete_task(&mut |_, blocks| {
+                reviewed.push(question(&blocks));
+                Ok(answer(true))
+            })
+            .unwrap();
+    }
+    assert!(reviewed[0].contains("hallo"));
+    assert!(reviewed[1].contains("haus"));
+    assert!(reviewed[2].contains("gehen"));
+    assert_eq!(languages.tasks_to_complete(), 0);
+    assert!(!languages.has_due());
+    let verbs = languages.child("german").unwrap().child("verbs").unwrap();
+    assert_eq!(verbs.review_log().len(), 1);
+    assert!(matches!(
+        languages.complete_task(&mut |_, _| Ok(answer(true))),
+        Err(Error::NoTaskToComplete { .. })
+    ));
+}
+
+#[test]
+fn sub_decks_round_trip_and_migrate() {
+    let mut facade = deck("languages", &["hallo"]);
+    facade.insert_child(deck("german", &["haus", "baum"]));
+    let value = to_value(&facade);
+    let restored: Facade<Dummy> = from_value(value.clone()).unwrap();
+    assert_eq!(to_value(&restored), value);
+    assert_eq!(restored.children().len(), 1);
+    assert_eq!(restored.tasks_total(), 3);
+
+    let migrated = facade.migrate_with(Clone::clone);
+    assert_eq!(migrated.child("german").unwrap().tasks_total(), 2);
+    assert_eq!(facade.migrate::<Dummy>().tasks_total(), 3);
+    assert_eq!(facade.remove_child("german").unwrap().tasks_total(), 2);
+    assert_eq!(facade.tasks_total(), 1);
+}
@@ -0,0 +1,77 @@
+mod support;
+
+use std::{
+    io,
+    sync::atomic::{AtomicU64, Ordering},
+    time::{Duration, SystemTime},
+};
+
+use ssr_core::tasks_facade::TasksFacade;
+use ssr_facade::Facade;
+use support::{
+    Dummy, answer, card,
+    value::{Value, from_value, to_value},
+};
+
+static NOW: AtomicU64 = AtomicU64::new(1_700_000_000);
+
+fn clock() -> SystemTime {
+    SystemTime::UNIX_EPOCH + Duration::from_secs(NOW.load(Ordering::Relaxed))
+}
+
+/// Reviews at the glitched time stay last in the chronological log.
+fn first_review(facade: &Facade<Dummy>) -> SystemTime {
+    facade.review_log().iter().next().unwrap().reviewed_at
+}
+
+#[test]
+fn forward_clock_glitch_doesnt_outlive_the_session() {
+    let mut facade = Facade::<Dummy>::new("clock".to_owned(), 0.9);
+    facade.set_clock(Some(clock));
+    facade.create_task(card("question"));
+    let id = facade.iter().next().unwrap().1;
+    let review = |facade: &mut Facade<Dummy>| {
+        facade
+            .complete_early(id, &mut |_, _| Ok(answer(true)))
+            .unwrap();
+    };
+
+    let glitch = NOW.load(Ordering::Relaxed) + 70 * 365 * 86400;
+    let correct = NOW.swap(glitch, Ordering::Relaxed);
+    review(&mut facade);
+    NOW.store(correct, Ordering::Relaxed);
+    review(&mut facade);
+    assert!(facade.clock_skew_detected().is_some());
+    let glitched = clock() + Duration::from_secs(glitch - correct);
+    assert!(
+        facade
+            .review_log()
+            .iter()
+            .all(|r| r.reviewed_at == glitched)
+    );
+
+    let mut value = to_value(&facade);
+    assert_eq!(value.get("latest_seen_time"), None);
+    let Value::Map(entries) = &mut value else {
+        panic!("facade is a map");
+    };
+    let saved_by_older_version = to_value(&Some(
+        SystemTime::UNIX_EPOCH + Duration::from_secs(4_000_000_000),
+    ));
+    entries.push((
+        Value::Str("latest_seen_time".to_owned()),
+        saved_by_older_version,
+    ));
+    let read = move |_: &mut dyn io::Read| from_value(value).map_err(io::Error::other);
+    let (mut loaded, diagnostics) = Facade::<Dummy>::load(io::empty(), read).unwrap();
+    assert!(diagnostics.unknown_fields.is_empty());
+    loaded.set_clock(Some(clock));
+    review(&mut loaded);
+    assert_eq!(loaded.clock_skew_detected(), None);
+    assert_eq!(first_review(&loaded), clock());
+
+    facade.reset_clock_skew();
+    assert_eq!(facade.clock_skew_detected(), None);
+    review(&mut facade);
+    assert_eq!(first_review(&facade), clock());
+}
@@ -0,0 +1,55 @@
+mod support;
+
+use ssr_core::tasks_facade::TasksFacade;
+use ssr_facade::{Collection, Facade, ReverseMode};
+use support::{Dummy, answer, card};
+
+#[test]
+fn collection_skips_decks_over_daily_limit() {
+    let deck = |name: &str, questions: &[&str]| {
+        let mut deck = Facade::<Dummy>::new(name.to_owned(), 0.9);
+        deck.set_deterministic(Some(1));
+        for question in questions {
+            deck.create_task(card(question));
+        }
+        deck
+    };
+    let mut limited = deck("limited", &["one", "two"]);
+    limited.set_reviews_per_day(Some(1));
+    limited.complete_task(&mut |_, _| Ok(answer(true))).unwrap();
+    assert!(!limited.has_due());
+
+    let mut collection = Collection::new();
+    collection.insert_deck(limited);
+    collection.insert_deck(deck("open", &["three"]));
+    let mut picked = Vec::new();
+    collection
+        .complete_task(&mut |name, _, _| {
+            picked.push(name.to_owned());
+            Ok(answer(true))
+        })
+        .unwrap();
+    assert_eq!(picked, ["open"]);
+    assert!(
+        collection
+            .complete_task(&mut |_, _, _| Ok(answer(true)))
+            .is_err()
+    );
+}
+
+#[test]
+fn moved_task_leaves_no_dangling_links() {
+    let mut source = Facade::<Dummy>::new("source".to_owned(), 0.9);
+    let (forward, reverse) = source
+        .create_bidirectional("front", "back", ReverseMode::AfterForwardMatures)
+        .unwrap();
+    let mut collection = Collection::new();
+    collection.insert_deck(source);
+    collection.insert_deck(Facade::new("target".to_owned(), 0.9));
+    assert!(collection.move_task("source", "target", forward));
+
+    let source = collection.deck("source").unwrap();
+    assert_eq!(source.sibling(reverse), None);
+    assert_eq!(source.prerequisites(reverse).count(), 0);
+    assert_eq!(collection.deck("target").unwrap().sibling(forward), None);
+}
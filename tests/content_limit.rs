@@ -0,0 +1,104 @@
+mod support;
+
+use s_text_input_f::{Block, BlockAnswered, BlocksWithAnswer, ParagraphItem};
+use ssr_core::{task::Task, tasks_facade::TasksFacade};
+use ssr_facade::{
+    ContentLimit, Facade, FixedIntervalTask, MEDIA_REFERENCE_PREFIX, MediaStore, OversizePolicy,
+    RejectReason,
+};
+use support::answer;
+
+fn article(question: &str, article: &str) -> BlocksWithAnswer {
+    BlocksWithAnswer {
+        blocks: vec![Block::Paragraph(vec![
+            ParagraphItem::Text(question.to_owned()),
+            ParagraphItem::Text(article.to_owned()),
+            ParagraphItem::Placeholder,
+        ])],
+        answer: answer(true),
+    }
+}
+
+fn texts(blocks: &[Block]) -> Vec<&str> {
+    blocks
+        .iter()
+        .flat_map(|block| match block {
+            Block::Paragraph(items) => items.as_slice(),
+            _ => &[],
+        })
+        .filter_map(|item| match item {
+            ParagraphItem::Text(text) => Some(text.as_str()),
+            ParagraphItem::Placeholder => None,
+        })
+        .collect()
+}
+
+#[test]
+fn oversized_text_is_stored_out_of_line() {
+    let mut facade = Facade::<FixedIntervalTask>::new("media".to_owned(), 0.9);
+    facade.set_content_limit(Some(ContentLimit {
+        max_bytes: 200,
+        policy: OversizePolicy::StoreOutOfLine,
+    }));
+    let long = "article ".repeat(1000);
+    facade.create_task(article("Summarize:", &long));
+    facade.create_task(article("Title of:", &long));
+    assert_eq!(facade.tasks_total(), 2);
+    assert_eq!(facade.media().len(), 1);
+    for (task, _) in facade.iter() {
+        let content = task.get_blocks();
+        let texts = texts(&content.blocks);
+        assert!(!texts[0].starts_with(MEDIA_REFERENCE_PREFIX));
+        assert!(texts[1].starts_with(MEDIA_REFERENCE_PREFIX));
+    }
+
+    let id = facade.iter().next().unwrap().1;
+    let mut other = Facade::<FixedIntervalTask>::new("other".to_owned(), 0.9);
+    assert!(facade.transfer(id, &mut other));
+    assert_eq!(facade.prune_media(), 0);
+    other
+        .complete_task(&mut |_, blocks| {
+            assert_eq!(texts(&blocks)[1], long);
+            Ok(answer(true))
+        })
+        .unwrap();
+
+    let id = facade.iter().next().unwrap().1;
+    facade.remove(id);
+    facade.empty_trash();
+    assert_eq!(facade.prune_media(), 1);
+    assert!(facade.media().is_empty());
+}
+
+#[test]
+fn out_of_line_keeps_options_and_answers_in_the_task() {
+    let limit = ContentLimit {
+        max_bytes: 100,
+        policy: OversizePolicy::StoreOutOfLine,
+    };
+    let mut media = MediaStore::default();
+    let mut content = article("Pick one:", &"text ".repeat(100));
+    content.blocks.push(Block::OneOf(vec!["option".repeat(20)]));
+    assert_eq!(
+        limit.apply(content, &mut media).err(),
+        Some(RejectReason::TooLarge)
+    );
+    assert!(media.is_empty());
+}
+
+#[test]
+fn answered_blocks_count_their_payload() {
+    let limit = ContentLimit {
+        max_bytes: 40,
+        policy: OversizePolicy::Reject,
+    };
+    let content = BlocksWithAnswer {
+        blocks: vec![Block::Answered(BlockAnswered::OneOf {
+            items: vec!["yes".to_owned(), "no".to_owned()],
+            user_answer: 0,
+            correct_answer: 1,
+        })],
+        answer: answer(true),
+    };
+    assert!(limit.apply(content, &mut MediaStore::default()).is_ok());
+}
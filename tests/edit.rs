@@ -0,0 +1,34 @@
+mod support;
+
+use ssr_core::tasks_facade::TasksFacade;
+use ssr_facade::{Facade, Outcome};
+use support::{Dummy, answer, card};
+
+#[test]
+fn edits_keep_or_restart_the_schedule() {
+    let mut facade = Facade::<Dummy>::new("edit".to_owned(), 0.9);
+    facade.create_task(card("question"));
+    facade.complete_task(&mut |_, _| Ok(answer(true))).unwrap();
+    let id = facade.iter().next().unwrap().1;
+    let due = |facade: &Facade<Dummy>| facade.iter_owned().next().unwrap().next_repetition;
+
+    let before = due(&facade);
+    assert_eq!(facade.edit_task_with(id, Dummy::clone), Ok(true));
+    assert_eq!(due(&facade), before);
+    facade
+        .complete_early(id, &mut |_, _| Ok(answer(true)))
+        .unwrap();
+    assert_eq!(
+        facade.task_history(id).last().unwrap().outcome,
+        Outcome::Pass
+    );
+
+    assert_eq!(facade.edit_task(id, card("edited")), Ok(true));
+    facade
+        .complete_early(id, &mut |_, _| Ok(answer(true)))
+        .unwrap();
+    assert_eq!(
+        facade.task_history(id).last().unwrap().outcome,
+        Outcome::New
+    );
+}
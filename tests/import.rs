@@ -0,0 +1,61 @@
+mod support;
+
+use std::time::{Duration, SystemTime};
+
+use ssr_core::tasks_facade::TasksFacade;
+use ssr_facade::{ContentLimit, Facade, IdGenerator, OversizePolicy};
+use support::{Dummy, card};
+
+#[test]
+fn deterministic_import_gives_distinct_repeatable_ids() {
+    let import = |generator| {
+        let mut facade = Facade::<Dummy>::new("import".to_owned(), 0.9);
+        facade.set_deterministic(Some(3));
+        facade.set_clock(Some(|| {
+            SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000)
+        }));
+        facade.set_id_generator(generator);
+        let mut importer = facade.begin_import();
+        let ids =
+            ["one", "two", "three"].map(|question| importer.create_task(card(question)).unwrap());
+        assert_eq!(importer.commit().imported, ids);
+        ids
+    };
+    for generator in [IdGenerator::Random, IdGenerator::UuidV7] {
+        let ids = import(generator);
+        assert!(ids[0] != ids[1] && ids[1] != ids[2] && ids[0] != ids[2]);
+        assert_eq!(import(generator), ids);
+    }
+}
+
+#[test]
+fn dry_run_leaves_the_facade_untouched() {
+    let mut facade = Facade::<Dummy>::new("import".to_owned(), 0.9);
+    facade.set_deterministic(Some(3));
+    facade.set_content_limit(Some(ContentLimit {
+        max_bytes: 200,
+        policy: OversizePolicy::StoreOutOfLine,
+    }));
+    let long = "article ".repeat(100);
+    let import = |facade: &mut Facade<Dummy>, commit| {
+        let mut importer = facade.begin_import();
+        let ids = [
+            importer.create_task(card("short")),
+            importer.create_task(card(&long)),
+        ];
+        assert_eq!(importer.create_task(card("short")), None);
+        assert_eq!(importer.pending(), 2);
+        if commit {
+            assert_eq!(importer.commit().imported.len(), 2);
+        }
+        ids
+    };
+
+    let epoch = facade.epoch();
+    let dry_run = import(&mut facade, false);
+    assert_eq!(facade.epoch(), epoch);
+    assert!(facade.media().is_empty());
+    assert_eq!(import(&mut facade, true), dry_run);
+    assert_eq!(facade.tasks_total(), 2);
+    assert_eq!(facade.media().len(), 1);
+}
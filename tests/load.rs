@@ -0,0 +1,88 @@
+mod support;
+
+use std::{cell::RefCell, fs, io};
+
+use ssr_core::tasks_facade::TasksFacade;
+use ssr_facade::{
+    App, AppConfig, FORMAT_VERSION, Facade, LoadError, Loaded, NewerFormatError, NewerFormatPolicy,
+};
+use support::{
+    Dummy, card,
+    value::{Value, from_value, to_value},
+};
+
+thread_local! {
+    /// What the `read` function pointers handed to [`App::open`] return.
+    static FILE: RefCell<Option<Value>> = const { RefCell::new(None) };
+}
+
+fn read_file(_: &mut dyn io::Read) -> io::Result<Facade<'static, Dummy>> {
+    let value = FILE.with_borrow(Clone::clone).expect("file is set");
+    from_value(value).map_err(io::Error::other)
+}
+
+fn newer_facade() -> Value {
+    let mut facade = Facade::<Dummy>::new("newer".to_owned(), 0.9);
+    facade.create_task(card("question"));
+    let mut value = to_value(&facade);
+    *value.get_mut("format_version").unwrap() = Value::U64(u64::from(FORMAT_VERSION) + 1);
+    value
+}
+
+fn is_newer_format(err: &io::Error) -> bool {
+    err.kind() == io::ErrorKind::InvalidData
+        && err
+            .get_ref()
+            .is_some_and(|inner| inner.is::<NewerFormatError>())
+}
+
+#[test]
+fn every_loader_refuses_newer_format() {
+    FILE.set(Some(newer_facade()));
+    let err = Facade::load_with(|| Ok(Vec::new()), read_file)
+        .err()
+        .unwrap();
+    assert!(is_newer_format(&err));
+
+    let dir = std::env::temp_dir().join(format!("ssr-facade-load-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("newer.deck");
+    fs::write(&path, "newer").unwrap();
+    let err = Facade::restore_backup(&path, read_file).err().unwrap();
+    assert!(is_newer_format(&err));
+
+    let write = |_: &Facade<Dummy>, writer: &mut dyn io::Write| writer.write_all(b"older");
+    let err = App::open(&path, AppConfig::default(), read_file, write)
+        .err()
+        .unwrap();
+    assert!(is_newer_format(&err));
+    assert_eq!(fs::read_to_string(&path).unwrap(), "newer");
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn policy_opens_newer_format_with_diagnostics() {
+    let load = |policy| {
+        let read = |_: &mut dyn io::Read| from_value(newer_facade()).map_err(io::Error::other);
+        Facade::<Dummy>::load_with_policy(io::empty(), read, policy)
+    };
+    assert!(matches!(
+        load(NewerFormatPolicy::Fail),
+        Err(LoadError::NewerFormat(_))
+    ));
+    let newer = NewerFormatError {
+        found: FORMAT_VERSION + 1,
+        supported: FORMAT_VERSION,
+    };
+
+    let (loaded, diagnostics) = load(NewerFormatPolicy::ReadOnly).unwrap();
+    assert!(matches!(loaded, Loaded::ReadOnly(_)));
+    assert_eq!(diagnostics.newer_format, Some(newer));
+
+    let (loaded, diagnostics) = load(NewerFormatPolicy::BestEffort).unwrap();
+    let Loaded::Writable(facade) = loaded else {
+        panic!("best effort loads for writing");
+    };
+    assert_eq!(facade.tasks_total(), 1);
+    assert_eq!(diagnostics.newer_format, Some(newer));
+}
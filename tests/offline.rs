@@ -0,0 +1,30 @@
+mod support;
+
+use std::time::{Duration, SystemTime};
+
+use ssr_core::tasks_facade::TasksFacade;
+use ssr_facade::{Facade, OfflineAnswer};
+use support::{Dummy, card};
+
+#[test]
+fn offline_results_count_from_when_they_were_answered() {
+    let mut facade = Facade::<Dummy>::new("offline".to_owned(), 0.9);
+    facade.create_task(card("question"));
+    let id = facade.iter().next().unwrap().1;
+    let answered_at = SystemTime::now() - Duration::from_secs(2 * 86400);
+
+    let outcomes = facade.apply_review_results([(id, OfflineAnswer::Pass, answered_at)]);
+    assert!(outcomes[0].is_ok());
+    let record = facade.task_history(id)[0].clone();
+    assert_eq!(record.reviewed_at, answered_at);
+    // Dummy passes with a one minute interval.
+    assert!(record.next_interval() < Duration::from_secs(3600));
+    assert!(record.next_due < SystemTime::now());
+    assert_eq!(facade.tasks_to_complete(), 1);
+    assert_eq!(facade.today_counters().reviews, 0);
+    assert_eq!(facade.today_counters().new, 0);
+
+    let outcomes = facade.apply_review_results([(id, OfflineAnswer::Pass, SystemTime::now())]);
+    assert!(outcomes[0].is_ok());
+    assert_eq!(facade.today_counters().reviews, 1);
+}
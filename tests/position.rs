@@ -0,0 +1,27 @@
+mod support;
+
+use ssr_core::tasks_facade::TasksFacade;
+use ssr_facade::Facade;
+use support::{Dummy, card};
+
+#[test]
+fn positions_saturate_at_the_end_of_the_range() {
+    let mut facade = Facade::<Dummy>::new("position".to_owned(), 0.9);
+    for question in ["one", "two", "three"] {
+        facade.create_task(card(question));
+    }
+    let ids = facade.iter().map(|(_, id)| id).collect::<Vec<_>>();
+    assert!(facade.reposition(ids[0], u64::MAX));
+    assert_eq!(facade.position(ids[0]), Some(u64::MAX));
+
+    facade.create_task(card("four"));
+    let added = facade.iter().map(|(_, id)| id).find(|id| !ids.contains(id));
+    assert_eq!(facade.position(added.unwrap()), Some(u64::MAX));
+
+    assert!(facade.reposition(ids[1], u64::MAX - 1));
+    assert!(facade.reposition(ids[2], u64::MAX - 1));
+    assert_eq!(facade.reorder(&[ids[1], ids[2], ids[0]]), 3);
+    assert_eq!(facade.position(ids[1]), Some(u64::MAX - 1));
+    assert_eq!(facade.position(ids[2]), Some(u64::MAX));
+    assert_eq!(facade.position(ids[0]), Some(u64::MAX));
+}
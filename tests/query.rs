@@ -0,0 +1,42 @@
+mod support;
+
+use std::time::{Duration, SystemTime};
+
+use ssr_core::tasks_facade::TasksFacade;
+use ssr_facade::{Action, Facade, Query, QueryError};
+use support::{Dummy, card};
+
+#[test]
+fn dangling_or_is_rejected() {
+    for search in ["tag:x or", "or tag:x", "tag:a or or tag:b", "or"] {
+        assert_eq!(
+            search.parse::<Query>(),
+            Err(QueryError::EmptyAlternative),
+            "{search}"
+        );
+    }
+    assert_eq!(
+        "tag:a or tag:b".parse::<Query>(),
+        Ok(Query::Or(vec![
+            Query::Tag("a".to_owned()),
+            Query::Tag("b".to_owned())
+        ]))
+    );
+    assert_eq!("".parse::<Query>(), Ok(Query::All));
+}
+
+#[test]
+fn bulk_postpone_counts_only_changed_tasks() {
+    let mut facade = Facade::<Dummy>::new("bulk".to_owned(), 0.9);
+    for question in ["one", "two"] {
+        facade.create_task(card(question));
+    }
+    let frozen = facade.iter().next().unwrap().1;
+    assert!(facade.freeze(frozen, SystemTime::now() + Duration::from_secs(86400)));
+
+    let hour = Action::Postpone(Duration::from_secs(3600));
+    assert_eq!(facade.apply_to_query(&Query::All, &hour), 1);
+    let overflow = Action::Postpone(Duration::MAX);
+    assert_eq!(facade.apply_to_query(&Query::All, &overflow), 0);
+    assert_eq!(facade.apply_to_query(&Query::All, &Action::Delete), 2);
+}
@@ -0,0 +1,32 @@
+mod support;
+
+use ssr_core::{task::Task, tasks_facade::TasksFacade};
+use ssr_facade::{Event, Facade};
+use support::{Dummy, card};
+
+#[test]
+fn ignored_writes_are_reported() {
+    let mut facade = Facade::<Dummy>::new("read only".to_owned(), 0.9);
+    facade.create_task(card("question"));
+    let id = facade.iter().next().unwrap().1;
+    let mut read_only = facade.into_read_only();
+
+    read_only.set_desired_retention(0.5);
+    read_only.create_task(card("another"));
+    read_only.insert(Dummy::new(card("third")));
+    assert!(!read_only.remove(id));
+    assert_eq!(read_only.get_desired_retention(), 0.9);
+    assert_eq!(read_only.tasks_total(), 1);
+    let calls = read_only
+        .take_events()
+        .into_iter()
+        .map(|event| match event {
+            Event::ReadOnlyCallIgnored { call } => call,
+            event => panic!("unexpected {event:?}"),
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        calls,
+        ["set_desired_retention", "create_task", "insert", "remove"]
+    );
+}
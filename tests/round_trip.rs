@@ -0,0 +1,273 @@
+mod support;
+
+use std::{
+    io,
+    time::{Duration, SystemTime},
+};
+
+use ssr_core::tasks_facade::TasksFacade;
+use ssr_facade::{
+    Collection, ContentLimit, FORMAT_VERSION, Facade, LoadError, OversizePolicy, ReverseMode,
+    Template, TemplateRow,
+};
+use support::{
+    Dummy, answer, card,
+    value::{Value, from_value, to_value},
+};
+
+/// Facade with every optional part of the format filled in.
+fn populated() -> Facade<'static, Dummy> {
+    let mut facade = Facade::new("deck".to_owned(), 0.85);
+    for question in ["first", "second", "third", "fourth"] {
+        facade.create_task(card(question));
+    }
+    facade.set_reviewer(Some("alice".to_owned()));
+    facade.set_stats_reviewer(Some("alice".to_owned()));
+    facade.set_utc_offset_secs(2 * 3600);
+    facade.set_rollover_hour(4);
+    facade.set_day_granularity(true);
+    facade.set_content_limit(Some(ContentLimit {
+        max_bytes: 1024,
+        policy: OversizePolicy::Truncate,
+    }));
+    facade.set_review_ahead_cap(Some(Duration::from_secs(3600)));
+    facade.set_learn_ahead(Duration::from_secs(20 * 60));
+    facade.set_sibling_spacing(Some(Duration::from_secs(2 * 24 * 3600)));
+    facade.set_max_interval_growth(Some(4.));
+    facade.set_deterministic(Some(7));
+    facade.set_new_per_day(Some(20));
+    facade.set_reviews_per_day(Some(200));
+    facade.settings_mut().set("ui.theme", "dark");
+    facade.settings_mut().set("ui.font_size", 14);
+    facade.settings_mut().set("ui.scale", 1.5);
+    facade.settings_mut().set("audio", true);
+    facade.complete_task(&mut |_, _| Ok(answer(true))).unwrap();
+    facade.complete_task(&mut |_, _| Ok(answer(false))).unwrap();
+
+    let ids = facade.iter().map(|(_, id)| id).collect::<Vec<_>>();
+    facade.add_tag(ids[0], "verbs");
+    facade.add_tag(ids[1], "nouns");
+    facade.add_parameter_group("grammar");
+    facade.assign_tag_to_parameter_group("verbs", "grammar");
+    facade.set_task_parameter_group(ids[1], Some("grammar".to_owned()));
+    facade.set_interval_multiplier(ids[0], Some(0.7));
+    facade.set_tag_interval_multiplier("nouns", Some(1.2));
+    facade.add_prerequisite(ids[2], ids[0]);
+    facade.add_tag_prerequisite("nouns", "verbs");
+    facade.set_due(ids[2], SystemTime::now() + Duration::from_secs(86400));
+    facade.postpone(ids[1], Duration::from_secs(600));
+    facade.remove(ids[3]);
+
+    let template = card("{word}");
+    facade
+        .set_template(
+            "words",
+            Template {
+                blocks: template.blocks,
+                answer: template.answer,
+            },
+        )
+        .unwrap();
+    let row = TemplateRow::from([("word".to_owned(), "fifth".to_owned())]);
+    facade.create_from_template("words", [row]).unwrap();
+    let mut child = Facade::new("irregular".to_owned(), 0.9);
+    child.create_task(card("sixth"));
+    facade.insert_child(child);
+    facade
+}
+
+#[test]
+fn facade_round_trips() {
+    let facade = populated();
+    let first = to_value(&facade);
+    let restored: Facade<Dummy> = from_value(first.clone()).unwrap();
+    assert_eq!(to_value(&restored), first);
+
+    assert_eq!(restored.get_name(), "deck");
+    assert_eq!(restored.tasks_total(), facade.tasks_total());
+    assert_eq!(restored.review_log().len(), facade.review_log().len());
+    assert_eq!(restored.trash().count(), 1);
+    assert_eq!(restored.settings(), facade.settings());
+    assert_eq!(restored.utc_offset_secs(), 2 * 3600);
+    assert_eq!(restored.content_limit(), facade.content_limit());
+    assert_eq!(restored.stats_reviewer(), Some("alice"));
+    let snapshots = facade.iter_owned().collect::<Vec<_>>();
+    assert!(
+        snapshots
+            .iter()
+            .all(|snapshot| restored.is_current(snapshot))
+    );
+}
+
+#[test]
+fn clone_serializes_identically() {
+    let facade = populated();
+    assert_eq!(to_value(&facade.clone()), to_value(&facade));
+}
+
+#[test]
+fn oldest_format_loads_with_defaults() {
+    let mut facade = Facade::<Dummy>::new("old".to_owned(), 0.9);
+    facade.create_task(card("question"));
+    let mut value = to_value(&facade);
+    value.retain_keys(&[
+        "name",
+        "tasks_pool",
+        "tasks_to_recall",
+        "desired_retention",
+        "state",
+    ]);
+    for queue in ["tasks_pool", "tasks_to_recall"] {
+        let Some(Value::Seq(tasks)) = value.get_mut(queue) else {
+            panic!("{queue} is a sequence");
+        };
+        for task in tasks {
+            task.retain_keys(&["task", "id"]);
+        }
+    }
+
+    let restored: Facade<Dummy> = from_value(value).unwrap();
+    assert_eq!(restored.get_name(), "old");
+    assert_eq!(restored.tasks_total(), 1);
+    assert_eq!(restored.review_log().len(), 0);
+    assert_eq!(restored.trash().count(), 0);
+    assert_eq!(restored.utc_offset_secs(), 0);
+    assert_eq!(restored.content_limit(), None);
+    assert_eq!(restored.format_version(), 0);
+    let id = restored.iter().next().unwrap().1;
+    assert!(restored.tags(id).unwrap().is_empty());
+    assert_eq!(restored.prerequisites(id).count(), 0);
+    assert_eq!(restored.task_parameter_group(id), None);
+}
+
+#[test]
+fn load_reports_upgrade_and_refuses_newer_format() {
+    let read = |value: Value| {
+        move |_: &mut dyn io::Read| from_value::<Facade<Dummy>>(value).map_err(io::Error::other)
+    };
+    let mut value = to_value(&populated());
+    *value.get_mut("format_version").unwrap() = Value::U64(0);
+    let (_, diagnostics) = Facade::load(io::empty(), read(value.clone())).unwrap();
+    assert_eq!(diagnostics.upgraded_from, Some(0));
+    assert_eq!(diagnostics.ids_regenerated, 0);
+    assert!(diagnostics.repairs.is_empty());
+
+    let extra = Value::Seq(vec![Value::Str("kept".to_owned()), Value::U64(7)]);
+    let Value::Map(entries) = &mut value else {
+        panic!("facade is a map");
+    };
+    entries.push((Value::Str("added_by_plugin".to_owned()), extra.clone()));
+    let (facade, diagnostics) = Facade::load(io::empty(), read(value.clone())).unwrap();
+    assert_eq!(diagnostics.unknown_fields, ["added_by_plugin"]);
+    assert_eq!(to_value(&facade).get("added_by_plugin"), Some(&extra));
+    let again = from_value::<Facade<Dummy>>(to_value(&facade)).unwrap();
+    assert_eq!(to_value(&again), to_value(&facade));
+
+    *value.get_mut("format_version").unwrap() = Value::U64(u64::from(FORMAT_VERSION) + 1);
+    assert!(matches!(
+        Facade::load(io::empty(), read(value)),
+        Err(LoadError::NewerFormat(_))
+    ));
+}
+
+#[test]
+fn collection_round_trips() {
+    let mut collection = Collection::new();
+    collection.insert_deck(populated());
+    collection.insert_deck(Facade::new("empty".to_owned(), 0.9));
+    collection.meta_mut("empty").unwrap().color = Some("red".to_owned());
+    let first = to_value(&collection);
+    let restored: Collection<Dummy> = from_value(first.clone()).unwrap();
+    assert_eq!(to_value(&restored), first);
+    assert_eq!(restored.tasks_total(), collection.tasks_total());
+}
+
+#[test]
+fn delta_round_trips() {
+    let facade = populated();
+    let delta = facade.delta_since(0);
+    let first = to_value(&delta);
+    let restored: ssr_facade::Delta<Dummy> = from_value(first.clone()).unwrap();
+    assert_eq!(to_value(&restored), first);
+
+    let mut replica = Facade::<Dummy>::new("deck".to_owned(), 0.85);
+    replica.apply_delta(restored);
+    assert_eq!(replica.tasks_total(), facade.tasks_total());
+    assert!(!facade.review_log().overrides().is_empty());
+    assert_eq!(
+        replica.review_log().overrides(),
+        facade.review_log().overrides()
+    );
+}
+
+/// Every key written by the serializer must be described by the schema.
+#[test]
+fn schema_covers_serialized_fields() {
+    let schema = Facade::<Dummy>::schema();
+    let facade = to_value(&populated());
+
+    let mut maps = vec![&facade];
+    for key in [
+        "review_log",
+        "day",
+        "settings",
+        "parameter_groups",
+        "auto_tag_policy",
+    ] {
+        maps.push(facade.get(key).unwrap());
+    }
+    for queue in ["tasks_pool", "tasks_to_recall"] {
+        if let Some(Value::Seq(tasks)) = facade.get(queue) {
+            maps.extend(tasks);
+        }
+    }
+    let log = facade.get("review_log").unwrap();
+    for key in ["records", "overrides"] {
+        if let Some(Value::Seq(entries)) = log.get(key) {
+            maps.extend(entries);
+        }
+    }
+    if let Some(Value::Seq(trash)) = facade.get("trash") {
+        maps.extend(trash);
+        maps.extend(trash.iter().filter_map(|trashed| trashed.get("task")));
+    }
+
+    for map in maps {
+        for key in map.keys() {
+            assert!(
+                schema.contains(&format!("\"{key}\"")),
+                "`{key}` is missing from the schema"
+            );
+        }
+    }
+}
+
+#[test]
+fn regenerated_duplicates_keep_links_and_history() {
+    let mut facade = Facade::<Dummy>::new("duplicates".to_owned(), 0.9);
+    facade
+        .create_bidirectional("front", "back", ReverseMode::AfterForwardMatures)
+        .unwrap();
+    facade.complete_task(&mut |_, _| Ok(answer(true))).unwrap();
+    let mut value = to_value(&facade);
+    for queue in ["tasks_pool", "tasks_to_recall"] {
+        let Some(Value::Seq(tasks)) = value.get_mut(queue) else {
+            panic!("{queue} is a sequence");
+        };
+        tasks.extend(tasks.clone());
+    }
+
+    let read = move |_: &mut dyn io::Read| from_value(value).map_err(io::Error::other);
+    let (facade, diagnostics) = Facade::<Dummy>::load(io::empty(), read).unwrap();
+    assert_eq!(diagnostics.ids_regenerated, 2);
+    let ids = facade.iter().map(|(_, id)| id).collect::<Vec<_>>();
+    assert_eq!(ids.len(), 4);
+    for id in ids {
+        let sibling = facade.sibling(id).unwrap();
+        assert_eq!(facade.sibling(sibling), Some(id));
+        let prerequisites = facade.prerequisites(id).collect::<Vec<_>>();
+        assert!(prerequisites.is_empty() || prerequisites == [sibling]);
+        let reviewed = facade.task_history(id).len() + facade.task_history(sibling).len();
+        assert_eq!(reviewed, 1);
+    }
+}
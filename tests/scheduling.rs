@@ -0,0 +1,137 @@
+mod support;
+
+use std::time::{Duration, SystemTime};
+
+use ssr_core::tasks_facade::TasksFacade;
+use ssr_facade::{Facade, FacadeStatus};
+use support::{Dummy, answer, card};
+
+#[test]
+fn status_counts_what_complete_task_hands_out() {
+    let mut facade = Facade::<Dummy>::new("status".to_owned(), 0.9);
+    for question in ["one", "two", "three"] {
+        facade.create_task(card(question));
+    }
+    facade.set_new_per_day(Some(2));
+    assert_eq!(facade.status(), FacadeStatus::Due(2));
+    facade.complete_task(&mut |_, _| Ok(answer(true))).unwrap();
+    facade.complete_task(&mut |_, _| Ok(answer(true))).unwrap();
+    assert!(matches!(facade.status(), FacadeStatus::AllDone { .. }));
+}
+
+#[test]
+fn utc_offset_change_moves_day_boundaries_at_once() {
+    let now = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+    let mut facade = Facade::<Dummy>::new("offset".to_owned(), 0.9);
+    facade.set_clock(Some(|| {
+        SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000)
+    }));
+    facade.set_day_granularity(true);
+    facade.create_task(card("question"));
+    let id = facade.iter().next().unwrap().1;
+    // 00:13 UTC of the next day, 21:13 of the same day at UTC-3.
+    facade.set_due(id, now + Duration::from_secs(2 * 3600));
+    facade.find_tasks_to_recall();
+    assert_eq!(facade.tasks_to_complete(), 0);
+
+    facade.set_utc_offset_secs(-3 * 3600);
+    facade.find_tasks_to_recall();
+    assert_eq!(facade.tasks_to_complete(), 1);
+}
+
+#[test]
+fn postpone_refuses_overflow_and_set_due_requeues() {
+    let mut facade = Facade::<Dummy>::new("postpone".to_owned(), 0.9);
+    facade.create_task(card("question"));
+    facade.complete_task(&mut |_, _| Ok(answer(true))).unwrap();
+    let id = facade.iter().next().unwrap().1;
+    let due = |facade: &Facade<Dummy>| facade.iter_owned().next().unwrap().next_repetition;
+
+    let before = due(&facade);
+    assert!(!facade.postpone(id, Duration::MAX));
+    assert_eq!(due(&facade), before);
+    assert!(facade.review_log().overrides().is_empty());
+
+    assert!(facade.set_due(id, SystemTime::UNIX_EPOCH));
+    assert_eq!(facade.tasks_to_complete(), 1);
+}
+
+#[test]
+fn huge_durations_dont_overflow_schedules() {
+    let mut facade = Facade::<Dummy>::new("overflow".to_owned(), 0.9);
+    facade.create_task(card("question"));
+    facade.complete_task(&mut |_, _| Ok(answer(true))).unwrap();
+    let id = facade.iter().next().unwrap().1;
+
+    assert_eq!(facade.shift_schedule(Duration::MAX), 0);
+    assert!(facade.review_log().overrides().is_empty());
+
+    facade.set_min_interval(Duration::MAX);
+    facade
+        .complete_early(id, &mut |_, _| Ok(answer(true)))
+        .unwrap();
+}
+
+#[test]
+fn far_future_due_survives_local_day_math() {
+    let far = SystemTime::UNIX_EPOCH + Duration::from_secs(i64::MAX.unsigned_abs());
+    let mut facade = Facade::<Dummy>::new("far".to_owned(), 0.9);
+    facade.create_task(card("question"));
+    let id = facade.iter().next().unwrap().1;
+    facade.set_utc_offset_secs(14 * 3600);
+    facade.set_day_granularity(true);
+    assert!(facade.set_due(id, far));
+    facade.find_tasks_to_recall();
+    assert_eq!(facade.tasks_to_complete(), 0);
+    assert!(facade.until_next_repetition().is_some());
+    assert!(matches!(facade.status(), FacadeStatus::AllDone { .. }));
+}
+
+#[test]
+fn reported_confidence_requeues_the_task() {
+    let mut facade = Facade::<Dummy>::new("confidence".to_owned(), 0.9);
+    for question in ["sure", "unsure"] {
+        facade.create_task(card(question));
+    }
+    for confidence in [0.9, 0.2] {
+        facade
+            .complete_task_with_confidence(&mut |_, _| Ok((answer(true), Some(confidence))))
+            .unwrap();
+    }
+    let confidences = (facade.review_log().iter())
+        .map(|r| r.confidence)
+        .collect::<Vec<_>>();
+    assert_eq!(confidences, [Some(0.9), Some(0.2)]);
+    let unsure = facade.review_log().iter().last().unwrap().task_id;
+    assert_eq!(facade.tasks_to_complete(), 1);
+    facade
+        .complete_task(&mut |id, _| {
+            assert_eq!(id, unsure);
+            Ok(answer(true))
+        })
+        .unwrap();
+}
+
+#[test]
+fn schedule_follows_only_the_chosen_reviewer() {
+    let mut facade = Facade::<Dummy>::new("reviewers".to_owned(), 0.9);
+    facade.create_task(card("question"));
+    let id = facade.iter().next().unwrap().1;
+    facade.set_reviewer(Some("alice".to_owned()));
+    facade.complete_task(&mut |_, _| Ok(answer(true))).unwrap();
+    assert_eq!(facade.tasks_to_complete(), 0);
+
+    facade.set_schedule_reviewer(Some("bob".to_owned()));
+    assert_eq!(facade.tasks_to_complete(), 1);
+    facade.set_reviewer(Some("bob".to_owned()));
+    facade.complete_task(&mut |_, _| Ok(answer(false))).unwrap();
+    let bob_due = facade.explain_schedule(id).unwrap().due;
+
+    facade.set_schedule_reviewer(Some("alice".to_owned()));
+    let alice_due = facade.explain_schedule(id).unwrap().due;
+    assert!(alice_due >= bob_due + Duration::from_secs(50));
+    assert_eq!(facade.tasks_to_complete(), 0);
+
+    facade.set_schedule_reviewer(None);
+    assert!(facade.explain_schedule(id).unwrap().due < alice_due);
+}
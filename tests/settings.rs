@@ -0,0 +1,32 @@
+mod support;
+
+use ssr_core::tasks_facade::TasksFacade;
+use ssr_facade::{Facade, SettingValue};
+use support::{Dummy, card};
+
+#[test]
+fn settings_change_in_place_without_touching_tasks() {
+    let mut facade = Facade::<Dummy>::new("settings".to_owned(), 0.9);
+    facade.create_task(card("question"));
+    let epoch = facade.epoch();
+
+    assert_eq!(facade.settings_mut().set("ui.theme", "dark"), None);
+    facade.settings_mut().set("ui.font_size", 14);
+    facade.settings_mut().set("sync.enabled", true);
+    assert_eq!(
+        facade.settings_mut().set("ui.theme", "light"),
+        Some(SettingValue::from("dark"))
+    );
+    let ui = facade.settings().namespace("ui").collect::<Vec<_>>();
+    assert_eq!(
+        ui,
+        [
+            ("font_size", &SettingValue::Int(14)),
+            ("theme", &SettingValue::from("light")),
+        ]
+    );
+    assert_eq!(facade.settings_mut().clear_namespace("ui"), 2);
+    assert_eq!(facade.settings().iter().count(), 1);
+    assert_eq!(facade.epoch(), epoch);
+    assert_eq!(facade.tasks_to_complete(), 1);
+}
@@ -0,0 +1,86 @@
+mod support;
+
+use std::time::Duration;
+
+use ssr_core::tasks_facade::TasksFacade;
+use ssr_facade::{AutoTagPolicy, ContentLimit, Facade, IdGenerator, OversizePolicy, Template};
+use support::{Dummy, card, value::to_value};
+
+/// Serialized fields that describe tasks, history or the deck itself rather
+/// than how it is scheduled.
+const NOT_SETTINGS: [&str; 16] = [
+    "name",
+    "tasks_pool",
+    "tasks_to_recall",
+    "format_version",
+    "last_optimized",
+    "epoch",
+    "deleted",
+    "review_log",
+    "trash",
+    "media",
+    "next_position",
+    "stats_reviewer",
+    "schedule_reviewer",
+    "settings",
+    "day",
+    "children",
+];
+
+#[test]
+fn split_off_copies_every_scheduling_setting() {
+    let mut facade = Facade::<Dummy>::new("deck".to_owned(), 0.8);
+    for question in ["first", "second"] {
+        facade.create_task(card(question));
+    }
+    let ids = facade.iter().map(|(_, id)| id).collect::<Vec<_>>();
+    facade.add_tag(ids[1], "verbs");
+    let hour = Duration::from_secs(3600);
+    facade.add_parameter_group("grammar");
+    facade.set_min_interval(hour);
+    facade.set_max_interval_growth(Some(3.));
+    facade.set_deterministic(Some(9));
+    facade.set_utc_offset_secs(3600);
+    facade.set_rollover_hour(5);
+    facade.set_day_granularity(true);
+    facade.set_id_generator(IdGenerator::UuidV7);
+    facade.set_content_limit(Some(ContentLimit {
+        max_bytes: 4096,
+        policy: OversizePolicy::Reject,
+    }));
+    facade.set_prerequisite_maturity(2 * hour);
+    facade.add_tag_prerequisite("verbs", "nouns");
+    facade.set_sibling_spacing(Some(3 * hour));
+    facade.set_tag_interval_multiplier("verbs", Some(1.5));
+    facade.set_learn_ahead(4 * hour);
+    facade.set_review_ahead_cap(Some(5 * hour));
+    facade.set_new_per_day(Some(7));
+    facade.set_reviews_per_day(Some(70));
+    facade.set_min_reviews_for_optimize(12);
+    facade.exclude_tag_from_optimization("verbs");
+    facade.set_auto_tag_policy(AutoTagPolicy {
+        leech_lapses: Some(4),
+        easy_streak: Some(6),
+    });
+    let template = card("{word}");
+    facade
+        .set_template(
+            "words",
+            Template {
+                blocks: template.blocks,
+                answer: template.answer,
+            },
+        )
+        .unwrap();
+    facade.set_trash_retention(6 * hour);
+
+    let split = facade.split_off("verbs", |_, id| id == ids[1]);
+    assert_eq!(split.tasks_total(), 1);
+    assert_eq!(split.tags(ids[1]).unwrap().len(), 1);
+    let (original, split) = (to_value(&facade), to_value(&split));
+    for key in original.keys() {
+        if !NOT_SETTINGS.contains(&key.as_str()) {
+            assert_eq!(original.get(&key), split.get(&key), "`{key}` differs");
+        }
+    }
+}
@@ -0,0 +1,54 @@
+mod support;
+
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::{Duration, SystemTime},
+};
+
+use ssr_core::tasks_facade::TasksFacade;
+use ssr_facade::{Facade, SimulationConfig};
+use support::{Dummy, answer, card};
+
+#[test]
+fn stats_handle_zero_bins_and_frozen_reviews() {
+    static NOW: AtomicU64 = AtomicU64::new(1_700_000_000);
+    let mut facade = Facade::<Dummy>::new("stats".to_owned(), 0.9);
+    facade.set_clock(Some(|| {
+        SystemTime::UNIX_EPOCH + Duration::from_secs(NOW.load(Ordering::Relaxed))
+    }));
+    facade.create_task(card("question"));
+    facade.complete_task(&mut |_, _| Ok(answer(true))).unwrap();
+    let id = facade.iter().next().unwrap().1;
+    facade
+        .complete_early(id, &mut |_, _| Ok(answer(true)))
+        .unwrap();
+    assert!(facade.calibration(0).is_empty());
+    let day = Duration::from_secs(86400);
+    assert!(facade.freeze(
+        id,
+        SystemTime::UNIX_EPOCH + Duration::from_secs(1_800_000_000)
+    ));
+    NOW.fetch_add(3600, Ordering::Relaxed);
+    let curve = facade.forgetting_curve(id, day, day).unwrap();
+    facade
+        .complete_early(id, &mut |_, _| Ok(answer(true)))
+        .unwrap();
+    assert_eq!(facade.forgetting_curve(id, day, day).unwrap(), curve);
+}
+
+#[test]
+fn simulation_tolerates_any_grade_probability() {
+    let mut facade = Facade::<Dummy>::new("simulate".to_owned(), 0.9);
+    facade.create_task(card("question"));
+    let config = SimulationConfig {
+        days: 3,
+        new_per_day: 1,
+        seed: 5,
+    };
+    for probability in [f64::NAN, -1., 2., f64::INFINITY] {
+        let simulation = facade.simulate(config, |_| probability);
+        assert_eq!(simulation.days.len(), 3);
+    }
+    let failing = facade.simulate(config, |_| f64::NAN);
+    assert!(failing.days.iter().all(|day| day.passed == 0));
+}
@@ -0,0 +1,78 @@
+// Every test crate includes this module, but not all of them use every helper.
+#![allow(dead_code)]
+
+pub mod value;
+
+use std::time::{Duration, SystemTime};
+
+use s_text_input_f::{Block, BlocksWithAnswer, ParagraphItem};
+use serde::{Deserialize, Serialize};
+use ssr_core::task::Task;
+use ssr_facade::ConfidenceTask;
+
+/// Task doubling its interval on every correct answer.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Dummy {
+    question: String,
+    last: Option<SystemTime>,
+    interval: u64,
+}
+
+impl Task<'_> for Dummy {
+    type SharedState = ();
+
+    fn new(input: BlocksWithAnswer) -> Self {
+        Self {
+            question: format!("{:?}", input.blocks),
+            last: None,
+            interval: 0,
+        }
+    }
+    fn get_blocks(&self) -> BlocksWithAnswer {
+        card(&self.question)
+    }
+    fn next_repetition(&self, _shared_state: &(), _retrievability: f64) -> SystemTime {
+        self.last.map_or(SystemTime::UNIX_EPOCH, |last| {
+            last + Duration::from_secs(self.interval)
+        })
+    }
+    fn complete(
+        &mut self,
+        _shared_state: &mut (),
+        _desired_retention: f64,
+        interaction: &mut impl FnMut(Vec<Block>) -> std::io::Result<Vec<Vec<String>>>,
+    ) -> std::io::Result<()> {
+        let response = interaction(self.get_blocks().blocks)?;
+        self.last = Some(SystemTime::now());
+        self.interval = if response[0][0] == "a" {
+            (self.interval * 2).max(60)
+        } else {
+            1
+        };
+        Ok(())
+    }
+}
+
+/// Unsure answers are asked again right away.
+impl ConfidenceTask<'_> for Dummy {
+    fn confidence_reported(&mut self, _shared_state: &mut (), confidence: f64) {
+        if confidence < 0.5 {
+            self.interval = 0;
+        }
+    }
+}
+
+pub fn card(question: &str) -> BlocksWithAnswer {
+    BlocksWithAnswer {
+        blocks: vec![Block::Paragraph(vec![
+            ParagraphItem::Text(question.to_owned()),
+            ParagraphItem::Placeholder,
+        ])],
+        answer: vec![vec!["a".to_owned()]],
+    }
+}
+
+/// Response of [`Dummy`] to a review: correct or not.
+pub fn answer(correct: bool) -> Vec<Vec<String>> {
+    vec![vec![if correct { "a" } else { "b" }.to_owned()]]
+}
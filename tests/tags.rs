@@ -0,0 +1,29 @@
+mod support;
+
+use ssr_core::tasks_facade::TasksFacade;
+use ssr_facade::{AutoTagPolicy, Facade, LEECH_TAG};
+use support::{Dummy, answer, card};
+
+#[test]
+fn auto_tags_touch_only_tasks_whose_tags_change() {
+    let mut facade = Facade::<Dummy>::new("tags".to_owned(), 0.9);
+    for question in ["one", "two", "three"] {
+        facade.create_task(card(question));
+    }
+    let policy = AutoTagPolicy {
+        leech_lapses: Some(1),
+        easy_streak: None,
+    };
+    facade.set_auto_tag_policy(policy.clone());
+    let epoch = facade.epoch();
+    facade.set_auto_tag_policy(policy);
+    assert_eq!(facade.epoch(), epoch);
+
+    facade.complete_task(&mut |_, _| Ok(answer(false))).unwrap();
+    let leech = facade.review_log().iter().last().unwrap().task_id;
+    assert!(facade.tags(leech).unwrap().contains(LEECH_TAG));
+    let epoch = facade.epoch();
+    facade.set_auto_tag_policy(AutoTagPolicy::default());
+    assert!(!facade.tags(leech).unwrap().contains(LEECH_TAG));
+    assert_eq!(facade.epoch(), epoch + 1);
+}
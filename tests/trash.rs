@@ -0,0 +1,44 @@
+mod support;
+
+use std::time::{Duration, SystemTime};
+
+use ssr_core::tasks_facade::TasksFacade;
+use ssr_facade::{Facade, IdGenerator};
+use support::{Dummy, answer, card};
+
+#[test]
+fn restore_keeps_trashed_task_on_id_collision() {
+    let mut facade = Facade::<Dummy>::new("trash".to_owned(), 0.9);
+    facade.set_id_generator(IdGenerator::ContentHash);
+    facade.create_task(card("question"));
+    facade.complete_task(&mut |_, _| Ok(answer(true))).unwrap();
+    let id = facade.iter().next().unwrap().1;
+    assert!(facade.remove(id));
+    assert!(!facade.remove(id));
+
+    facade.create_task(card("question"));
+    assert_eq!(facade.iter().next().unwrap().1, id);
+    assert!(!facade.restore(id));
+    assert_eq!(facade.trash().count(), 1);
+
+    assert!(facade.remove(id));
+    assert!(facade.restore(id));
+    assert_eq!(facade.task_history(id).len(), 1);
+}
+
+#[test]
+fn restore_brings_back_due_overrides() {
+    let mut facade = Facade::<Dummy>::new("trash".to_owned(), 0.9);
+    facade.create_task(card("question"));
+    let id = facade.iter().next().unwrap().1;
+    let due = SystemTime::now() + Duration::from_secs(86400);
+    assert!(facade.set_due(id, due));
+    assert!(facade.remove(id));
+    assert!(facade.review_log().overrides().is_empty());
+
+    assert!(facade.restore(id));
+    let overrides = facade.review_log().overrides();
+    assert_eq!(overrides.len(), 1);
+    assert_eq!(overrides[0].task_id, id);
+    assert_eq!(facade.explain_schedule(id).unwrap().due, due);
+}